@@ -0,0 +1,7 @@
+-- Add migration script here
+ALTER TABLE todos
+    ADD COLUMN due_date TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN recurrence TEXT,
+    ADD COLUMN series_id UUID;
+
+CREATE INDEX idx_todos_series_id ON todos (series_id);
\ No newline at end of file
@@ -0,0 +1,15 @@
+-- Add migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- 'simple' 設定は言語依存の語幹処理を行わないため、英語以外のタイトルも壊さずにトークン化できる
+ALTER TABLE todos
+    ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', coalesce(title, '')), 'A') ||
+        setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX idx_todos_search_vector ON todos USING GIN (search_vector);
+
+-- 日本語など空白で区切られないテキストは tsvector では語に分割できないため、
+-- トライグラムによる部分一致（ILIKE）で補う
+CREATE INDEX idx_todos_search_trgm ON todos USING GIN ((title || ' ' || coalesce(description, '')) gin_trgm_ops);
\ No newline at end of file
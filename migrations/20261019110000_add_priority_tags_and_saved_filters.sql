@@ -0,0 +1,15 @@
+-- Add migration script here
+-- priority: 0 = low, 1 = normal, 2 = high, 3 = urgent
+ALTER TABLE todos
+    ADD COLUMN priority SMALLINT NOT NULL DEFAULT 1,
+    ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX idx_todos_tags ON todos USING GIN (tags);
+
+CREATE TABLE saved_filters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    name TEXT NOT NULL,
+    query TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
\ No newline at end of file
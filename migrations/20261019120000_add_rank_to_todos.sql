@@ -0,0 +1,11 @@
+-- Add migration script here
+-- 並べ替え用の辞書順キー。バイト順で比較するため COLLATE "C" を指定する
+ALTER TABLE todos ADD COLUMN rank TEXT COLLATE "C";
+
+UPDATE todos t SET rank = lpad(to_hex(o.n), 8, '0') || '8'
+    FROM (SELECT id, row_number() OVER (ORDER BY id) AS n FROM todos) o
+    WHERE t.id = o.id;
+
+ALTER TABLE todos ALTER COLUMN rank SET NOT NULL;
+
+CREATE INDEX idx_todos_rank ON todos (rank, id);
\ No newline at end of file
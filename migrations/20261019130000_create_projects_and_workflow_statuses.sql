@@ -0,0 +1,20 @@
+-- Add migration script here
+CREATE TABLE projects (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    name TEXT NOT NULL,
+    -- { "statuses": [{ "key", "name", "done" }], "transitions": [{ "from", "to" }] }
+    workflow JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
+
+-- completed はステータスから導出される値として引き続き保持する
+ALTER TABLE todos
+    ADD COLUMN project_id UUID REFERENCES projects (id) ON DELETE RESTRICT,
+    ADD COLUMN status TEXT NOT NULL DEFAULT 'todo';
+
+UPDATE todos SET status = CASE WHEN completed THEN 'done' ELSE 'todo' END;
+
+ALTER TABLE todos ALTER COLUMN status DROP DEFAULT;
+
+CREATE INDEX idx_todos_project_status ON todos (project_id, status);
\ No newline at end of file
@@ -0,0 +1,23 @@
+-- Add migration script here
+-- Todo を削除するとコメントと編集履歴もまとめて削除する
+CREATE TABLE comments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    todo_id UUID NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+    author TEXT NOT NULL,
+    -- Markdown
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    edited_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_comments_todo_id ON comments (todo_id, created_at);
+
+-- 編集前の本文。created_at はその本文が書かれた日時
+CREATE TABLE comment_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    comment_id UUID NOT NULL REFERENCES comments (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX idx_comment_revisions_comment_id ON comment_revisions (comment_id, created_at);
@@ -0,0 +1,18 @@
+-- Add migration script here
+-- Todo か請求書のどちらか一方に添付する。本体は BlobStore に storage_key で保存する
+-- 親の削除で行はまとめて消えるが、BlobStore 上の本体は残る
+CREATE TABLE attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    todo_id UUID REFERENCES todos (id) ON DELETE CASCADE,
+    invoice_id UUID REFERENCES invoices (id) ON DELETE CASCADE,
+    file_name TEXT NOT NULL,
+    -- アップロードされた内容から判定した Content-Type
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    CHECK (num_nonnulls (todo_id, invoice_id) = 1)
+);
+
+CREATE INDEX idx_attachments_todo_id ON attachments (todo_id) WHERE todo_id IS NOT NULL;
+CREATE INDEX idx_attachments_invoice_id ON attachments (invoice_id) WHERE invoice_id IS NOT NULL;
@@ -0,0 +1,40 @@
+-- Add migration script here
+CREATE TABLE users (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
+
+CREATE UNIQUE INDEX idx_users_email ON users (lower(email));
+
+CREATE TABLE todo_assignees (
+    todo_id UUID NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    assigned_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    PRIMARY KEY (todo_id, user_id)
+);
+
+CREATE INDEX idx_todo_assignees_user_id ON todo_assignees (user_id);
+
+CREATE TABLE todo_watchers (
+    todo_id UUID NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    PRIMARY KEY (todo_id, user_id)
+);
+
+CREATE INDEX idx_todo_watchers_user_id ON todo_watchers (user_id);
+
+-- 担当者の追加・解除の履歴。actor_id は変更したユーザー（不明な場合は NULL）
+CREATE TABLE todo_assignment_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    todo_id UUID NOT NULL REFERENCES todos (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    action TEXT NOT NULL CHECK (action IN ('assigned', 'unassigned')),
+    actor_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
+
+CREATE INDEX idx_todo_assignment_history_todo_id ON todo_assignment_history (todo_id, created_at);
@@ -0,0 +1,7 @@
+-- Add migration script here
+-- 削除はゴミ箱への移動とし、保持期間を過ぎたものをパージジョブが物理削除する
+ALTER TABLE todos ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE invoices ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_todos_deleted_at ON todos (deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_invoices_deleted_at ON invoices (deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,95 @@
+-- Add migration script here
+-- Todo・請求書の変更履歴。変更と同じトランザクションでトリガーが記録する
+CREATE TABLE audit_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    -- 変更されたテーブル名（todos / invoices）
+    entity_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('create', 'update', 'delete', 'restore', 'purge')),
+    actor_id UUID,
+    -- 1 回の API 呼び出しで記録された一連のイベントに共通の ID
+    operation_id UUID,
+    -- { "列名": { "before": 変更前, "after": 変更後 } }
+    changes JSONB NOT NULL,
+    -- 同じトランザクション内でも順序が分かるよう、文の実行時刻を使う。
+    -- 取り消しの期限判定に使うため、セッションのタイムゾーンによらない時刻で記録する
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT clock_timestamp() NOT NULL
+);
+
+CREATE INDEX idx_audit_events_entity ON audit_events (entity_type, entity_id, created_at);
+CREATE INDEX idx_audit_events_operation_id ON audit_events (operation_id) WHERE operation_id IS NOT NULL;
+
+-- 実行者と操作 ID は、アプリケーションがトランザクションごとに set_config で渡す。
+-- トリガーの引数に渡した列は差分に含めない
+CREATE FUNCTION record_audit_event () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+    old_row JSONB := '{}';
+    new_row JSONB := '{}';
+    changes JSONB;
+    action TEXT;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD) - TG_ARGV;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW) - TG_ARGV;
+    END IF;
+
+    SELECT coalesce(jsonb_object_agg(key, jsonb_build_object('before', old_row -> key, 'after', new_row -> key)), '{}')
+        INTO changes
+        FROM jsonb_object_keys(old_row || new_row) AS key
+        WHERE coalesce(old_row -> key, 'null') IS DISTINCT FROM coalesce(new_row -> key, 'null');
+
+    IF TG_OP = 'INSERT' THEN
+        action := 'create';
+    ELSIF TG_OP = 'DELETE' THEN
+        action := 'purge';
+    ELSIF OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN
+        action := 'delete';
+    ELSIF OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NULL THEN
+        action := 'restore';
+    ELSE
+        action := 'update';
+    END IF;
+
+    -- 差分に含めない列だけが変わった更新は記録しない
+    IF action = 'update' AND changes = '{}' THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO audit_events (entity_type, entity_id, action, actor_id, operation_id, changes)
+    VALUES (
+        TG_TABLE_NAME,
+        ((old_row || new_row) ->> 'id')::UUID,
+        action,
+        nullif(current_setting('app.actor_id', TRUE), '')::UUID,
+        nullif(current_setting('app.operation_id', TRUE), '')::UUID,
+        changes
+    );
+    RETURN NULL;
+END;
+$$;
+
+-- rank は並び順の調整で一斉に書き換わるため対象外
+CREATE TRIGGER todos_audit
+    AFTER INSERT OR UPDATE OR DELETE ON todos
+    FOR EACH ROW EXECUTE FUNCTION record_audit_event ('updated_at', 'search_vector', 'rank');
+
+CREATE TRIGGER invoices_audit
+    AFTER INSERT OR UPDATE OR DELETE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION record_audit_event ('updated_at');
+
+-- 記録したイベントは変更・削除できない
+CREATE FUNCTION forbid_audit_event_changes () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+    RAISE EXCEPTION 'audit events are immutable';
+END;
+$$;
+
+CREATE TRIGGER audit_events_immutable
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION forbid_audit_event_changes ();
+
+CREATE TRIGGER audit_events_no_truncate
+    BEFORE TRUNCATE ON audit_events
+    FOR EACH STATEMENT EXECUTE FUNCTION forbid_audit_event_changes ();
@@ -0,0 +1,31 @@
+-- Add migration script here
+-- 楽観的排他制御用の版数。ETag / If-Match に使う
+ALTER TABLE todos ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE invoices ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+
+-- 更新のたびに版数を上げる
+CREATE FUNCTION bump_version () RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END;
+$$;
+
+CREATE TRIGGER todos_bump_version
+    BEFORE UPDATE ON todos
+    FOR EACH ROW EXECUTE FUNCTION bump_version ();
+
+CREATE TRIGGER invoices_bump_version
+    BEFORE UPDATE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION bump_version ();
+
+-- 版数は変更履歴の差分に含めない
+DROP TRIGGER todos_audit ON todos;
+CREATE TRIGGER todos_audit
+    AFTER INSERT OR UPDATE OR DELETE ON todos
+    FOR EACH ROW EXECUTE FUNCTION record_audit_event ('updated_at', 'search_vector', 'rank', 'version');
+
+DROP TRIGGER invoices_audit ON invoices;
+CREATE TRIGGER invoices_audit
+    AFTER INSERT OR UPDATE OR DELETE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION record_audit_event ('updated_at', 'version');
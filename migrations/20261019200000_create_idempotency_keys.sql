@@ -0,0 +1,15 @@
+-- Add migration script here
+-- Idempotency-Key ヘッダ付きの POST リクエストと、そのレスポンスを記録する。
+-- status_code が NULL の行は処理中を表す。キーはユーザーごとに別々に扱う
+CREATE TABLE idempotency_keys (
+  user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  key TEXT NOT NULL,
+  request_hash TEXT NOT NULL,
+  status_code SMALLINT,
+  response_headers JSONB,
+  response_body BYTEA,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  PRIMARY KEY (user_id, key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys (created_at);
@@ -0,0 +1,18 @@
+-- Add migration script here
+-- パスワードを設定していないユーザー（管理 API で作成したユーザー）はログインできない
+ALTER TABLE users ADD COLUMN password_hash TEXT;
+
+-- リフレッシュトークンはハッシュだけを保存する。使うたびに同じ family_id の新しいトークンに置き換え、
+-- 置き換え済みのトークンが再び使われた場合は漏えいとみなして family ごと無効にする
+CREATE TABLE refresh_tokens (
+  id UUID PRIMARY KEY,
+  user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  family_id UUID NOT NULL,
+  token_hash TEXT NOT NULL UNIQUE,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  revoked_at TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_refresh_tokens_family_id ON refresh_tokens (family_id);
+CREATE INDEX idx_refresh_tokens_expires_at ON refresh_tokens (expires_at);
@@ -0,0 +1,93 @@
+-- Add migration script here
+-- Todo・請求書は作成したユーザーだけが参照・変更できる。絞り込みはリポジトリのクエリで行う。
+-- 既存の行は末尾で最初に登録されたユーザーの所有にする
+ALTER TABLE todos ADD COLUMN owner_id UUID REFERENCES users (id);
+ALTER TABLE invoices ADD COLUMN owner_id UUID REFERENCES users (id);
+
+CREATE INDEX idx_todos_owner_id ON todos (owner_id, rank, id);
+CREATE INDEX idx_invoices_owner_id ON invoices (owner_id, id);
+
+-- 物理削除した後も履歴を所有者で絞り込めるよう、イベントにも所有者を記録する
+ALTER TABLE audit_events ADD COLUMN owner_id UUID;
+
+CREATE INDEX idx_audit_events_owner_id ON audit_events (owner_id, entity_type, entity_id);
+
+CREATE OR REPLACE FUNCTION record_audit_event () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+    old_row JSONB := '{}';
+    new_row JSONB := '{}';
+    changes JSONB;
+    action TEXT;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD) - TG_ARGV;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW) - TG_ARGV;
+    END IF;
+
+    SELECT coalesce(jsonb_object_agg(key, jsonb_build_object('before', old_row -> key, 'after', new_row -> key)), '{}')
+        INTO changes
+        FROM jsonb_object_keys(old_row || new_row) AS key
+        WHERE coalesce(old_row -> key, 'null') IS DISTINCT FROM coalesce(new_row -> key, 'null');
+
+    IF TG_OP = 'INSERT' THEN
+        action := 'create';
+    ELSIF TG_OP = 'DELETE' THEN
+        action := 'purge';
+    ELSIF OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN
+        action := 'delete';
+    ELSIF OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NULL THEN
+        action := 'restore';
+    ELSE
+        action := 'update';
+    END IF;
+
+    -- 差分に含めない列だけが変わった更新は記録しない
+    IF action = 'update' AND changes = '{}' THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO audit_events (entity_type, entity_id, action, actor_id, operation_id, owner_id, changes)
+    VALUES (
+        TG_TABLE_NAME,
+        ((old_row || new_row) ->> 'id')::UUID,
+        action,
+        nullif(current_setting('app.actor_id', TRUE), '')::UUID,
+        nullif(current_setting('app.operation_id', TRUE), '')::UUID,
+        ((old_row || new_row) ->> 'owner_id')::UUID,
+        changes
+    );
+    RETURN NULL;
+END;
+$$;
+
+-- 所有者のいない既存の行（変更履歴を含む）は、最初に登録されたユーザーの所有にする。ユーザーがいなければ引き継ぎ用のユーザーを作る
+-- （パスワードがないためログインできない。メールアドレスを実際の管理者のものに変えてから OpenID Connect でログインする）。
+-- 移行による書き込みは版数・変更履歴に反映しない
+ALTER TABLE todos DISABLE TRIGGER todos_bump_version, DISABLE TRIGGER todos_audit;
+ALTER TABLE invoices DISABLE TRIGGER invoices_bump_version, DISABLE TRIGGER invoices_audit;
+ALTER TABLE audit_events DISABLE TRIGGER audit_events_immutable;
+
+DO $$
+DECLARE
+    legacy_owner UUID;
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM todos WHERE owner_id IS NULL)
+        AND NOT EXISTS (SELECT 1 FROM invoices WHERE owner_id IS NULL)
+        AND NOT EXISTS (SELECT 1 FROM audit_events WHERE owner_id IS NULL) THEN
+        RETURN;
+    END IF;
+    SELECT id INTO legacy_owner FROM users ORDER BY created_at, id LIMIT 1;
+    IF legacy_owner IS NULL THEN
+        INSERT INTO users (name, email) VALUES ('Legacy data owner', 'legacy-owner@localhost') RETURNING id INTO legacy_owner;
+    END IF;
+    UPDATE todos SET owner_id = legacy_owner WHERE owner_id IS NULL;
+    UPDATE invoices SET owner_id = legacy_owner WHERE owner_id IS NULL;
+    UPDATE audit_events SET owner_id = legacy_owner WHERE owner_id IS NULL;
+END;
+$$;
+
+ALTER TABLE todos ENABLE TRIGGER todos_bump_version, ENABLE TRIGGER todos_audit;
+ALTER TABLE invoices ENABLE TRIGGER invoices_bump_version, ENABLE TRIGGER invoices_audit;
+ALTER TABLE audit_events ENABLE TRIGGER audit_events_immutable;
@@ -0,0 +1,162 @@
+-- Add migration script here
+-- Todo・プロジェクト・請求書は組織ごとに分かれる。メンバーは組織内のデータをすべて参照できる。
+-- 各ユーザーには本人だけが所属する個人用の組織を用意し、組織を指定しないリクエストはそこで扱う
+CREATE TABLE organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid (),
+    name TEXT NOT NULL,
+    -- 個人用の組織の持ち主。共有の組織は NULL
+    personal_user_id UUID UNIQUE REFERENCES users (id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
+
+CREATE TABLE organization_memberships (
+    organization_id UUID NOT NULL REFERENCES organizations (id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    role TEXT NOT NULL CHECK (role IN ('owner', 'admin', 'member')),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL,
+    PRIMARY KEY (organization_id, user_id)
+);
+
+CREATE INDEX idx_organization_memberships_user_id ON organization_memberships (user_id);
+
+-- 招待トークンはハッシュだけを保存する。招待されたメールアドレスのユーザーだけが受け入れられる
+CREATE TABLE organization_invitations (
+    id UUID PRIMARY KEY,
+    organization_id UUID NOT NULL REFERENCES organizations (id) ON DELETE CASCADE,
+    email TEXT NOT NULL,
+    role TEXT NOT NULL CHECK (role IN ('admin', 'member')),
+    token_hash TEXT NOT NULL UNIQUE,
+    invited_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    accepted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT (now() AT TIME ZONE 'Asia/Tokyo') NOT NULL
+);
+
+CREATE INDEX idx_organization_invitations_organization_id ON organization_invitations (organization_id, created_at);
+
+-- ユーザーの作成と同時に個人用の組織を作る（管理 API と登録のどちらで作成した場合も）
+CREATE FUNCTION create_personal_organization () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+    organization_id UUID;
+BEGIN
+    INSERT INTO organizations (name, personal_user_id) VALUES (NEW.name, NEW.id) RETURNING id INTO organization_id;
+    INSERT INTO organization_memberships (organization_id, user_id, role) VALUES (organization_id, NEW.id, 'owner');
+    RETURN NULL;
+END;
+$$;
+
+CREATE TRIGGER users_personal_organization AFTER INSERT ON users
+    FOR EACH ROW EXECUTE FUNCTION create_personal_organization ();
+
+INSERT INTO organizations (name, personal_user_id) SELECT name, id FROM users;
+INSERT INTO organization_memberships (organization_id, user_id, role)
+    SELECT id, personal_user_id, 'owner' FROM organizations WHERE personal_user_id IS NOT NULL;
+
+-- owner_id は作成したユーザーとして残し、参照範囲は organization_id で決める
+ALTER TABLE todos ADD COLUMN organization_id UUID REFERENCES organizations (id);
+ALTER TABLE invoices ADD COLUMN organization_id UUID REFERENCES organizations (id);
+ALTER TABLE projects ADD COLUMN organization_id UUID REFERENCES organizations (id);
+
+-- 移行による書き込みは版数・変更履歴に反映しない
+ALTER TABLE todos DISABLE TRIGGER todos_bump_version, DISABLE TRIGGER todos_audit;
+ALTER TABLE invoices DISABLE TRIGGER invoices_bump_version, DISABLE TRIGGER invoices_audit;
+
+UPDATE todos SET organization_id = o.id FROM organizations o WHERE o.personal_user_id = todos.owner_id;
+UPDATE invoices SET organization_id = o.id FROM organizations o WHERE o.personal_user_id = invoices.owner_id;
+
+ALTER TABLE todos ENABLE TRIGGER todos_bump_version, ENABLE TRIGGER todos_audit;
+ALTER TABLE invoices ENABLE TRIGGER invoices_bump_version, ENABLE TRIGGER invoices_audit;
+-- プロジェクトは属する Todo の組織に移す。Todo が複数の組織にまたがる場合は最初に作成された Todo の組織にする
+UPDATE projects SET organization_id = (
+    SELECT t.organization_id FROM todos t WHERE t.project_id = projects.id AND t.organization_id IS NOT NULL ORDER BY t.id LIMIT 1
+);
+-- Todo のないプロジェクトは、既存の Todo・請求書を引き継いだユーザー（最初に登録されたユーザー）の個人用の組織に移す。
+-- ユーザーがいなければ引き継ぎ用のユーザーを作る（20261019220000 と同じ）
+DO $$
+DECLARE
+    legacy_owner UUID;
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM projects WHERE organization_id IS NULL) THEN
+        RETURN;
+    END IF;
+    SELECT id INTO legacy_owner FROM users ORDER BY created_at, id LIMIT 1;
+    IF legacy_owner IS NULL THEN
+        INSERT INTO users (name, email) VALUES ('Legacy data owner', 'legacy-owner@localhost') RETURNING id INTO legacy_owner;
+    END IF;
+    UPDATE projects SET organization_id = o.id FROM organizations o
+        WHERE o.personal_user_id = legacy_owner AND projects.organization_id IS NULL;
+END;
+$$;
+
+-- どの組織にも属さない行は誰からも見えなくなるため、組織を必須にする
+ALTER TABLE todos ALTER COLUMN organization_id SET NOT NULL;
+ALTER TABLE invoices ALTER COLUMN organization_id SET NOT NULL;
+ALTER TABLE projects ALTER COLUMN organization_id SET NOT NULL;
+
+DROP INDEX idx_todos_owner_id;
+DROP INDEX idx_invoices_owner_id;
+CREATE INDEX idx_todos_organization_id ON todos (organization_id, rank, id);
+CREATE INDEX idx_invoices_organization_id ON invoices (organization_id, id);
+CREATE INDEX idx_projects_organization_id ON projects (organization_id);
+
+ALTER TABLE audit_events ADD COLUMN organization_id UUID;
+
+-- 既存のイベントにだけ組織を補う
+ALTER TABLE audit_events DISABLE TRIGGER audit_events_immutable;
+UPDATE audit_events SET organization_id = o.id FROM organizations o WHERE o.personal_user_id = audit_events.owner_id;
+ALTER TABLE audit_events ENABLE TRIGGER audit_events_immutable;
+
+DROP INDEX idx_audit_events_owner_id;
+CREATE INDEX idx_audit_events_organization_id ON audit_events (organization_id, entity_type, entity_id);
+
+CREATE OR REPLACE FUNCTION record_audit_event () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+    old_row JSONB := '{}';
+    new_row JSONB := '{}';
+    changes JSONB;
+    action TEXT;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD) - TG_ARGV;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW) - TG_ARGV;
+    END IF;
+
+    SELECT coalesce(jsonb_object_agg(key, jsonb_build_object('before', old_row -> key, 'after', new_row -> key)), '{}')
+        INTO changes
+        FROM jsonb_object_keys(old_row || new_row) AS key
+        WHERE coalesce(old_row -> key, 'null') IS DISTINCT FROM coalesce(new_row -> key, 'null');
+
+    IF TG_OP = 'INSERT' THEN
+        action := 'create';
+    ELSIF TG_OP = 'DELETE' THEN
+        action := 'purge';
+    ELSIF OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN
+        action := 'delete';
+    ELSIF OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NULL THEN
+        action := 'restore';
+    ELSE
+        action := 'update';
+    END IF;
+
+    -- 差分に含めない列だけが変わった更新は記録しない
+    IF action = 'update' AND changes = '{}' THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO audit_events (entity_type, entity_id, action, actor_id, operation_id, owner_id, organization_id, changes)
+    VALUES (
+        TG_TABLE_NAME,
+        ((old_row || new_row) ->> 'id')::UUID,
+        action,
+        nullif(current_setting('app.actor_id', TRUE), '')::UUID,
+        nullif(current_setting('app.operation_id', TRUE), '')::UUID,
+        ((old_row || new_row) ->> 'owner_id')::UUID,
+        ((old_row || new_row) ->> 'organization_id')::UUID,
+        changes
+    );
+    RETURN NULL;
+END;
+$$;
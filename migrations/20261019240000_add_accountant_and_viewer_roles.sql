@@ -0,0 +1,9 @@
+-- Add migration script here
+-- 請求書の発行・取り消しだけを行う accountant と、参照だけができる viewer を追加する
+ALTER TABLE organization_memberships DROP CONSTRAINT organization_memberships_role_check;
+ALTER TABLE organization_memberships ADD CONSTRAINT organization_memberships_role_check
+    CHECK (role IN ('owner', 'admin', 'accountant', 'member', 'viewer'));
+
+ALTER TABLE organization_invitations DROP CONSTRAINT organization_invitations_role_check;
+ALTER TABLE organization_invitations ADD CONSTRAINT organization_invitations_role_check
+    CHECK (role IN ('admin', 'accountant', 'member', 'viewer'));
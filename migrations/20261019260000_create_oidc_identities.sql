@@ -0,0 +1,23 @@
+-- OpenID Connect でログインしたユーザーと IdP のアカウント（iss と sub の組）の対応
+CREATE TABLE user_identities (
+  issuer TEXT NOT NULL,
+  subject TEXT NOT NULL,
+  user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  PRIMARY KEY (issuer, subject)
+);
+
+CREATE INDEX idx_user_identities_user_id ON user_identities (user_id);
+
+-- ログインの開始から IdP のコールバックまでの state と PKCE の code_verifier。コールバックで 1 度だけ使って削除する
+CREATE TABLE oidc_login_states (
+  state_hash TEXT PRIMARY KEY,
+  code_verifier TEXT NOT NULL,
+  nonce TEXT NOT NULL,
+  -- ログイン中のユーザーが IdP のアカウントを対応付ける場合のユーザー
+  link_user_id UUID REFERENCES users (id) ON DELETE CASCADE,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_oidc_login_states_expires_at ON oidc_login_states (expires_at);
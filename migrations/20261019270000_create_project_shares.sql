@@ -0,0 +1,29 @@
+-- プロジェクトを組織の外のユーザーに共有する。メールアドレス宛ての招待（1 度だけ受け入れられる）と、
+-- 知っているユーザーなら誰でも受け入れられるリンク（email が NULL）がある。トークンはハッシュだけを保存する
+CREATE TABLE project_shares (
+  id UUID PRIMARY KEY,
+  project_id UUID NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+  permission TEXT NOT NULL CHECK (permission IN ('view', 'edit')),
+  email TEXT,
+  token_hash TEXT NOT NULL UNIQUE,
+  created_by UUID REFERENCES users (id) ON DELETE SET NULL,
+  -- リンクは取り消すまで有効
+  expires_at TIMESTAMP WITH TIME ZONE,
+  accepted_at TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_project_shares_project_id ON project_shares (project_id, created_at);
+
+-- 共有を受け入れたユーザー。共有を取り消すと、その共有で受け入れたユーザーも外れる
+CREATE TABLE project_collaborators (
+  project_id UUID NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+  user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+  share_id UUID NOT NULL REFERENCES project_shares (id) ON DELETE CASCADE,
+  permission TEXT NOT NULL CHECK (permission IN ('view', 'edit')),
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+  PRIMARY KEY (project_id, user_id)
+);
+
+CREATE INDEX idx_project_collaborators_user_id ON project_collaborators (user_id);
+CREATE INDEX idx_project_collaborators_share_id ON project_collaborators (share_id);
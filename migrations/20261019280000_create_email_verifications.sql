@@ -0,0 +1,9 @@
+-- メールアドレスの変更の確認待ち。新しいアドレスに送ったトークンで確認されるまで users.email は変えない。
+-- ユーザーごとに 1 件だけで、新しく変更を申請すると置き換える
+CREATE TABLE email_verifications (
+  user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+  email TEXT NOT NULL,
+  token_hash TEXT NOT NULL UNIQUE,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
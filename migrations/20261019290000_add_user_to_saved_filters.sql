@@ -0,0 +1,11 @@
+-- Add migration script here
+-- 保存済みフィルタは作成したユーザーだけが参照・変更できる。絞り込みはリポジトリのクエリで行う
+ALTER TABLE saved_filters ADD COLUMN user_id UUID REFERENCES users (id) ON DELETE CASCADE;
+
+-- 既存のフィルタは、Todo・請求書と同じく最初に登録されたユーザーの所有にする。ユーザーがいなければ削除する
+UPDATE saved_filters SET user_id = (SELECT id FROM users ORDER BY created_at, id LIMIT 1) WHERE user_id IS NULL;
+DELETE FROM saved_filters WHERE user_id IS NULL;
+
+ALTER TABLE saved_filters ALTER COLUMN user_id SET NOT NULL;
+
+CREATE INDEX idx_saved_filters_user_id ON saved_filters (user_id, name, id);
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- 処理中のキーの期限。クライアントの切断やレスポンスの記録の失敗で処理中のまま残ったキーは、
+-- 期限を過ぎれば同じリクエストの再送で処理し直せる
+ALTER TABLE idempotency_keys ADD COLUMN locked_until TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now();
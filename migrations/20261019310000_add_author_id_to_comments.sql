@@ -0,0 +1,4 @@
+-- Add migration script here
+-- コメントの投稿者。author は投稿時点のユーザー名で、退会後も表示に使う
+-- 既存のコメントは投稿者を特定できないため NULL のままにする（owner・admin だけが削除できる）
+ALTER TABLE comments ADD COLUMN author_id UUID REFERENCES users (id) ON DELETE SET NULL;
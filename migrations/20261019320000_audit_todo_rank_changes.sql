@@ -0,0 +1,60 @@
+-- Add migration script here
+-- 並べ替え（rank の変更）も変更履歴に記録し、操作の取り消しで元の位置に戻せるようにする。
+-- 組織全体の振り直しは並び順を変えないため、トランザクションごとに app.audit_ignored_columns
+-- （カンマ区切りの列名）で差分に含めない列を追加できるようにし、振り直しでは rank を指定する
+CREATE OR REPLACE FUNCTION record_audit_event () RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+    old_row JSONB := '{}';
+    new_row JSONB := '{}';
+    changes JSONB;
+    action TEXT;
+    ignored TEXT[] := TG_ARGV || coalesce(string_to_array(nullif(current_setting('app.audit_ignored_columns', TRUE), ''), ','), '{}');
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD) - ignored;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW) - ignored;
+    END IF;
+
+    SELECT coalesce(jsonb_object_agg(key, jsonb_build_object('before', old_row -> key, 'after', new_row -> key)), '{}')
+        INTO changes
+        FROM jsonb_object_keys(old_row || new_row) AS key
+        WHERE coalesce(old_row -> key, 'null') IS DISTINCT FROM coalesce(new_row -> key, 'null');
+
+    IF TG_OP = 'INSERT' THEN
+        action := 'create';
+    ELSIF TG_OP = 'DELETE' THEN
+        action := 'purge';
+    ELSIF OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL THEN
+        action := 'delete';
+    ELSIF OLD.deleted_at IS NOT NULL AND NEW.deleted_at IS NULL THEN
+        action := 'restore';
+    ELSE
+        action := 'update';
+    END IF;
+
+    -- 差分に含めない列だけが変わった更新は記録しない
+    IF action = 'update' AND changes = '{}' THEN
+        RETURN NULL;
+    END IF;
+
+    INSERT INTO audit_events (entity_type, entity_id, action, actor_id, operation_id, owner_id, organization_id, changes)
+    VALUES (
+        TG_TABLE_NAME,
+        ((old_row || new_row) ->> 'id')::UUID,
+        action,
+        nullif(current_setting('app.actor_id', TRUE), '')::UUID,
+        nullif(current_setting('app.operation_id', TRUE), '')::UUID,
+        ((old_row || new_row) ->> 'owner_id')::UUID,
+        ((old_row || new_row) ->> 'organization_id')::UUID,
+        changes
+    );
+    RETURN NULL;
+END;
+$$;
+
+DROP TRIGGER todos_audit ON todos;
+CREATE TRIGGER todos_audit
+    AFTER INSERT OR UPDATE OR DELETE ON todos
+    FOR EACH ROW EXECUTE FUNCTION record_audit_event ('updated_at', 'search_vector', 'version');
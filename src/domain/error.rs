@@ -0,0 +1,116 @@
+use sqlx::error::ErrorKind;
+use std::fmt;
+
+use crate::domain::query::parser::ParseError;
+use crate::domain::repositories::blob_store::BlobError;
+use crate::domain::repositories::identity_provider::IdentityProviderError;
+use crate::domain::validation::ValidationErrors;
+
+// ユースケースが返すエラー。presentation::problem で RFC 9457 の problem+json に変換する
+#[derive(Debug)]
+pub enum AppError {
+  // リクエストの値が不正
+  BadRequest(String),
+  // フィルタ式の構文エラー（位置とトークンもあわせて返す）
+  InvalidFilter(ParseError),
+  // 認証されていない
+  Unauthorized(String),
+  // 認証されているが、その操作を行う権限がない
+  Forbidden(String),
+  NotFound(String),
+  // 現在の状態と矛盾する（一意制約・参照制約の違反を含む）
+  Conflict(String),
+  // 期限を過ぎていて処理できない
+  Gone(String),
+  // If-Match の版数と一致しない、または読み込んだ後に他で更新された
+  PreconditionFailed(String),
+  PayloadTooLarge(String),
+  // リクエスト本体の Content-Type に対応していない
+  UnsupportedMediaType(String),
+  // 形式は正しいが内容を処理できない
+  Validation(String),
+  // 項目ごとの検証エラー（すべての項目のエラーをまとめて返す）
+  InvalidFields(ValidationErrors),
+  // 内容はログにだけ出し、クライアントには返さない
+  Internal(String),
+}
+
+impl AppError {
+  // RowNotFound を対象に応じた NotFound にする。それ以外は From<sqlx::Error> と同じ
+  pub fn from_sqlx(err: sqlx::Error, not_found: &str) -> Self {
+    match err {
+      sqlx::Error::RowNotFound => AppError::NotFound(not_found.to_string()),
+      err => err.into(),
+    }
+  }
+}
+
+impl fmt::Display for AppError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      AppError::InvalidFilter(err) => write!(f, "{}", err),
+      AppError::InvalidFields(errors) => write!(f, "{}", errors),
+      AppError::BadRequest(message)
+      | AppError::Unauthorized(message)
+      | AppError::Forbidden(message)
+      | AppError::NotFound(message)
+      | AppError::Conflict(message)
+      | AppError::Gone(message)
+      | AppError::PreconditionFailed(message)
+      | AppError::PayloadTooLarge(message)
+      | AppError::UnsupportedMediaType(message)
+      | AppError::Validation(message)
+      | AppError::Internal(message) => write!(f, "{}", message),
+    }
+  }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<sqlx::Error> for AppError {
+  fn from(err: sqlx::Error) -> Self {
+    match err {
+      sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
+      sqlx::Error::Database(db_err) => {
+        let constraint = db_err.constraint().unwrap_or("unknown").to_string();
+        match db_err.kind() {
+          ErrorKind::UniqueViolation => AppError::Conflict(format!("already exists (constraint '{}')", constraint)),
+          // 参照先がない、または参照されているため削除できない
+          ErrorKind::ForeignKeyViolation => AppError::Conflict(format!("referenced resource conflict (constraint '{}')", constraint)),
+          ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+            AppError::Validation(format!("invalid value (constraint '{}')", constraint))
+          }
+          _ => AppError::Internal(format!("database error: {}", db_err)),
+        }
+      }
+      err => AppError::Internal(format!("database error: {}", err)),
+    }
+  }
+}
+
+impl From<BlobError> for AppError {
+  fn from(err: BlobError) -> Self {
+    AppError::Internal(err.to_string())
+  }
+}
+
+impl From<IdentityProviderError> for AppError {
+  fn from(err: IdentityProviderError) -> Self {
+    match err {
+      IdentityProviderError::InvalidGrant(message) => AppError::Unauthorized(message),
+      err => AppError::Internal(err.to_string()),
+    }
+  }
+}
+
+impl From<ParseError> for AppError {
+  fn from(err: ParseError) -> Self {
+    AppError::InvalidFilter(err)
+  }
+}
+
+impl From<ValidationErrors> for AppError {
+  fn from(errors: ValidationErrors) -> Self {
+    AppError::InvalidFields(errors)
+  }
+}
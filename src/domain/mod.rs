@@ -1,2 +1,6 @@
+pub mod error;
 pub mod models;
+pub mod policy;
+pub mod query;
 pub mod repositories;
+pub mod validation;
@@ -0,0 +1,121 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+
+// 発行する API キーの先頭に付ける。JWT のアクセストークンと見分けるために使う
+pub const TOKEN_PREFIX: &str = "tak_";
+
+// 一覧でキーを見分けるために保存する先頭の文字数（TOKEN_PREFIX を含む）
+pub const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+// 有効期間の既定値と上限（日数）
+pub const DEFAULT_TTL_DAYS: i64 = 90;
+pub const MAX_TTL_DAYS: i64 = 365;
+
+// last_used_at を更新する間隔。リクエストごとに書き込まないよう、この間隔より細かくは記録しない
+pub const LAST_USED_RESOLUTION: Duration = Duration::minutes(1);
+
+// API キーで許可する操作。read は参照（GET）、write は作成・変更・削除
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApiScope {
+  #[serde(rename = "todos:read")]
+  TodosRead,
+  #[serde(rename = "todos:write")]
+  TodosWrite,
+  #[serde(rename = "invoices:read")]
+  InvoicesRead,
+  #[serde(rename = "invoices:write")]
+  InvoicesWrite,
+  #[serde(rename = "projects:read")]
+  ProjectsRead,
+  #[serde(rename = "projects:write")]
+  ProjectsWrite,
+}
+
+impl ApiScope {
+  // api_keys.scopes に記録される値
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ApiScope::TodosRead => "todos:read",
+      ApiScope::TodosWrite => "todos:write",
+      ApiScope::InvoicesRead => "invoices:read",
+      ApiScope::InvoicesWrite => "invoices:write",
+      ApiScope::ProjectsRead => "projects:read",
+      ApiScope::ProjectsWrite => "projects:write",
+    }
+  }
+
+  // write は同じリソースの read も含む
+  pub fn grants(&self, required: ApiScope) -> bool {
+    use ApiScope::*;
+    *self == required
+      || matches!(
+        (self, required),
+        (TodosWrite, TodosRead) | (InvoicesWrite, InvoicesRead) | (ProjectsWrite, ProjectsRead)
+      )
+  }
+}
+
+impl TryFrom<String> for ApiScope {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "todos:read" => Ok(ApiScope::TodosRead),
+      "todos:write" => Ok(ApiScope::TodosWrite),
+      "invoices:read" => Ok(ApiScope::InvoicesRead),
+      "invoices:write" => Ok(ApiScope::InvoicesWrite),
+      "projects:read" => Ok(ApiScope::ProjectsRead),
+      "projects:write" => Ok(ApiScope::ProjectsWrite),
+      _ => Err(format!("unknown API scope '{}'", value)),
+    }
+  }
+}
+
+// API キーに許可されたスコープの集合
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiScopes(pub Vec<ApiScope>);
+
+impl ApiScopes {
+  pub fn allows(&self, required: ApiScope) -> bool {
+    self.0.iter().any(|scope| scope.grants(required))
+  }
+
+  pub fn as_strings(&self) -> Vec<&'static str> {
+    self.0.iter().map(ApiScope::as_str).collect()
+  }
+}
+
+impl TryFrom<Vec<String>> for ApiScopes {
+  type Error = String;
+
+  fn try_from(values: Vec<String>) -> Result<Self, Self::Error> {
+    values.into_iter().map(ApiScope::try_from).collect::<Result<Vec<_>, _>>().map(ApiScopes)
+  }
+}
+
+// 保存された API キー。キー自体は保存せず SHA-256 のハッシュで照合する
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiKey {
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub name: String,
+  // キーの先頭 DISPLAY_PREFIX_LENGTH 文字
+  pub prefix: String,
+  pub token_hash: String,
+  #[sqlx(try_from = "Vec<String>")]
+  pub scopes: ApiScopes,
+  pub expires_at: DateTime<Utc>,
+  pub last_used_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+// API キーの作成結果。キーはこの時だけ返す
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+  pub api_key: ApiKey,
+  pub token: String,
+}
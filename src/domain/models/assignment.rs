@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::domain::models::todo::Todo;
+
+// 1 つの Todo に設定できる担当者の数
+pub const MAX_ASSIGNEES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssignmentAction {
+  Assigned,
+  Unassigned,
+}
+
+impl AssignmentAction {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      AssignmentAction::Assigned => "assigned",
+      AssignmentAction::Unassigned => "unassigned",
+    }
+  }
+}
+
+impl TryFrom<String> for AssignmentAction {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "assigned" => Ok(AssignmentAction::Assigned),
+      "unassigned" => Ok(AssignmentAction::Unassigned),
+      _ => Err(format!("unknown assignment action '{}'", value)),
+    }
+  }
+}
+
+// 担当者の追加・解除の記録
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssignmentEvent {
+  pub id: Uuid,
+  pub todo_id: Uuid,
+  pub user_id: Uuid,
+  #[sqlx(try_from = "String")]
+  pub action: AssignmentAction,
+  // 変更したユーザー。識別できなかった場合は None
+  pub actor_id: Option<Uuid>,
+  pub created_at: DateTime<Utc>,
+}
+
+// 自分の作業一覧（未完了のもののみ）
+#[derive(Debug, Clone)]
+pub struct MyWork {
+  pub assigned: Vec<Todo>,
+  // 担当ではないがウォッチしている Todo
+  pub watching: Vec<Todo>,
+}
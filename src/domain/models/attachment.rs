@@ -0,0 +1,90 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use sqlx::FromRow;
+
+// 内容から Content-Type を判定するときに調べる先頭のバイト数
+const SNIFF_LENGTH: usize = 8192;
+
+// 添付先。Todo と請求書のどちらか一方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentOwner {
+  Todo(Uuid),
+  Invoice(Uuid),
+}
+
+impl AttachmentOwner {
+  pub fn id(&self) -> Uuid {
+    match self {
+      AttachmentOwner::Todo(id) | AttachmentOwner::Invoice(id) => *id,
+    }
+  }
+
+  fn kind(&self) -> &'static str {
+    match self {
+      AttachmentOwner::Todo(_) => "todos",
+      AttachmentOwner::Invoice(_) => "invoices",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Attachment {
+  pub id: Uuid,
+  pub todo_id: Option<Uuid>,
+  pub invoice_id: Option<Uuid>,
+  pub file_name: String,
+  // クライアントの申告ではなく、内容から判定した値
+  pub content_type: String,
+  pub size: i64,
+  // BlobStore 上のキー
+  pub storage_key: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+  pub fn new(owner: AttachmentOwner, file_name: String, content_type: String, size: i64) -> Self {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
+    let now_utc = now_jst.with_timezone(&Utc);
+
+    let id = Uuid::now_v7();
+    let (todo_id, invoice_id) = match owner {
+      AttachmentOwner::Todo(todo_id) => (Some(todo_id), None),
+      AttachmentOwner::Invoice(invoice_id) => (None, Some(invoice_id)),
+    };
+
+    Self {
+      id,
+      todo_id,
+      invoice_id,
+      file_name,
+      content_type,
+      size,
+      storage_key: format!("{}/{}/{}", owner.kind(), owner.id(), id),
+      created_at: now_utc,
+    }
+  }
+
+  pub fn belongs_to(&self, owner: AttachmentOwner) -> bool {
+    match owner {
+      AttachmentOwner::Todo(id) => self.todo_id == Some(id),
+      AttachmentOwner::Invoice(id) => self.invoice_id == Some(id),
+    }
+  }
+}
+
+// 先頭のバイト列から Content-Type を判定する。判定できないテキストは text/plain、それ以外は application/octet-stream
+pub fn sniff_content_type(data: &[u8]) -> String {
+  if let Some(kind) = infer::get(data) {
+    return kind.mime_type().to_string();
+  }
+
+  let head = &data[..data.len().min(SNIFF_LENGTH)];
+  match std::str::from_utf8(head) {
+    // 途中で切れたマルチバイト文字は不正とみなさない
+    Ok(_) => "text/plain; charset=utf-8".to_string(),
+    Err(err) if err.error_len().is_none() => "text/plain; charset=utf-8".to_string(),
+    Err(_) => "application/octet-stream".to_string(),
+  }
+}
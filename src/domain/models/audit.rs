@@ -0,0 +1,113 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use std::collections::BTreeMap;
+
+// 変更履歴を記録する対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditEntity {
+  Todo,
+  Invoice,
+}
+
+impl AuditEntity {
+  // audit_events.entity_type に記録されるテーブル名
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      AuditEntity::Todo => "todos",
+      AuditEntity::Invoice => "invoices",
+    }
+  }
+}
+
+impl TryFrom<String> for AuditEntity {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "todos" => Ok(AuditEntity::Todo),
+      "invoices" => Ok(AuditEntity::Invoice),
+      _ => Err(format!("unknown audit entity '{}'", value)),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+  Create,
+  Update,
+  // ゴミ箱への移動
+  Delete,
+  // ゴミ箱からの復元
+  Restore,
+  // 保持期間経過後の物理削除
+  Purge,
+}
+
+impl TryFrom<String> for AuditAction {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "create" => Ok(AuditAction::Create),
+      "update" => Ok(AuditAction::Update),
+      "delete" => Ok(AuditAction::Delete),
+      "restore" => Ok(AuditAction::Restore),
+      "purge" => Ok(AuditAction::Purge),
+      _ => Err(format!("unknown audit action '{}'", value)),
+    }
+  }
+}
+
+// 1 列分の変更前後の値
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+  pub before: serde_json::Value,
+  pub after: serde_json::Value,
+}
+
+// Todo・請求書の変更の記録。変更と同じトランザクションで DB のトリガーが書き込む
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEvent {
+  pub id: Uuid,
+  #[sqlx(try_from = "String")]
+  pub entity_type: AuditEntity,
+  pub entity_id: Uuid,
+  #[sqlx(try_from = "String")]
+  pub action: AuditAction,
+  // 変更したユーザー。識別できなかった場合は None
+  pub actor_id: Option<Uuid>,
+  // 同じ API 呼び出しで記録されたイベントに共通の ID
+  pub operation_id: Option<Uuid>,
+  // 列名ごとの変更前後の値
+  pub changes: Json<BTreeMap<String, FieldChange>>,
+  pub created_at: DateTime<Utc>,
+}
+
+// 変更を記録する際の実行者と操作 ID。リポジトリがトランザクションごとに DB へ渡す
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditContext {
+  pub actor_id: Option<Uuid>,
+  pub operation_id: Uuid,
+}
+
+impl AuditContext {
+  pub fn new(actor_id: Option<Uuid>) -> Self {
+    Self {
+      actor_id,
+      operation_id: Uuid::now_v7(),
+    }
+  }
+}
+
+// 操作の取り消しの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertOutcome {
+  Reverted,
+  // 対象がその後に変更（または物理削除）されていたため取り消さなかった
+  Conflict,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+pub const PASSWORD_MAX_LENGTH: usize = 128;
+
+// アクセストークン（JWT）のクレーム
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessClaims {
+  // ユーザー ID
+  pub sub: Uuid,
+  pub iat: i64,
+  pub exp: i64,
+}
+
+// 保存されたリフレッシュトークン。トークン自体は保存せず SHA-256 のハッシュで照合する
+#[derive(Debug, Clone, FromRow)]
+pub struct RefreshToken {
+  pub id: Uuid,
+  pub user_id: Uuid,
+  // 最初のログインから続くトークンの系列
+  pub family_id: Uuid,
+  pub token_hash: String,
+  pub expires_at: DateTime<Utc>,
+  pub revoked_at: Option<DateTime<Utc>>,
+}
+
+// ログイン・リフレッシュで発行するトークンの組
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+  pub access_token: String,
+  pub access_token_expires_at: DateTime<Utc>,
+  pub refresh_token: String,
+  pub refresh_token_expires_at: DateTime<Utc>,
+}
@@ -0,0 +1,48 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use sqlx::FromRow;
+
+pub const BODY_MAX_LENGTH: usize = 10_000;
+
+// Todo へのコメント。本文は Markdown のまま保存する
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Comment {
+  pub id: Uuid,
+  pub todo_id: Uuid,
+  // 投稿したユーザー。退会したユーザーと、投稿者を記録する前のコメントは None
+  pub author_id: Option<Uuid>,
+  // 投稿時点のユーザー名
+  pub author: String,
+  pub body: String,
+  pub created_at: DateTime<Utc>,
+  // 一度も編集されていない場合は None
+  pub edited_at: Option<DateTime<Utc>>,
+}
+
+// 編集前の本文
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CommentRevision {
+  pub id: Uuid,
+  pub comment_id: Uuid,
+  pub body: String,
+  pub created_at: DateTime<Utc>,
+}
+
+impl Comment {
+  pub fn new(todo_id: Uuid, author_id: Uuid, author: String, body: String) -> Self {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
+    let now_utc = now_jst.with_timezone(&Utc);
+
+    Self {
+      id: Uuid::now_v7(),
+      todo_id,
+      author_id: Some(author_id),
+      author,
+      body,
+      created_at: now_utc,
+      edited_at: None,
+    }
+  }
+}
@@ -0,0 +1,29 @@
+use sqlx::FromRow;
+use sqlx::types::Json;
+
+// Idempotency-Key ごとに記録したリクエストとレスポンス。status_code が None の間は処理中（locked_until まで）
+#[derive(Debug, Clone, FromRow)]
+pub struct IdempotencyRecord {
+  pub request_hash: String,
+  pub status_code: Option<i16>,
+  pub response_headers: Option<Json<Vec<(String, String)>>>,
+  pub response_body: Option<Vec<u8>>,
+}
+
+impl IdempotencyRecord {
+  pub fn response(self) -> Option<StoredResponse> {
+    Some(StoredResponse {
+      status: u16::try_from(self.status_code?).ok()?,
+      headers: self.response_headers.map(|headers| headers.0).unwrap_or_default(),
+      body: self.response_body.unwrap_or_default(),
+    })
+  }
+}
+
+// 再送時にそのまま返すレスポンス
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  pub body: Vec<u8>,
+}
@@ -3,6 +3,8 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc, FixedOffset, TimeZone};
 use sqlx::FromRow;
 
+use crate::domain::validation::ValidationErrors;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Invoice {
   pub id: Uuid,
@@ -10,20 +12,57 @@ pub struct Invoice {
   pub paid: bool,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
+  // 更新のたびに DB で増える版数（ETag に使う）
+  pub version: i64,
+  // ゴミ箱に移動した日時
+  pub deleted_at: Option<DateTime<Utc>>,
+}
+
+// 部分更新（JSON Merge Patch）の内容。None の項目は変更しない
+#[derive(Debug, Clone, Default)]
+pub struct InvoicePatch {
+  pub amount: Option<i32>,
+  pub paid: Option<bool>,
+}
+
+pub fn validate_amount(errors: &mut ValidationErrors, amount: i32) {
+  if amount < 0 {
+    errors.add("amount", "range", "amount must not be negative");
+  }
+}
+
+impl InvoicePatch {
+  pub fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if let Some(amount) = self.amount {
+      validate_amount(&mut errors, amount);
+    }
+    errors.into_result()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.amount.is_none() && self.paid.is_none()
+  }
 }
 
 impl Invoice {
-  pub fn new(amount: i32) -> Self {
+  pub fn new(amount: i32) -> Result<Self, ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    validate_amount(&mut errors, amount);
+    errors.into_result()?;
+
     let jst = FixedOffset::east_opt(9 * 3600).unwrap();
     let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
     let now_utc = now_jst.with_timezone(&Utc);
     
-    Self {
+    Ok(Self {
       id: Uuid::now_v7(),
       amount,
       paid: false,
       created_at: now_utc,
-      updated_at: now_utc
-    }
+      updated_at: now_utc,
+      version: 1,
+      deleted_at: None,
+    })
   }
-}
\ No newline at end of file
+}
@@ -1,2 +1,21 @@
 pub mod todo;
-pub mod invoice;
\ No newline at end of file
+pub mod invoice;
+pub mod api_key;
+pub mod assignment;
+pub mod attachment;
+pub mod audit;
+pub mod auth;
+pub mod comment;
+pub mod idempotency;
+pub mod oidc;
+pub mod organization;
+pub mod page;
+pub mod project;
+pub mod rank;
+pub mod recurrence;
+pub mod saved_filter;
+pub mod search;
+pub mod share;
+pub mod trash;
+pub mod user;
+pub mod workflow;
\ No newline at end of file
@@ -0,0 +1,82 @@
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+
+use crate::domain::models::organization::MembershipRole;
+
+// ログインを始めてから IdP から戻るまでの有効期間
+pub const LOGIN_STATE_TTL: Duration = Duration::minutes(10);
+
+// ログインの開始時に保存し、IdP からのコールバックで 1 度だけ使う値。state はハッシュで照合する
+#[derive(Debug, Clone, FromRow)]
+pub struct LoginState {
+  pub state_hash: String,
+  // PKCE の code_verifier
+  pub code_verifier: String,
+  // ID トークンの nonce と照合する
+  pub nonce: String,
+  // ログイン中のユーザーが IdP のアカウントを対応付ける場合のユーザー
+  pub link_user_id: Option<Uuid>,
+  pub expires_at: DateTime<Utc>,
+}
+
+// ログインの開始時にブラウザへ返すもの。state はクッキーにも入れ、コールバックで同じブラウザか確かめる
+#[derive(Debug, Clone)]
+pub struct LoginRedirect {
+  pub authorization_url: String,
+  pub state: String,
+}
+
+// IdP が ID トークンで返した、検証済みのユーザーの情報
+#[derive(Debug, Clone)]
+pub struct IdentityClaims {
+  // IdP 内で一意なユーザーの識別子
+  pub subject: String,
+  pub email: Option<String>,
+  pub email_verified: bool,
+  pub name: Option<String>,
+  pub groups: Vec<String>,
+}
+
+// IdP のグループを組織の役割に対応付ける
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupRoleMapping {
+  pub group: String,
+  pub organization_id: Uuid,
+  pub role: MembershipRole,
+}
+
+impl GroupRoleMapping {
+  // "group=organization_id:role" をカンマで区切った設定を読み込む
+  pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+    value
+      .split(',')
+      .map(str::trim)
+      .filter(|entry| !entry.is_empty())
+      .map(|entry| {
+        let invalid = || format!("invalid group role mapping '{}' (expected group=organization_id:role)", entry);
+        let (group, target) = entry.rsplit_once('=').ok_or_else(invalid)?;
+        let (organization_id, role) = target.split_once(':').ok_or_else(invalid)?;
+        let organization_id = Uuid::parse_str(organization_id.trim()).map_err(|_| invalid())?;
+        let role = MembershipRole::try_from(role.trim().to_string())?;
+        if role == MembershipRole::Owner {
+          return Err(format!("group role mapping '{}' cannot grant the owner role", entry));
+        }
+        Ok(Self { group: group.trim().to_string(), organization_id, role })
+      })
+      .collect()
+  }
+
+  // 設定にある組織ごとに、設定の順で最初に一致したグループの役割を選ぶ。一致するグループがなければ None
+  pub fn resolve(mappings: &[Self], groups: &[String]) -> Vec<(Uuid, Option<MembershipRole>)> {
+    let mut roles: Vec<(Uuid, Option<MembershipRole>)> = Vec::new();
+    for mapping in mappings {
+      let matched = groups.contains(&mapping.group).then_some(mapping.role);
+      match roles.iter_mut().find(|(id, _)| *id == mapping.organization_id) {
+        Some((_, role)) => *role = role.or(matched),
+        None => roles.push((mapping.organization_id, matched)),
+      }
+    }
+    roles
+  }
+}
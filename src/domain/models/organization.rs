@@ -0,0 +1,128 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+
+// 招待トークンの有効期間
+pub const INVITATION_TTL: Duration = Duration::days(7);
+
+// 組織内の役割。役割ごとに許可する操作は domain::policy で決める
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MembershipRole {
+  // 最後の 1 人は組織から外せない
+  Owner,
+  Admin,
+  // 請求書の担当
+  Accountant,
+  Member,
+  // 参照だけができる
+  Viewer,
+}
+
+impl MembershipRole {
+  // organization_memberships.role に記録される値
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      MembershipRole::Owner => "owner",
+      MembershipRole::Admin => "admin",
+      MembershipRole::Accountant => "accountant",
+      MembershipRole::Member => "member",
+      MembershipRole::Viewer => "viewer",
+    }
+  }
+}
+
+impl TryFrom<String> for MembershipRole {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "owner" => Ok(MembershipRole::Owner),
+      "admin" => Ok(MembershipRole::Admin),
+      "accountant" => Ok(MembershipRole::Accountant),
+      "member" => Ok(MembershipRole::Member),
+      "viewer" => Ok(MembershipRole::Viewer),
+      _ => Err(format!("unknown membership role '{}'", value)),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Organization {
+  pub id: Uuid,
+  pub name: String,
+  // 個人用の組織の持ち主。共有の組織は None
+  pub personal_user_id: Option<Uuid>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Organization {
+  pub fn new(name: String) -> Self {
+    let now = Utc::now();
+    Self {
+      id: Uuid::now_v7(),
+      name,
+      personal_user_id: None,
+      created_at: now,
+      updated_at: now,
+    }
+  }
+}
+
+// ユーザーが所属する組織と、その組織での役割
+#[derive(Debug, Clone, FromRow)]
+pub struct UserOrganization {
+  #[sqlx(flatten)]
+  pub organization: Organization,
+  #[sqlx(try_from = "String")]
+  pub role: MembershipRole,
+}
+
+// 組織のメンバー
+#[derive(Debug, Clone, FromRow)]
+pub struct Member {
+  pub user_id: Uuid,
+  pub name: String,
+  pub email: String,
+  #[sqlx(try_from = "String")]
+  pub role: MembershipRole,
+  pub created_at: DateTime<Utc>,
+}
+
+// 保存された招待。トークン自体は保存せず SHA-256 のハッシュで照合する
+#[derive(Debug, Clone, FromRow)]
+pub struct Invitation {
+  pub id: Uuid,
+  pub organization_id: Uuid,
+  pub email: String,
+  #[sqlx(try_from = "String")]
+  pub role: MembershipRole,
+  pub token_hash: String,
+  pub invited_by: Option<Uuid>,
+  pub expires_at: DateTime<Utc>,
+  pub accepted_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+// 招待の作成結果。トークンはこの時だけ返す
+#[derive(Debug, Clone)]
+pub struct IssuedInvitation {
+  pub invitation: Invitation,
+  pub token: String,
+}
+
+// リクエストの対象となる組織と、リクエストしたユーザーのその組織での役割。
+// 組織ごとに分かれるデータはこの組織の範囲だけを参照・変更する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tenant {
+  pub organization_id: Uuid,
+  pub user_id: Uuid,
+  pub role: MembershipRole,
+  // 共有されたプロジェクトとしてアクセスしている場合は、そのプロジェクト（と Todo）だけに範囲を絞る。
+  // role は共有の権限から決まる（domain::models::share）
+  pub project_id: Option<Uuid>,
+}
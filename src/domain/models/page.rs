@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+// UUIDv7 の id は時系列順に並ぶため、そのままキーセット（カーソル）ページングのキーに使う
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 50;
+pub const MAX_PAGE_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PageRequest {
+  pub limit: i64,
+  // 直前のページの最後の id。これより後ろの行を返す
+  pub cursor: Option<Uuid>,
+}
+
+impl PageRequest {
+  pub fn new(limit: Option<i64>, cursor: Option<Uuid>) -> Self {
+    Self {
+      limit: limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT),
+      cursor,
+    }
+  }
+
+  // 次ページの有無を判定するため 1 件多く取得する
+  pub fn fetch_limit(&self) -> i64 {
+    self.limit + 1
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub next_cursor: Option<Uuid>,
+}
+
+impl<T> Page<T> {
+  // `fetch_limit` 件で取得した行からページを組み立てる
+  pub fn from_rows(mut rows: Vec<T>, request: PageRequest, id: impl Fn(&T) -> Uuid) -> Self {
+    let has_more = rows.len() as i64 > request.limit;
+    rows.truncate(request.limit as usize);
+    let next_cursor = if has_more { rows.last().map(id) } else { None };
+    Self { items: rows, next_cursor }
+  }
+
+  pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+    Page {
+      items: self.items.into_iter().map(f).collect(),
+      next_cursor: self.next_cursor,
+    }
+  }
+}
@@ -0,0 +1,34 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use sqlx::types::Json;
+use sqlx::FromRow;
+
+use crate::domain::models::workflow::Workflow;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Project {
+  pub id: Uuid,
+  pub name: String,
+  pub workflow: Json<Workflow>,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl Project {
+  pub fn new(name: String, workflow: Workflow) -> Self {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
+    let now_utc = now_jst.with_timezone(&Utc);
+
+    Self {
+      id: Uuid::now_v7(),
+      name,
+      workflow: Json(workflow),
+      created_at: now_utc,
+      updated_at: now_utc
+    }
+  }
+}
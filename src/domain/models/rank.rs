@@ -0,0 +1,163 @@
+// 並び順のための辞書順キー（fractional indexing）
+// 2 つのキーの間に常に新しいキーを作れるため、並べ替えで更新するのは移動した 1 行だけで済む。
+// キーは 0-9a-z の 36 進数字列で、末尾に '0' を置かない（"1" と "10" が同じ位置を表してしまうため）。
+// DB 側では COLLATE "C" でバイト順に比較する
+
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: usize = 36;
+
+// キーがこの長さを超えたら全体を振り直す
+pub const MAX_RANK_LENGTH: usize = 32;
+
+fn digit_index(c: u8) -> usize {
+  DIGITS.iter().position(|d| *d == c).unwrap_or(0)
+}
+
+pub fn is_valid_rank(rank: &str) -> bool {
+  !rank.is_empty() && !rank.ends_with('0') && rank.bytes().all(|c| DIGITS.contains(&c))
+}
+
+// lower < 結果 < upper となるキーを返す。None はそれぞれ先頭・末尾を表す。
+// lower >= upper の場合は None（呼び出し側で振り直しが必要）
+pub fn rank_between(lower: Option<&str>, upper: Option<&str>) -> Option<String> {
+  let lower = lower.unwrap_or("");
+  if upper.is_some_and(|upper| lower >= upper || !is_valid_rank(upper)) {
+    return None;
+  }
+  if !lower.is_empty() && !is_valid_rank(lower) {
+    return None;
+  }
+
+  // 末尾への追加はよくある操作なので、桁を 1 つ繰り上げるだけの短いキーにする
+  if upper.is_none()
+    && let Some(i) = lower.bytes().position(|c| c != b'z')
+  {
+    let mut key = lower.as_bytes()[..i].to_vec();
+    key.push(DIGITS[digit_index(lower.as_bytes()[i]) + 1]);
+    return String::from_utf8(key).ok();
+  }
+
+  let key = midpoint(lower.as_bytes(), upper.map(str::as_bytes));
+  String::from_utf8(key).ok()
+}
+
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> Vec<u8> {
+  if let Some(upper) = upper {
+    // 共通の接頭辞はそのまま残し、残りの部分の中間を求める
+    let mut n = 0;
+    while n < upper.len() && lower.get(n).copied().unwrap_or(b'0') == upper[n] {
+      n += 1;
+    }
+    if n > 0 {
+      let mut key = upper[..n].to_vec();
+      key.extend(midpoint(lower.get(n..).unwrap_or(&[]), Some(&upper[n..])));
+      return key;
+    }
+  }
+
+  let low = lower.first().map(|c| digit_index(*c)).unwrap_or(0);
+  let high = upper.and_then(|u| u.first()).map(|c| digit_index(*c)).unwrap_or(BASE);
+
+  if high - low > 1 {
+    return vec![DIGITS[(low + high) / 2]];
+  }
+  // 隣り合う桁の場合、upper が 2 桁以上なら先頭 1 桁だけで間に収まる
+  if let Some(upper) = upper.filter(|upper| upper.len() > 1) {
+    return vec![upper[0]];
+  }
+  let mut key = vec![DIGITS[low]];
+  key.extend(midpoint(lower.get(1..).unwrap_or(&[]), None));
+  key
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 結果が lower と upper の間にあり、キーとして有効であることも確かめる
+  fn between(lower: Option<&str>, upper: Option<&str>) -> String {
+    let key = rank_between(lower, upper).unwrap();
+    assert!(is_valid_rank(&key), "{:?}", key);
+    assert!(lower.is_none_or(|lower| lower < key.as_str()), "{:?} < {:?}", lower, key);
+    assert!(upper.is_none_or(|upper| key.as_str() < upper), "{:?} < {:?}", key, upper);
+    key
+  }
+
+  #[test]
+  fn first_key_is_the_middle_digit() {
+    assert_eq!(between(None, None), "i");
+  }
+
+  #[test]
+  fn empty_lower_bound_takes_the_middle_below_upper() {
+    assert_eq!(between(None, Some("i")), "9");
+    // "0" は使えないため、"0" の後ろに桁を足す
+    assert_eq!(between(None, Some("1")), "0i");
+    assert_eq!(between(None, Some("01")), "00i");
+  }
+
+  #[test]
+  fn empty_upper_bound_increments_the_first_digit_that_is_not_z() {
+    assert_eq!(between(Some("i"), None), "j");
+    assert_eq!(between(Some("y5"), None), "z");
+    assert_eq!(between(Some("zi"), None), "zj");
+  }
+
+  #[test]
+  fn after_an_all_z_key_appends_a_digit() {
+    assert_eq!(between(Some("z"), None), "zi");
+    assert_eq!(between(Some("zzz"), None), "zzzi");
+    assert_eq!(between(Some(&"z".repeat(MAX_RANK_LENGTH)), None).len(), MAX_RANK_LENGTH + 1);
+  }
+
+  #[test]
+  fn adjacent_digits_extend_the_key() {
+    assert_eq!(between(Some("a"), Some("b")), "ai");
+    assert_eq!(between(Some("ay"), Some("b")), "az");
+    assert_eq!(between(Some("az"), Some("b")), "azi");
+    // upper が 2 桁以上なら、その先頭の桁だけで間に収まる
+    assert_eq!(between(Some("a"), Some("b5")), "b");
+  }
+
+  #[test]
+  fn long_shared_prefix_is_kept() {
+    assert_eq!(between(Some("abcd1"), Some("abcd3")), "abcd2");
+    assert_eq!(between(Some("abc"), Some("abcj")), "abc9");
+    assert_eq!(between(Some("abcdefgh1"), Some("abcdefgh2")), "abcdefgh1i");
+  }
+
+  #[test]
+  fn midpoint_continues_with_the_middle_digit_when_no_digit_fits() {
+    assert_eq!(midpoint(b"", None), b"i");
+    assert_eq!(midpoint(b"y", None), b"z");
+    assert_eq!(midpoint(b"z", None), b"zi");
+    assert_eq!(midpoint(b"1", Some(b"3")), b"2");
+    assert_eq!(midpoint(b"1z", Some(b"2")), b"1zi");
+  }
+
+  #[test]
+  fn invalid_or_unordered_bounds_are_rejected() {
+    assert_eq!(rank_between(Some("b"), Some("a")), None);
+    assert_eq!(rank_between(Some("a"), Some("a")), None);
+    assert_eq!(rank_between(Some("a0"), None), None);
+    assert_eq!(rank_between(None, Some("A")), None);
+    assert_eq!(rank_between(Some(""), Some("")), None);
+  }
+
+  #[test]
+  fn repeated_inserts_at_the_same_position_grow_the_key_past_max_rank_length() {
+    // 同じ位置への挿入を繰り返すとキーが伸び続ける。MAX_RANK_LENGTH を超えたら呼び出し側で振り直す
+    let mut upper = "b".to_string();
+    let mut inserts = 0;
+    while upper.len() <= MAX_RANK_LENGTH {
+      upper = between(Some("a"), Some(&upper));
+      inserts += 1;
+    }
+    assert_eq!(upper.len(), MAX_RANK_LENGTH + 1);
+    assert!(inserts > MAX_RANK_LENGTH, "{} inserts", inserts);
+
+    // 上限を超えたキーの間にもキーは作れる
+    let key = between(Some("a"), Some(&upper));
+    assert!(key.len() > MAX_RANK_LENGTH);
+  }
+}
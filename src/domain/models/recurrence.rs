@@ -0,0 +1,401 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+// RFC 5545 の RRULE のうち、Todo の繰り返しに必要なサブセットを扱う
+// 例: "FREQ=DAILY", "FREQ=WEEKLY;BYDAY=MO,WE", "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1"
+
+// 無限ループを避けるため、探索する期間数の上限
+const MAX_PERIODS: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+  // 1MO（第1月曜）や -1FR（最終金曜）の序数。MONTHLY/YEARLY でのみ意味を持つ
+  pub ordinal: Option<i32>,
+  pub weekday: Weekday,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+  pub freq: Frequency,
+  pub interval: u32,
+  pub count: Option<u32>,
+  pub until: Option<DateTime<Utc>>,
+  pub by_day: Vec<ByDay>,
+  pub by_month_day: Vec<i32>,
+  pub by_month: Vec<u32>,
+  pub by_set_pos: Vec<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceError(pub String);
+
+impl fmt::Display for RecurrenceError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "invalid RRULE: {}", self.0)
+  }
+}
+
+impl std::error::Error for RecurrenceError {}
+
+impl FromStr for RecurrenceRule {
+  type Err = RecurrenceError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+    let mut freq = None;
+    let mut rule = RecurrenceRule {
+      freq: Frequency::Daily,
+      interval: 1,
+      count: None,
+      until: None,
+      by_day: Vec::new(),
+      by_month_day: Vec::new(),
+      by_month: Vec::new(),
+      by_set_pos: Vec::new(),
+    };
+
+    for part in s.split(';').filter(|p| !p.is_empty()) {
+      let (key, value) = part
+        .split_once('=')
+        .ok_or_else(|| RecurrenceError(format!("expected KEY=VALUE, got '{}'", part)))?;
+
+      match key.to_ascii_uppercase().as_str() {
+        "FREQ" => {
+          freq = Some(match value.to_ascii_uppercase().as_str() {
+            "DAILY" => Frequency::Daily,
+            "WEEKLY" => Frequency::Weekly,
+            "MONTHLY" => Frequency::Monthly,
+            "YEARLY" => Frequency::Yearly,
+            other => return Err(RecurrenceError(format!("unsupported FREQ '{}'", other))),
+          })
+        }
+        "INTERVAL" => {
+          rule.interval = parse_number(key, value)?;
+          if rule.interval == 0 {
+            return Err(RecurrenceError("INTERVAL must be positive".to_string()));
+          }
+        }
+        "COUNT" => rule.count = Some(parse_number(key, value)?),
+        "UNTIL" => rule.until = Some(parse_until(value)?),
+        "BYDAY" => rule.by_day = parse_list(value, parse_by_day)?,
+        "BYMONTHDAY" => {
+          rule.by_month_day = parse_list(value, |v| {
+            let day: i32 = parse_number("BYMONTHDAY", v)?;
+            if day == 0 || !(-31..=31).contains(&day) {
+              return Err(RecurrenceError(format!("BYMONTHDAY out of range: {}", day)));
+            }
+            Ok(day)
+          })?
+        }
+        "BYMONTH" => {
+          rule.by_month = parse_list(value, |v| {
+            let month: u32 = parse_number("BYMONTH", v)?;
+            if !(1..=12).contains(&month) {
+              return Err(RecurrenceError(format!("BYMONTH out of range: {}", month)));
+            }
+            Ok(month)
+          })?
+        }
+        "BYSETPOS" => {
+          rule.by_set_pos = parse_list(value, |v| {
+            let pos: i32 = parse_number("BYSETPOS", v)?;
+            if pos == 0 {
+              return Err(RecurrenceError("BYSETPOS must not be 0".to_string()));
+            }
+            Ok(pos)
+          })?
+        }
+        "WKST" => {
+          if !value.eq_ignore_ascii_case("MO") {
+            return Err(RecurrenceError("only WKST=MO is supported".to_string()));
+          }
+        }
+        other => return Err(RecurrenceError(format!("unsupported rule part '{}'", other))),
+      }
+    }
+
+    rule.freq = freq.ok_or_else(|| RecurrenceError("FREQ is required".to_string()))?;
+    if rule.count.is_some() && rule.until.is_some() {
+      return Err(RecurrenceError("COUNT and UNTIL must not both be set".to_string()));
+    }
+    Ok(rule)
+  }
+}
+
+impl RecurrenceRule {
+  // `current` の次の発生日時を返す。`current` は直前の発生（＝現在の期日）で、
+  // 時刻部分は引き継がれる。`occurrences` はシリーズ内で既に生成された件数（COUNT 判定用）
+  pub fn next_after(&self, current: DateTime<Utc>, occurrences: u32) -> Option<DateTime<Utc>> {
+    if self.count.is_some_and(|count| occurrences >= count) {
+      return None;
+    }
+
+    let time = current.time();
+    let start = current.date_naive();
+
+    for step in 0..MAX_PERIODS {
+      let mut candidates = self.expand_period(start, step * self.interval)?;
+      candidates.sort();
+      candidates.dedup();
+      let candidates = self.apply_set_pos(candidates);
+
+      let next = candidates
+        .into_iter()
+        .map(|date| date.and_time(time).and_utc())
+        .find(|dt| *dt > current);
+
+      if let Some(next) = next {
+        return match self.until {
+          Some(until) if next > until => None,
+          _ => Some(next),
+        };
+      }
+    }
+    None
+  }
+
+  // start を含む期間から `offset` 期間後の候補日を列挙する
+  fn expand_period(&self, start: NaiveDate, offset: u32) -> Option<Vec<NaiveDate>> {
+    let dates = match self.freq {
+      Frequency::Daily => {
+        let day = start.checked_add_signed(Duration::days(offset as i64))?;
+        let matches_day = self.by_day.is_empty() || self.by_day.iter().any(|d| d.weekday == day.weekday());
+        let matches_month_day = self.by_month_day.is_empty() || self.matches_month_day(day);
+        let matches_month = self.by_month.is_empty() || self.by_month.contains(&day.month());
+        if matches_day && matches_month_day && matches_month { vec![day] } else { vec![] }
+      }
+      Frequency::Weekly => {
+        let week_start = start.checked_sub_signed(Duration::days(start.weekday().num_days_from_monday() as i64))?;
+        let week_start = week_start.checked_add_signed(Duration::weeks(offset as i64))?;
+        let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+          vec![start.weekday()]
+        } else {
+          self.by_day.iter().map(|d| d.weekday).collect()
+        };
+        (0..7)
+          .filter_map(|i| week_start.checked_add_signed(Duration::days(i)))
+          .filter(|d| weekdays.contains(&d.weekday()))
+          .collect()
+      }
+      Frequency::Monthly => {
+        let (year, month) = add_months(start.year(), start.month(), offset);
+        self.expand_month(year, month, start.day())
+      }
+      Frequency::Yearly => {
+        let year = start.year() + offset as i32;
+        let months: Vec<u32> = if self.by_month.is_empty() { vec![start.month()] } else { self.by_month.clone() };
+        months
+          .into_iter()
+          .flat_map(|month| self.expand_month(year, month, start.day()))
+          .collect()
+      }
+    };
+    Some(dates)
+  }
+
+  fn expand_month(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = (1..=days_in_month(year, month))
+      .filter_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+      .collect();
+
+    if !self.by_month_day.is_empty() {
+      return days
+        .into_iter()
+        .filter(|d| self.matches_month_day(*d))
+        .filter(|d| self.by_day.is_empty() || self.by_day.iter().any(|b| b.weekday == d.weekday()))
+        .collect();
+    }
+
+    if !self.by_day.is_empty() {
+      return days
+        .into_iter()
+        .filter(|d| self.by_day.iter().any(|b| matches_by_day(b, *d, year, month)))
+        .collect();
+    }
+
+    days.into_iter().filter(|d| d.day() == default_day).collect()
+  }
+
+  fn matches_month_day(&self, date: NaiveDate) -> bool {
+    let last = days_in_month(date.year(), date.month()) as i32;
+    let day = date.day() as i32;
+    self.by_month_day
+      .iter()
+      .any(|&d| if d > 0 { d == day } else { last + d + 1 == day })
+  }
+
+  fn apply_set_pos(&self, candidates: Vec<NaiveDate>) -> Vec<NaiveDate> {
+    if self.by_set_pos.is_empty() {
+      return candidates;
+    }
+    let len = candidates.len() as i32;
+    let mut selected: Vec<NaiveDate> = self
+      .by_set_pos
+      .iter()
+      .filter_map(|&pos| {
+        let index = if pos > 0 { pos - 1 } else { len + pos };
+        (0..len).contains(&index).then(|| candidates[index as usize])
+      })
+      .collect();
+    selected.sort();
+    selected.dedup();
+    selected
+  }
+}
+
+fn matches_by_day(by_day: &ByDay, date: NaiveDate, year: i32, month: u32) -> bool {
+  if by_day.weekday != date.weekday() {
+    return false;
+  }
+  match by_day.ordinal {
+    None => true,
+    Some(n) if n > 0 => (date.day() as i32 - 1) / 7 + 1 == n,
+    Some(n) => {
+      let remaining = days_in_month(year, month) as i32 - date.day() as i32;
+      -(remaining / 7 + 1) == n
+    }
+  }
+}
+
+fn add_months(year: i32, month: u32, offset: u32) -> (i32, u32) {
+  let total = year * 12 + (month as i32 - 1) + offset as i32;
+  (total.div_euclid(12), (total.rem_euclid(12) + 1) as u32)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+  let (next_year, next_month) = add_months(year, month, 1);
+  NaiveDate::from_ymd_opt(next_year, next_month, 1)
+    .and_then(|d| d.pred_opt())
+    .map(|d| d.day())
+    .unwrap_or(28)
+}
+
+fn parse_number<N: FromStr>(key: &str, value: &str) -> Result<N, RecurrenceError> {
+  value
+    .parse()
+    .map_err(|_| RecurrenceError(format!("{} must be a number, got '{}'", key, value)))
+}
+
+fn parse_list<V>(
+  value: &str,
+  parse: impl Fn(&str) -> Result<V, RecurrenceError>,
+) -> Result<Vec<V>, RecurrenceError> {
+  value.split(',').map(|v| parse(v.trim())).collect()
+}
+
+fn parse_by_day(value: &str) -> Result<ByDay, RecurrenceError> {
+  // 末尾 2 バイトで分割するため、ASCII 以外（文字の途中で分割してしまう）は受け付けない
+  if value.len() < 2 || !value.is_ascii() {
+    return Err(RecurrenceError(format!("invalid BYDAY '{}'", value)));
+  }
+  let (ordinal, day) = value.split_at(value.len() - 2);
+  let weekday = match day.to_ascii_uppercase().as_str() {
+    "MO" => Weekday::Mon,
+    "TU" => Weekday::Tue,
+    "WE" => Weekday::Wed,
+    "TH" => Weekday::Thu,
+    "FR" => Weekday::Fri,
+    "SA" => Weekday::Sat,
+    "SU" => Weekday::Sun,
+    _ => return Err(RecurrenceError(format!("invalid weekday in BYDAY '{}'", value))),
+  };
+  let ordinal = if ordinal.is_empty() {
+    None
+  } else {
+    let n: i32 = parse_number("BYDAY", ordinal.trim_start_matches('+'))?;
+    if n == 0 || !(-5..=5).contains(&n) {
+      return Err(RecurrenceError(format!("BYDAY ordinal out of range: {}", value)));
+    }
+    Some(n)
+  };
+  Ok(ByDay { ordinal, weekday })
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, RecurrenceError> {
+  let invalid = || RecurrenceError(format!("invalid UNTIL '{}'", value));
+  if value.len() == 8 {
+    let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+    return date.and_hms_opt(23, 59, 59).map(|d| d.and_utc()).ok_or_else(invalid);
+  }
+  chrono::NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+    .map(|d| d.and_utc())
+    .map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+  }
+
+  fn next(rule: &str, current: &str, occurrences: u32) -> Option<DateTime<Utc>> {
+    rule.parse::<RecurrenceRule>().unwrap().next_after(at(current), occurrences)
+  }
+
+  #[test]
+  fn by_day_with_positive_ordinal_picks_nth_weekday_of_month() {
+    // 2026-10-13 は第 2 火曜
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=2TU", "2026-10-13T09:00:00Z", 1), Some(at("2026-11-10T09:00:00Z")));
+  }
+
+  #[test]
+  fn by_day_with_negative_ordinal_picks_last_weekday_of_month() {
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=-1FR", "2026-10-30T09:00:00Z", 1), Some(at("2026-11-27T09:00:00Z")));
+    assert_eq!(next("FREQ=MONTHLY;BYDAY=-1FR", "2026-11-27T09:00:00Z", 2), Some(at("2026-12-25T09:00:00Z")));
+  }
+
+  #[test]
+  fn by_day_ordinal_out_of_range_is_rejected() {
+    assert!("FREQ=MONTHLY;BYDAY=6MO".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=MONTHLY;BYDAY=0MO".parse::<RecurrenceRule>().is_err());
+  }
+
+  #[test]
+  fn by_day_with_non_ascii_value_is_rejected_without_panicking() {
+    assert!("FREQ=WEEKLY;BYDAY=あ".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;BYDAY=1あ".parse::<RecurrenceRule>().is_err());
+    assert!("FREQ=WEEKLY;BYDAY=Mö".parse::<RecurrenceRule>().is_err());
+  }
+
+  #[test]
+  fn by_month_day_31_skips_shorter_months() {
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=31", "2027-01-31T00:00:00Z", 1), Some(at("2027-03-31T00:00:00Z")));
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=31", "2027-03-31T00:00:00Z", 2), Some(at("2027-05-31T00:00:00Z")));
+  }
+
+  #[test]
+  fn negative_by_month_day_counts_from_month_end() {
+    assert_eq!(next("FREQ=MONTHLY;BYMONTHDAY=-1", "2027-01-31T00:00:00Z", 1), Some(at("2027-02-28T00:00:00Z")));
+  }
+
+  #[test]
+  fn count_stops_the_series_after_the_given_number_of_occurrences() {
+    assert_eq!(next("FREQ=DAILY;COUNT=3", "2026-10-19T08:00:00Z", 2), Some(at("2026-10-20T08:00:00Z")));
+    assert_eq!(next("FREQ=DAILY;COUNT=3", "2026-10-20T08:00:00Z", 3), None);
+  }
+
+  #[test]
+  fn until_stops_the_series_after_the_given_date() {
+    assert_eq!(next("FREQ=WEEKLY;UNTIL=20261102", "2026-10-19T08:00:00Z", 1), Some(at("2026-10-26T08:00:00Z")));
+    assert_eq!(next("FREQ=WEEKLY;UNTIL=20261102", "2026-10-26T08:00:00Z", 2), Some(at("2026-11-02T08:00:00Z")));
+    assert_eq!(next("FREQ=WEEKLY;UNTIL=20261102T000000Z", "2026-10-26T08:00:00Z", 2), None);
+  }
+
+  #[test]
+  fn count_and_until_together_are_rejected() {
+    assert!("FREQ=DAILY;COUNT=3;UNTIL=20261102".parse::<RecurrenceRule>().is_err());
+  }
+}
@@ -0,0 +1,35 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use sqlx::FromRow;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+pub const QUERY_MAX_LENGTH: usize = 1_000;
+
+// 名前を付けて保存したフィルタ式（スマートリスト）。作成したユーザーだけが使える
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavedFilter {
+  pub id: Uuid,
+  pub user_id: Uuid,
+  pub name: String,
+  pub query: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+impl SavedFilter {
+  pub fn new(user_id: Uuid, name: String, query: String) -> Self {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
+    let now_utc = now_jst.with_timezone(&Utc);
+
+    Self {
+      id: Uuid::now_v7(),
+      user_id,
+      name,
+      query,
+      created_at: now_utc,
+      updated_at: now_utc
+    }
+  }
+}
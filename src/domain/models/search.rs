@@ -0,0 +1,191 @@
+// 全文検索の結果と、スニペットのハイライト処理
+
+// ハイライト前後に残す文字数
+const SNIPPET_CONTEXT_CHARS: usize = 30;
+
+#[derive(Debug, Clone)]
+pub struct SearchHit<T> {
+  pub item: T,
+  pub rank: f32,
+  pub snippet: Option<String>,
+}
+
+// 検索語を空白で分割する。日本語は分かち書きされないため、語はそのまま部分一致に使う
+pub fn search_terms(query: &str) -> Vec<String> {
+  query
+    .split_whitespace()
+    .map(|term| term.trim_matches('"').to_string())
+    .filter(|term| !term.is_empty())
+    .collect()
+}
+
+// `text` 中の最初の一致箇所の前後を切り出し、一致部分を <mark> で囲む。
+// クライアントが HTML として描画できるよう、本文はエスケープする
+pub fn highlight(text: &str, terms: &[String]) -> Option<String> {
+  let chars: Vec<char> = text.chars().collect();
+  let folded: Vec<char> = chars.iter().map(|c| fold(*c)).collect();
+  let terms: Vec<Vec<char>> = terms
+    .iter()
+    .map(|t| t.chars().map(fold).collect::<Vec<char>>())
+    .filter(|t| !t.is_empty())
+    .collect();
+
+  let matches = find_matches(&folded, &terms);
+  let first = matches.first()?.0;
+
+  let start = first.saturating_sub(SNIPPET_CONTEXT_CHARS);
+  let end = (first + SNIPPET_CONTEXT_CHARS * 2).min(chars.len());
+
+  let mut snippet = String::new();
+  if start > 0 {
+    snippet.push('…');
+  }
+  let mut pos = start;
+  for (match_start, match_end) in matches.into_iter().filter(|(s, _)| *s >= start && *s < end) {
+    if match_start < pos {
+      continue;
+    }
+    push_escaped(&mut snippet, &chars[pos..match_start]);
+    snippet.push_str("<mark>");
+    push_escaped(&mut snippet, &chars[match_start..match_end]);
+    snippet.push_str("</mark>");
+    pos = match_end;
+  }
+  if pos < end {
+    push_escaped(&mut snippet, &chars[pos..end]);
+  }
+  if end < chars.len() {
+    snippet.push('…');
+  }
+  Some(snippet)
+}
+
+fn fold(c: char) -> char {
+  c.to_lowercase().next().unwrap_or(c)
+}
+
+// 一致箇所を (開始, 終了) の文字インデックスで、開始位置順に返す
+fn find_matches(text: &[char], terms: &[Vec<char>]) -> Vec<(usize, usize)> {
+  let mut matches = Vec::new();
+  let mut i = 0;
+  while i < text.len() {
+    let longest = terms
+      .iter()
+      .filter(|term| text[i..].starts_with(term))
+      .map(|term| term.len())
+      .max();
+    match longest {
+      Some(len) => {
+        matches.push((i, i + len));
+        i += len;
+      }
+      None => i += 1,
+    }
+  }
+  matches
+}
+
+fn push_escaped(out: &mut String, chars: &[char]) {
+  for c in chars {
+    match c {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      _ => out.push(*c),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn terms(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+  }
+
+  #[test]
+  fn search_terms_split_on_any_whitespace_and_drop_quotes() {
+    assert_eq!(search_terms("  会議\u{3000}資料 \"weekly\" \"\" "), terms(&["会議", "資料", "weekly"]));
+  }
+
+  #[test]
+  fn no_match_returns_none() {
+    assert_eq!(highlight("weekly report", &terms(&["monthly"])), None);
+    assert_eq!(highlight("weekly report", &terms(&[""])), None);
+  }
+
+  #[test]
+  fn html_in_the_text_is_escaped() {
+    assert_eq!(
+      highlight("a < b & \"c\" <b>report</b>", &terms(&["report"])),
+      Some("a &lt; b &amp; &quot;c&quot; &lt;b&gt;<mark>report</mark>&lt;/b&gt;".to_string())
+    );
+  }
+
+  #[test]
+  fn html_in_the_query_is_matched_literally_and_escaped() {
+    assert_eq!(
+      highlight("x <script>alert(1)</script>", &terms(&["<script>"])),
+      Some("x <mark>&lt;script&gt;</mark>alert(1)&lt;/script&gt;".to_string())
+    );
+    assert_eq!(highlight("a & b", &terms(&["&"])), Some("a <mark>&amp;</mark> b".to_string()));
+  }
+
+  #[test]
+  fn matches_ignore_case_and_keep_the_original_text() {
+    assert_eq!(highlight("Hello WORLD", &terms(&["world"])), Some("Hello <mark>WORLD</mark>".to_string()));
+    assert_eq!(highlight("ÉCOLE", &terms(&["école"])), Some("<mark>ÉCOLE</mark>".to_string()));
+  }
+
+  #[test]
+  fn cjk_terms_match_without_word_boundaries() {
+    assert_eq!(
+      highlight("今日は会議の資料を作る", &terms(&["会議", "資料"])),
+      Some("今日は<mark>会議</mark>の<mark>資料</mark>を作る".to_string())
+    );
+  }
+
+  #[test]
+  fn longest_term_wins_at_the_same_position() {
+    assert_eq!(highlight("会議室", &terms(&["会", "会議"])), Some("<mark>会議</mark>室".to_string()));
+  }
+
+  #[test]
+  fn long_text_is_cut_around_the_first_match() {
+    let text = format!("{}needle{}", "x".repeat(40), "y".repeat(70));
+    let expected = format!("…{}<mark>needle</mark>{}…", "x".repeat(SNIPPET_CONTEXT_CHARS), "y".repeat(54));
+    assert_eq!(highlight(&text, &terms(&["needle"])), Some(expected));
+  }
+
+  #[test]
+  fn ellipsis_is_added_only_when_text_is_cut() {
+    // 一致箇所の前がちょうど SNIPPET_CONTEXT_CHARS 文字なら先頭は切らない
+    let text = format!("{}needle", "x".repeat(SNIPPET_CONTEXT_CHARS));
+    assert_eq!(highlight(&text, &terms(&["needle"])), Some(format!("{}<mark>needle</mark>", "x".repeat(SNIPPET_CONTEXT_CHARS))));
+
+    let text = format!("{}needle", "x".repeat(SNIPPET_CONTEXT_CHARS + 1));
+    assert_eq!(highlight(&text, &terms(&["needle"])), Some(format!("…{}<mark>needle</mark>", "x".repeat(SNIPPET_CONTEXT_CHARS))));
+
+    // 末尾は一致箇所の開始から SNIPPET_CONTEXT_CHARS * 2 文字まで
+    let text = format!("needle{}", "y".repeat(SNIPPET_CONTEXT_CHARS * 2 - 6));
+    assert!(highlight(&text, &terms(&["needle"])).unwrap().ends_with('y'));
+    let text = format!("needle{}", "y".repeat(SNIPPET_CONTEXT_CHARS * 2 - 5));
+    assert!(highlight(&text, &terms(&["needle"])).unwrap().ends_with("y…"));
+  }
+
+  #[test]
+  fn only_matches_inside_the_window_are_marked() {
+    let text = format!("needle{}needle", "y".repeat(SNIPPET_CONTEXT_CHARS * 2));
+    let snippet = highlight(&text, &terms(&["needle"])).unwrap();
+    assert_eq!(snippet.matches("<mark>").count(), 1);
+    assert!(snippet.ends_with('…'));
+  }
+
+  #[test]
+  fn window_counts_characters_not_bytes() {
+    let text = format!("{}会議", "あ".repeat(SNIPPET_CONTEXT_CHARS + 5));
+    assert_eq!(highlight(&text, &terms(&["会議"])), Some(format!("…{}<mark>会議</mark>", "あ".repeat(SNIPPET_CONTEXT_CHARS))));
+  }
+}
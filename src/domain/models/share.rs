@@ -0,0 +1,95 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+
+use crate::domain::models::organization::MembershipRole;
+
+// メールアドレス宛ての共有の招待の有効期間。リンクは取り消すまで有効
+pub const SHARE_INVITATION_TTL: Duration = Duration::days(7);
+
+// 共有したユーザーに許可する操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SharePermission {
+  // Todo とそのコメント・添付ファイルの参照
+  View,
+  // Todo の作成・変更・削除。プロジェクト自体の変更はできない
+  Edit,
+}
+
+impl SharePermission {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      SharePermission::View => "view",
+      SharePermission::Edit => "edit",
+    }
+  }
+
+  // 共有されたプロジェクトとしてアクセスする際の役割。許可する操作は domain::policy で決める
+  pub fn role(&self) -> MembershipRole {
+    match self {
+      SharePermission::View => MembershipRole::Viewer,
+      SharePermission::Edit => MembershipRole::Member,
+    }
+  }
+}
+
+impl TryFrom<String> for SharePermission {
+  type Error = String;
+
+  fn try_from(value: String) -> Result<Self, Self::Error> {
+    match value.as_str() {
+      "view" => Ok(SharePermission::View),
+      "edit" => Ok(SharePermission::Edit),
+      _ => Err(format!("unknown share permission '{}'", value)),
+    }
+  }
+}
+
+// 保存された共有。トークン自体は保存せず SHA-256 のハッシュで照合する
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectShare {
+  pub id: Uuid,
+  pub project_id: Uuid,
+  #[sqlx(try_from = "String")]
+  pub permission: SharePermission,
+  // 招待したメールアドレス。None はリンクによる共有
+  pub email: Option<String>,
+  pub token_hash: String,
+  pub created_by: Option<Uuid>,
+  pub expires_at: Option<DateTime<Utc>>,
+  // 招待が受け入れられた日時。リンクは何度でも受け入れられるため記録しない
+  pub accepted_at: Option<DateTime<Utc>>,
+  pub created_at: DateTime<Utc>,
+}
+
+// 共有の作成結果。トークンはこの時だけ返す
+#[derive(Debug, Clone)]
+pub struct IssuedShare {
+  pub share: ProjectShare,
+  pub token: String,
+}
+
+// 共有を受け入れたユーザー
+#[derive(Debug, Clone, FromRow)]
+pub struct Collaborator {
+  pub user_id: Uuid,
+  pub name: String,
+  pub email: String,
+  #[sqlx(try_from = "String")]
+  pub permission: SharePermission,
+  pub created_at: DateTime<Utc>,
+}
+
+// ユーザーに共有されているプロジェクト
+#[derive(Debug, Clone, FromRow)]
+pub struct SharedProject {
+  pub project_id: Uuid,
+  pub name: String,
+  pub organization_id: Uuid,
+  pub organization_name: String,
+  #[sqlx(try_from = "String")]
+  pub permission: SharePermission,
+  pub shared_at: DateTime<Utc>,
+}
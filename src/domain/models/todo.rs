@@ -2,33 +2,288 @@ use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc, FixedOffset, TimeZone};
 use sqlx::FromRow;
+use sqlx::types::Json;
+use std::fmt;
+use std::str::FromStr;
 
+use crate::domain::models::recurrence::RecurrenceRule;
+use crate::domain::models::user::UserSummary;
+use crate::domain::validation::ValidationErrors;
+
+pub const TITLE_MAX_LENGTH: usize = 200;
+pub const DESCRIPTION_MAX_LENGTH: usize = 10_000;
+pub const MAX_TAGS: usize = 20;
+pub const TAG_MAX_LENGTH: usize = 50;
+
+// 優先度。大小比較できるよう SMALLINT で保存する
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[repr(i16)]
+pub enum Priority {
+  Low = 0,
+  #[default]
+  Normal = 1,
+  High = 2,
+  Urgent = 3,
+}
+
+impl Priority {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Priority::Low => "low",
+      Priority::Normal => "normal",
+      Priority::High => "high",
+      Priority::Urgent => "urgent",
+    }
+  }
+}
+
+impl fmt::Display for Priority {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for Priority {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_ascii_lowercase().as_str() {
+      "low" => Ok(Priority::Low),
+      "normal" => Ok(Priority::Normal),
+      "high" => Ok(Priority::High),
+      "urgent" => Ok(Priority::Urgent),
+      _ => Err(format!("unknown priority '{}'", s)),
+    }
+  }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Todo {
   pub id: Uuid,
   pub title: String,
   pub description: Option<String>,
+  // ステータスが完了扱いかどうか（status から導出して保存する）
   pub completed: bool,
+  pub project_id: Option<Uuid>,
+  // ワークフロー上のステータスのキー
+  pub status: String,
+  pub priority: Priority,
+  pub tags: Vec<String>,
+  pub due_date: Option<DateTime<Utc>>,
+  // RFC 5545 の RRULE 文字列（例: "FREQ=WEEKLY;BYDAY=MO,WE"）
+  pub recurrence: Option<String>,
+  // 繰り返し Todo のシリーズID（最初の Todo の id）
+  pub series_id: Option<Uuid>,
+  // 手動並べ替え用のキー（domain::models::rank を参照）
+  pub rank: String,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
+  // 更新のたびに DB で増える版数（ETag に使う）
+  pub version: i64,
+  // ゴミ箱に移動した日時
+  pub deleted_at: Option<DateTime<Utc>>,
+  // コメント数（保存せず、取得時に集計する）
+  pub comment_count: i64,
+  // 担当者（todo_assignees から取得時に集める）
+  pub assignees: Json<Vec<UserSummary>>,
+}
+
+// 作成・更新時にクライアントが指定する Todo の内容
+#[derive(Debug, Clone)]
+pub struct TodoDraft {
+  pub title: String,
+  pub description: String,
+  pub project_id: Option<Uuid>,
+  pub priority: Priority,
+  pub tags: Vec<String>,
+  pub due_date: Option<DateTime<Utc>>,
+  pub recurrence: Option<String>,
+}
+
+// 部分更新（JSON Merge Patch）の内容。None の項目は変更せず、Some(None) は null にする
+#[derive(Debug, Clone, Default)]
+pub struct TodoPatch {
+  pub title: Option<String>,
+  pub description: Option<Option<String>>,
+  pub project_id: Option<Option<Uuid>>,
+  pub status: Option<String>,
+  pub completed: Option<bool>,
+  pub priority: Option<Priority>,
+  pub tags: Option<Vec<String>>,
+  pub due_date: Option<Option<DateTime<Utc>>>,
+  pub recurrence: Option<Option<String>>,
+  pub series_id: Option<Option<Uuid>>,
+}
+
+impl TodoDraft {
+  pub fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("title", &self.title, 1, TITLE_MAX_LENGTH);
+    errors.length("description", &self.description, 0, DESCRIPTION_MAX_LENGTH);
+    validate_tags(&mut errors, &self.tags);
+    validate_recurrence(&mut errors, self.due_date, self.recurrence.as_deref());
+    errors.into_result()
+  }
+}
+
+// タグの数と長さ（前後の空白を除いた後の値に対して検証する）
+pub fn validate_tags(errors: &mut ValidationErrors, tags: &[String]) {
+  errors.max_items("tags", tags.len(), MAX_TAGS);
+  for (index, tag) in tags.iter().enumerate() {
+    errors.length(&format!("tags[{}]", index), tag, 1, TAG_MAX_LENGTH);
+  }
+}
+
+// 繰り返し設定は期日とセットでなければならず、RRULE として解釈できる必要がある
+pub fn validate_recurrence(errors: &mut ValidationErrors, due_date: Option<DateTime<Utc>>, recurrence: Option<&str>) {
+  let Some(recurrence) = recurrence else {
+    return;
+  };
+  if due_date.is_none() {
+    errors.add("due_date", "required", "due_date is required for recurring todos");
+  }
+  if let Err(err) = recurrence.parse::<RecurrenceRule>() {
+    errors.add("recurrence", "format", err.to_string());
+  }
+}
+
+impl TodoPatch {
+  // 含まれる項目だけを検証する。繰り返し設定と期日の組み合わせは変更後の Todo に対して検証する
+  pub fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if let Some(title) = &self.title {
+      errors.length("title", title, 1, TITLE_MAX_LENGTH);
+    }
+    if let Some(Some(description)) = &self.description {
+      errors.length("description", description, 0, DESCRIPTION_MAX_LENGTH);
+    }
+    if let Some(tags) = &self.tags {
+      validate_tags(&mut errors, tags);
+    }
+    errors.into_result()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.title.is_none()
+      && self.description.is_none()
+      && self.project_id.is_none()
+      && self.status.is_none()
+      && self.completed.is_none()
+      && self.priority.is_none()
+      && self.tags.is_none()
+      && self.due_date.is_none()
+      && self.recurrence.is_none()
+      && self.series_id.is_none()
+  }
 }
 
 impl Todo {
-  pub fn new(title: String, description: String) -> Self {
+  pub fn new(draft: TodoDraft) -> Result<Self, ValidationErrors> {
+    draft.validate()?;
+    Ok(Self::from_draft(draft))
+  }
+
+  // 検証済みのドラフトから生成する
+  fn from_draft(draft: TodoDraft) -> Self {
     // 日本時間のオフセット（UTC+9時間）
     let jst = FixedOffset::east_opt(9 * 3600).unwrap();
     // 現在の日本時間を取得し、UTCに変換
     let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
     let now_utc = now_jst.with_timezone(&Utc);
-        
+
+    let id = Uuid::now_v7();
+    // 繰り返し設定がある場合は自身を起点にシリーズを開始する
+    let series_id = draft.recurrence.as_ref().map(|_| id);
+
     Self {
-      id: Uuid::now_v7(),
-      title,
-      description: Some(description),
+      id,
+      title: draft.title,
+      description: Some(draft.description),
       completed: false,
+      project_id: draft.project_id,
+      // ステータスは保存時にワークフローの初期ステータスを割り当てる
+      status: String::new(),
+      priority: draft.priority,
+      tags: draft.tags,
+      due_date: draft.due_date,
+      recurrence: draft.recurrence,
+      series_id,
+      // 並び順は保存時に末尾のキーを割り当てる
+      rank: String::new(),
       created_at: now_utc,
-      updated_at: now_utc
+      updated_at: now_utc,
+      version: 1,
+      deleted_at: None,
+      comment_count: 0,
+      assignees: Json(Vec::new()),
+    }
+  }
+
+  // パッチを適用した後の内容。保存する前に繰り返しの次回分を求めるために使う
+  pub fn patched(&self, patch: &TodoPatch) -> Self {
+    let mut todo = self.clone();
+    if let Some(title) = &patch.title {
+      todo.title = title.clone();
     }
+    if let Some(description) = &patch.description {
+      todo.description = description.clone();
+    }
+    if let Some(project_id) = patch.project_id {
+      todo.project_id = project_id;
+    }
+    if let Some(status) = &patch.status {
+      todo.status = status.clone();
+    }
+    if let Some(completed) = patch.completed {
+      todo.completed = completed;
+    }
+    if let Some(priority) = patch.priority {
+      todo.priority = priority;
+    }
+    if let Some(tags) = &patch.tags {
+      todo.tags = tags.clone();
+    }
+    if let Some(due_date) = patch.due_date {
+      todo.due_date = due_date;
+    }
+    if let Some(recurrence) = &patch.recurrence {
+      todo.recurrence = recurrence.clone();
+    }
+    if let Some(series_id) = patch.series_id {
+      todo.series_id = series_id;
+    }
+    todo
+  }
+
+  // ドラフトの内容で上書きする（ステータス・プロジェクト・シリーズは変更しない）
+  pub fn apply(&mut self, draft: TodoDraft) -> Result<(), ValidationErrors> {
+    draft.validate()?;
+    self.title = draft.title;
+    self.description = Some(draft.description);
+    self.priority = draft.priority;
+    self.tags = draft.tags;
+    self.due_date = draft.due_date;
+    self.recurrence = draft.recurrence;
+    if self.recurrence.is_some() && self.series_id.is_none() {
+      self.series_id = Some(self.id);
+    }
+    Ok(())
   }
-}
\ No newline at end of file
+
+  // 完了した繰り返し Todo から、次回分の Todo を生成する（制限を設ける前の Todo からも生成できるよう検証しない）
+  pub fn next_occurrence(&self, due_date: DateTime<Utc>) -> Self {
+    let mut next = Todo::from_draft(TodoDraft {
+      title: self.title.clone(),
+      description: String::new(),
+      project_id: self.project_id,
+      priority: self.priority,
+      tags: self.tags.clone(),
+      due_date: Some(due_date),
+      recurrence: self.recurrence.clone(),
+    });
+    next.description = self.description.clone();
+    next.series_id = self.series_id.or(Some(self.id));
+    next
+  }
+}
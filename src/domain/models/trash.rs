@@ -0,0 +1,25 @@
+use sqlx::FromRow;
+
+use crate::domain::models::invoice::Invoice;
+use crate::domain::models::todo::Todo;
+
+// パージで物理削除した件数と、あわせて削除された添付ファイルの BlobStore 上のキー
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct Purged {
+  pub count: i64,
+  pub storage_keys: Vec<String>,
+}
+
+// ゴミ箱の中身（新しく削除したものから）
+#[derive(Debug, Clone)]
+pub struct Trash {
+  pub todos: Vec<Todo>,
+  pub invoices: Vec<Invoice>,
+}
+
+// パージ 1 回で物理削除した件数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PurgeSummary {
+  pub todos: i64,
+  pub invoices: i64,
+}
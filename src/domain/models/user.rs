@@ -0,0 +1,51 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use sqlx::FromRow;
+
+pub const NAME_MAX_LENGTH: usize = 100;
+// RFC 5321 のアドレス長の上限
+pub const EMAIL_MAX_LENGTH: usize = 254;
+// メールアドレスの変更を確認できる期間
+pub const EMAIL_VERIFICATION_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct User {
+  pub id: Uuid,
+  pub name: String,
+  pub email: String,
+  pub created_at: DateTime<Utc>,
+  pub updated_at: DateTime<Utc>,
+}
+
+// 確認待ちのメールアドレスの変更。トークンはハッシュだけを保存する
+#[derive(Debug, Clone)]
+pub struct EmailVerification {
+  pub user_id: Uuid,
+  pub email: String,
+  pub token_hash: String,
+  pub expires_at: DateTime<Utc>,
+}
+
+// 他のリソースに埋め込む最小限のユーザー情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow)]
+pub struct UserSummary {
+  pub id: Uuid,
+  pub name: String,
+}
+
+impl User {
+  pub fn new(name: String, email: String) -> Self {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let now_jst = jst.from_utc_datetime(&Utc::now().naive_utc());
+    let now_utc = now_jst.with_timezone(&Utc);
+
+    Self {
+      id: Uuid::now_v7(),
+      name,
+      email,
+      created_at: now_utc,
+      updated_at: now_utc
+    }
+  }
+}
@@ -0,0 +1,127 @@
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+
+use crate::domain::models::todo::Todo;
+
+pub const MAX_STATUSES: usize = 20;
+pub const STATUS_KEY_MAX_LENGTH: usize = 50;
+pub const STATUS_NAME_MAX_LENGTH: usize = 50;
+
+// プロジェクトごとに設定するワークフロー（カンバンの列と、列の間で許可する移動）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workflow {
+  // 並び順がそのままボードの列順になる。先頭が新規作成時のステータス
+  pub statuses: Vec<WorkflowStatus>,
+  // 空の場合はすべての遷移を許可する
+  #[serde(default)]
+  pub transitions: Vec<Transition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowStatus {
+  pub key: String,
+  pub name: String,
+  // このステータスの Todo は完了扱い（TodoResponse の completed）
+  #[serde(default)]
+  pub done: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transition {
+  pub from: String,
+  pub to: String,
+}
+
+impl Default for Workflow {
+  // プロジェクトに属さない Todo と、ワークフロー未指定のプロジェクトで使う
+  fn default() -> Self {
+    let status = |key: &str, name: &str, done: bool| WorkflowStatus {
+      key: key.to_string(),
+      name: name.to_string(),
+      done,
+    };
+    Self {
+      statuses: vec![
+        status("backlog", "Backlog", false),
+        status("todo", "Todo", false),
+        status("in_progress", "In Progress", false),
+        status("review", "Review", false),
+        status("done", "Done", true),
+      ],
+      transitions: Vec::new(),
+    }
+  }
+}
+
+impl Workflow {
+  pub fn validate(&self) -> Result<(), String> {
+    if self.statuses.is_empty() {
+      return Err("workflow must have at least one status".to_string());
+    }
+
+    let mut keys = HashSet::new();
+    for status in &self.statuses {
+      if status.key.trim().is_empty() {
+        return Err("status key must not be empty".to_string());
+      }
+      if !keys.insert(status.key.as_str()) {
+        return Err(format!("duplicate status key '{}'", status.key));
+      }
+    }
+
+    if !self.statuses.iter().any(|s| s.done) {
+      return Err("workflow must have at least one done status".to_string());
+    }
+    if self.statuses.first().is_some_and(|s| s.done) {
+      return Err("the first (initial) status must not be a done status".to_string());
+    }
+
+    for transition in &self.transitions {
+      for key in [&transition.from, &transition.to] {
+        if !keys.contains(key.as_str()) {
+          return Err(format!("transition refers to unknown status '{}'", key));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  pub fn status(&self, key: &str) -> Option<&WorkflowStatus> {
+    self.statuses.iter().find(|s| s.key == key)
+  }
+
+  pub fn initial_status(&self) -> &WorkflowStatus {
+    &self.statuses[0]
+  }
+
+  pub fn is_done(&self, key: &str) -> bool {
+    self.status(key).is_some_and(|s| s.done)
+  }
+
+  pub fn can_transition(&self, from: &str, to: &str) -> bool {
+    from == to
+      || self.transitions.is_empty()
+      || self.transitions.iter().any(|t| t.from == from && t.to == to)
+  }
+
+  // completed の切り替えに対応する遷移先として、`from` から移動できる最初の完了（または未完了）ステータスを返す
+  pub fn first_reachable(&self, from: &str, done: bool) -> Option<&WorkflowStatus> {
+    self.statuses
+      .iter()
+      .find(|s| s.done == done && self.can_transition(from, &s.key))
+  }
+
+  // 別のワークフローへ移るとき、同じキーがあればそれを、なければ完了状態が同じ最初のステータスを使う
+  pub fn equivalent_status(&self, key: &str, done: bool) -> &WorkflowStatus {
+    self.status(key)
+      .or_else(|| self.statuses.iter().find(|s| s.done == done))
+      .unwrap_or_else(|| self.initial_status())
+  }
+}
+
+// ボードの 1 列
+#[derive(Debug, Clone)]
+pub struct BoardColumn {
+  pub status: WorkflowStatus,
+  pub todos: Vec<Todo>,
+}
@@ -0,0 +1,102 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::MembershipRole;
+
+// 組織内で役割によって制限する操作。参照は組織のメンバーであれば役割によらずできる
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+  // Todo の作成・変更・削除と、そのコメント・添付ファイル・担当者の変更、操作の取り消し
+  EditTodos,
+  // プロジェクトとワークフローの作成・変更・削除
+  ManageProjects,
+  // 請求書の発行・変更・取り消し（ゴミ箱への移動と復元）と、その添付ファイルの変更
+  ManageInvoices,
+  // メンバーの招待・除外・役割の変更
+  ManageMembers,
+  // 他のユーザーが投稿したコメントの編集・削除（自分のコメントは EditTodos で編集・削除できる）
+  ModerateComments,
+}
+
+impl Permission {
+  fn describe(&self) -> &'static str {
+    match self {
+      Permission::EditTodos => "edit todos",
+      Permission::ManageProjects => "manage projects",
+      Permission::ManageInvoices => "issue or void invoices",
+      Permission::ManageMembers => "manage members",
+      Permission::ModerateComments => "edit or delete comments by other users",
+    }
+  }
+}
+
+// 役割ごとに許可する操作
+//
+// |                  | owner | admin | accountant | member | viewer |
+// |------------------|-------|-------|------------|--------|--------|
+// | EditTodos        |   o   |   o   |     o      |   o    |        |
+// | ManageProjects   |   o   |   o   |            |        |        |
+// | ManageInvoices   |   o   |       |     o      |        |        |
+// | ManageMembers    |   o   |   o   |            |        |        |
+// | ModerateComments |   o   |   o   |            |        |        |
+//
+// owner はすべての操作ができる（個人用の組織では本人が owner）。請求書は職務を分けるため、admin には許可しない
+pub fn allows(role: MembershipRole, permission: Permission) -> bool {
+  use MembershipRole::*;
+  match permission {
+    Permission::EditTodos => matches!(role, Owner | Admin | Accountant | Member),
+    Permission::ManageProjects => matches!(role, Owner | Admin),
+    Permission::ManageInvoices => matches!(role, Owner | Accountant),
+    Permission::ManageMembers => matches!(role, Owner | Admin),
+    Permission::ModerateComments => matches!(role, Owner | Admin),
+  }
+}
+
+// 許可されていない場合は Forbidden
+pub fn authorize(role: MembershipRole, permission: Permission) -> Result<(), AppError> {
+  if allows(role, permission) {
+    return Ok(());
+  }
+  Err(AppError::Forbidden(format!("The {} role is not allowed to {}", role.as_str(), permission.describe())))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use MembershipRole::*;
+
+  const PERMISSIONS: [Permission; 5] = [
+    Permission::EditTodos,
+    Permission::ManageProjects,
+    Permission::ManageInvoices,
+    Permission::ManageMembers,
+    Permission::ModerateComments,
+  ];
+
+  // 上の表と同じ並び（EditTodos, ManageProjects, ManageInvoices, ManageMembers, ModerateComments）
+  fn expected(role: MembershipRole) -> [bool; 5] {
+    match role {
+      Owner => [true, true, true, true, true],
+      Admin => [true, true, false, true, true],
+      Accountant => [true, false, true, false, false],
+      Member => [true, false, false, false, false],
+      Viewer => [false, false, false, false, false],
+    }
+  }
+
+  #[test]
+  fn allows_matches_the_role_matrix() {
+    for role in [Owner, Admin, Accountant, Member, Viewer] {
+      for (permission, allowed) in PERMISSIONS.into_iter().zip(expected(role)) {
+        assert_eq!(allows(role, permission), allowed, "{} / {:?}", role.as_str(), permission);
+      }
+    }
+  }
+
+  #[test]
+  fn authorize_rejects_with_forbidden() {
+    assert!(authorize(Member, Permission::EditTodos).is_ok());
+    match authorize(Admin, Permission::ManageInvoices) {
+      Err(AppError::Forbidden(message)) => assert_eq!(message, "The admin role is not allowed to issue or void invoices"),
+      other => panic!("unexpected result: {:?}", other),
+    }
+  }
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::domain::models::todo::Priority;
+
+// フィルタ式の構文木
+// 例: completed:false AND (tag:work OR priority>=high) AND due<2026-11-01
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+  And(Box<Filter>, Box<Filter>),
+  Or(Box<Filter>, Box<Filter>),
+  Not(Box<Filter>),
+  Condition(Condition),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+  pub field: Field,
+  pub op: CompareOp,
+  pub value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+  Completed,
+  Tag,
+  Priority,
+  Due,
+  Created,
+  Updated,
+  Title,
+  Description,
+}
+
+impl Field {
+  pub fn parse(name: &str) -> Option<Self> {
+    match name.to_ascii_lowercase().as_str() {
+      "completed" => Some(Field::Completed),
+      "tag" => Some(Field::Tag),
+      "priority" => Some(Field::Priority),
+      "due" => Some(Field::Due),
+      "created" => Some(Field::Created),
+      "updated" => Some(Field::Updated),
+      "title" => Some(Field::Title),
+      "description" => Some(Field::Description),
+      _ => None,
+    }
+  }
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      Field::Completed => "completed",
+      Field::Tag => "tag",
+      Field::Priority => "priority",
+      Field::Due => "due",
+      Field::Created => "created",
+      Field::Updated => "updated",
+      Field::Title => "title",
+      Field::Description => "description",
+    }
+  }
+
+  // 各フィールドで使える演算子
+  pub fn allows(&self, op: CompareOp) -> bool {
+    match self {
+      Field::Completed | Field::Tag | Field::Title | Field::Description => {
+        matches!(op, CompareOp::Match | CompareOp::Eq | CompareOp::NotEq)
+      }
+      Field::Priority | Field::Due | Field::Created | Field::Updated => true,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+  // `:` — 文字列フィールドでは部分一致、それ以外では等価
+  Match,
+  Eq,
+  NotEq,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+impl CompareOp {
+  pub fn symbol(&self) -> &'static str {
+    match self {
+      CompareOp::Match => ":",
+      CompareOp::Eq => "=",
+      CompareOp::NotEq => "!=",
+      CompareOp::Lt => "<",
+      CompareOp::Le => "<=",
+      CompareOp::Gt => ">",
+      CompareOp::Ge => ">=",
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Bool(bool),
+  Text(String),
+  Priority(Priority),
+  // 日付のみの指定は日本時間のその日全体を表す
+  Date(NaiveDate),
+  Timestamp(DateTime<Utc>),
+  // `due:none` のように値が無いことを表す
+  Null,
+}
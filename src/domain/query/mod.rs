@@ -0,0 +1,23 @@
+// Todo 一覧のフィルタ言語。文字列を構文木に変換し、SQL への変換はリポジトリ実装が行う
+pub mod ast;
+pub mod parser;
+
+use ast::Filter;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TodoOrder {
+  // 作成順（UUIDv7 の id 順）
+  #[default]
+  Created,
+  // ユーザーが並べ替えた順（rank 順）
+  Manual,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TodoQuery {
+  pub filter: Option<Filter>,
+  pub order: TodoOrder,
+  // 指定したユーザーが担当している Todo に絞り込む
+  pub assignee: Option<Uuid>,
+}
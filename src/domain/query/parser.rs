@@ -0,0 +1,462 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+
+use crate::domain::models::todo::Priority;
+use crate::domain::query::ast::{CompareOp, Condition, Field, Filter, Value};
+
+const MAX_QUERY_LENGTH: usize = 1000;
+const MAX_DEPTH: usize = 32;
+
+// 解析エラー。position は問題のあるトークンの開始位置（0 始まりの文字数）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+  pub message: String,
+  pub position: usize,
+  pub token: String,
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at position {} ('{}')", self.message, self.position, self.token)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+  LParen,
+  RParen,
+  And,
+  Or,
+  Not,
+  Op(CompareOp),
+  Word(String),
+  Str(String),
+  End,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+  kind: TokenKind,
+  text: String,
+  position: usize,
+}
+
+fn error(message: impl Into<String>, token: &Token) -> ParseError {
+  ParseError {
+    message: message.into(),
+    position: token.position,
+    token: token.text.clone(),
+  }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+  let chars: Vec<char> = input.chars().collect();
+  let mut tokens = Vec::new();
+  let mut i = 0;
+
+  while i < chars.len() {
+    let c = chars[i];
+    let start = i;
+
+    if c.is_whitespace() {
+      i += 1;
+      continue;
+    }
+
+    let kind = match c {
+      '(' => {
+        i += 1;
+        TokenKind::LParen
+      }
+      ')' => {
+        i += 1;
+        TokenKind::RParen
+      }
+      ':' | '=' => {
+        i += 1;
+        TokenKind::Op(if c == ':' { CompareOp::Match } else { CompareOp::Eq })
+      }
+      '!' | '<' | '>' => {
+        let followed_by_eq = chars.get(i + 1) == Some(&'=');
+        i += if followed_by_eq { 2 } else { 1 };
+        match (c, followed_by_eq) {
+          ('!', true) => TokenKind::Op(CompareOp::NotEq),
+          ('<', false) => TokenKind::Op(CompareOp::Lt),
+          ('<', true) => TokenKind::Op(CompareOp::Le),
+          ('>', false) => TokenKind::Op(CompareOp::Gt),
+          ('>', true) => TokenKind::Op(CompareOp::Ge),
+          _ => {
+            return Err(ParseError {
+              message: "unexpected character".to_string(),
+              position: start,
+              token: c.to_string(),
+            })
+          }
+        }
+      }
+      '"' => {
+        i += 1;
+        let mut value = String::new();
+        loop {
+          match chars.get(i) {
+            None => {
+              return Err(ParseError {
+                message: "unterminated string".to_string(),
+                position: start,
+                token: chars[start..].iter().collect(),
+              })
+            }
+            Some('"') => {
+              i += 1;
+              break;
+            }
+            Some('\\') if chars.get(i + 1).is_some() => {
+              value.push(chars[i + 1]);
+              i += 2;
+            }
+            Some(ch) => {
+              value.push(*ch);
+              i += 1;
+            }
+          }
+        }
+        TokenKind::Str(value)
+      }
+      _ => {
+        while i < chars.len() && !chars[i].is_whitespace() && !"()\":=!<>".contains(chars[i]) {
+          i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        match word.as_str() {
+          "AND" => TokenKind::And,
+          "OR" => TokenKind::Or,
+          "NOT" => TokenKind::Not,
+          _ => TokenKind::Word(word),
+        }
+      }
+    };
+
+    tokens.push(Token {
+      kind,
+      text: chars[start..i].iter().collect(),
+      position: start,
+    });
+  }
+
+  tokens.push(Token {
+    kind: TokenKind::End,
+    text: String::new(),
+    position: chars.len(),
+  });
+  Ok(tokens)
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+  depth: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> &Token {
+    &self.tokens[self.pos]
+  }
+
+  fn next(&mut self) -> Token {
+    let token = self.tokens[self.pos].clone();
+    if token.kind != TokenKind::End {
+      self.pos += 1;
+    }
+    token
+  }
+
+  // or := and ("OR" and)*
+  fn parse_or(&mut self) -> Result<Filter, ParseError> {
+    let mut left = self.parse_and()?;
+    while self.peek().kind == TokenKind::Or {
+      self.next();
+      let right = self.parse_and()?;
+      left = Filter::Or(Box::new(left), Box::new(right));
+    }
+    Ok(left)
+  }
+
+  // and := unary (("AND")? unary)*  — 隣接する条件は暗黙の AND とみなす
+  fn parse_and(&mut self) -> Result<Filter, ParseError> {
+    let mut left = self.parse_unary()?;
+    loop {
+      match self.peek().kind {
+        TokenKind::And => {
+          self.next();
+        }
+        TokenKind::Word(_) | TokenKind::LParen | TokenKind::Not => {}
+        _ => break,
+      }
+      let right = self.parse_unary()?;
+      left = Filter::And(Box::new(left), Box::new(right));
+    }
+    Ok(left)
+  }
+
+  // unary := "NOT" unary | "(" or ")" | condition
+  fn parse_unary(&mut self) -> Result<Filter, ParseError> {
+    self.depth += 1;
+    if self.depth > MAX_DEPTH {
+      return Err(error("filter is nested too deeply", self.peek()));
+    }
+
+    let result = match self.peek().kind {
+      TokenKind::Not => {
+        self.next();
+        self.parse_unary().map(|f| Filter::Not(Box::new(f)))
+      }
+      TokenKind::LParen => {
+        let open = self.next();
+        let inner = self.parse_or()?;
+        let close = self.next();
+        if close.kind != TokenKind::RParen {
+          let message = format!("expected ')' to close '(' at position {}", open.position);
+          return Err(error(message, &close));
+        }
+        Ok(inner)
+      }
+      _ => self.parse_condition(),
+    };
+
+    self.depth -= 1;
+    result
+  }
+
+  // condition := FIELD OP VALUE
+  fn parse_condition(&mut self) -> Result<Filter, ParseError> {
+    let field_token = self.next();
+    let field = match &field_token.kind {
+      TokenKind::Word(name) => Field::parse(name)
+        .ok_or_else(|| error(format!("unknown field '{}'", name), &field_token))?,
+      TokenKind::End => return Err(error("expected a condition", &field_token)),
+      _ => return Err(error("expected a field name", &field_token)),
+    };
+
+    let op_token = self.next();
+    let TokenKind::Op(op) = op_token.kind else {
+      return Err(error(format!("expected an operator after '{}'", field.name()), &op_token));
+    };
+    if !field.allows(op) {
+      let message = format!("operator '{}' is not supported for '{}'", op.symbol(), field.name());
+      return Err(error(message, &op_token));
+    }
+
+    let value_token = self.next();
+    let raw = match &value_token.kind {
+      TokenKind::Word(word) => word.clone(),
+      TokenKind::Str(value) => value.clone(),
+      _ => return Err(error(format!("expected a value after '{}'", op.symbol()), &value_token)),
+    };
+    let value = parse_value(field, op, &raw).map_err(|message| error(message, &value_token))?;
+
+    Ok(Filter::Condition(Condition { field, op, value }))
+  }
+}
+
+fn parse_value(field: Field, op: CompareOp, raw: &str) -> Result<Value, String> {
+  match field {
+    Field::Completed => match raw.to_ascii_lowercase().as_str() {
+      "true" | "yes" => Ok(Value::Bool(true)),
+      "false" | "no" => Ok(Value::Bool(false)),
+      _ => Err(format!("expected true or false for 'completed', got '{}'", raw)),
+    },
+    Field::Tag | Field::Title | Field::Description => {
+      if raw.is_empty() {
+        return Err(format!("expected a value for '{}'", field.name()));
+      }
+      Ok(Value::Text(raw.to_string()))
+    }
+    Field::Priority => raw
+      .parse::<Priority>()
+      .map(Value::Priority)
+      .map_err(|_| format!("expected one of low, normal, high, urgent for 'priority', got '{}'", raw)),
+    Field::Due | Field::Created | Field::Updated => {
+      if raw.eq_ignore_ascii_case("none") || raw.eq_ignore_ascii_case("null") {
+        if field != Field::Due || !matches!(op, CompareOp::Match | CompareOp::Eq | CompareOp::NotEq) {
+          return Err("'none' can only be compared with ':', '=' or '!=' on 'due'".to_string());
+        }
+        return Ok(Value::Null);
+      }
+      if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(Value::Date(date));
+      }
+      DateTime::parse_from_rfc3339(raw)
+        .map(|dt| Value::Timestamp(dt.with_timezone(&Utc)))
+        .map_err(|_| format!("expected a date (YYYY-MM-DD) or RFC 3339 timestamp, got '{}'", raw))
+    }
+  }
+}
+
+pub fn parse_filter(input: &str) -> Result<Filter, ParseError> {
+  if input.chars().count() > MAX_QUERY_LENGTH {
+    return Err(ParseError {
+      message: format!("filter must be at most {} characters", MAX_QUERY_LENGTH),
+      position: MAX_QUERY_LENGTH,
+      token: String::new(),
+    });
+  }
+
+  let mut parser = Parser {
+    tokens: tokenize(input)?,
+    pos: 0,
+    depth: 0,
+  };
+  let filter = parser.parse_or()?;
+
+  let trailing = parser.next();
+  if trailing.kind != TokenKind::End {
+    return Err(error("unexpected token", &trailing));
+  }
+  Ok(filter)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn text(field: Field, op: CompareOp, value: &str) -> Filter {
+    Filter::Condition(Condition { field, op, value: Value::Text(value.to_string()) })
+  }
+
+  fn tag(value: &str) -> Filter {
+    text(Field::Tag, CompareOp::Match, value)
+  }
+
+  fn and(left: Filter, right: Filter) -> Filter {
+    Filter::And(Box::new(left), Box::new(right))
+  }
+
+  fn or(left: Filter, right: Filter) -> Filter {
+    Filter::Or(Box::new(left), Box::new(right))
+  }
+
+  fn not(filter: Filter) -> Filter {
+    Filter::Not(Box::new(filter))
+  }
+
+  fn parse_error(input: &str) -> ParseError {
+    parse_filter(input).unwrap_err()
+  }
+
+  #[test]
+  fn and_binds_tighter_than_or() {
+    assert_eq!(parse_filter("tag:a OR tag:b AND tag:c"), Ok(or(tag("a"), and(tag("b"), tag("c")))));
+    assert_eq!(parse_filter("tag:a AND tag:b OR tag:c"), Ok(or(and(tag("a"), tag("b")), tag("c"))));
+  }
+
+  #[test]
+  fn not_binds_tighter_than_and() {
+    assert_eq!(parse_filter("NOT tag:a AND tag:b"), Ok(and(not(tag("a")), tag("b"))));
+    assert_eq!(parse_filter("NOT NOT tag:a"), Ok(not(not(tag("a")))));
+  }
+
+  #[test]
+  fn operators_are_left_associative() {
+    assert_eq!(parse_filter("tag:a OR tag:b OR tag:c"), Ok(or(or(tag("a"), tag("b")), tag("c"))));
+    assert_eq!(parse_filter("tag:a AND tag:b AND tag:c"), Ok(and(and(tag("a"), tag("b")), tag("c"))));
+  }
+
+  #[test]
+  fn adjacent_conditions_are_joined_with_and() {
+    assert_eq!(parse_filter("tag:a tag:b"), parse_filter("tag:a AND tag:b"));
+    assert_eq!(parse_filter("tag:a NOT tag:b"), Ok(and(tag("a"), not(tag("b")))));
+    assert_eq!(parse_filter("tag:a (tag:b OR tag:c)"), Ok(and(tag("a"), or(tag("b"), tag("c")))));
+  }
+
+  #[test]
+  fn parentheses_override_precedence() {
+    assert_eq!(parse_filter("(tag:a OR tag:b) AND tag:c"), Ok(and(or(tag("a"), tag("b")), tag("c"))));
+    assert_eq!(parse_filter("NOT (tag:a OR tag:b)"), Ok(not(or(tag("a"), tag("b")))));
+    assert_eq!(parse_filter("((tag:a))"), Ok(tag("a")));
+  }
+
+  #[test]
+  fn quoted_values_keep_spaces_operators_and_escaped_quotes() {
+    assert_eq!(parse_filter("title:\"weekly report\""), Ok(text(Field::Title, CompareOp::Match, "weekly report")));
+    assert_eq!(parse_filter("tag=\"a:b (c)\""), Ok(text(Field::Tag, CompareOp::Eq, "a:b (c)")));
+    assert_eq!(parse_filter(r#"title:"say \"hi\"""#), Ok(text(Field::Title, CompareOp::Match, "say \"hi\"")));
+    assert_eq!(parse_filter("description:\"AND\""), Ok(text(Field::Description, CompareOp::Match, "AND")));
+  }
+
+  #[test]
+  fn values_are_parsed_by_field() {
+    let condition = |field, op, value| Filter::Condition(Condition { field, op, value });
+    assert_eq!(parse_filter("completed:yes"), Ok(condition(Field::Completed, CompareOp::Match, Value::Bool(true))));
+    assert_eq!(parse_filter("priority>=high"), Ok(condition(Field::Priority, CompareOp::Ge, Value::Priority(Priority::High))));
+    assert_eq!(
+      parse_filter("due<2026-11-01"),
+      Ok(condition(Field::Due, CompareOp::Lt, Value::Date(NaiveDate::from_ymd_opt(2026, 11, 1).unwrap())))
+    );
+    assert_eq!(parse_filter("due!=none"), Ok(condition(Field::Due, CompareOp::NotEq, Value::Null)));
+  }
+
+  #[test]
+  fn errors_report_the_position_and_token() {
+    let err = parse_error("tag:a OR bogus:1");
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("unknown field 'bogus'", 9, "bogus"));
+
+    let err = parse_error("tag:a AND");
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("expected a condition", 9, ""));
+
+    let err = parse_error("priority:extreme");
+    assert_eq!((err.position, err.token.as_str()), (9, "extreme"));
+
+    let err = parse_error("tag<a");
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("operator '<' is not supported for 'tag'", 3, "<"));
+
+    let err = parse_error("tag:a)");
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("unexpected token", 5, ")"));
+
+    let err = parse_error("due>none");
+    assert_eq!((err.position, err.token.as_str()), (4, "none"));
+  }
+
+  #[test]
+  fn unclosed_parenthesis_points_at_the_end_and_names_the_open_position() {
+    let err = parse_error("tag:a (tag:b");
+    assert_eq!(err.message, "expected ')' to close '(' at position 6");
+    assert_eq!((err.position, err.token.as_str()), (12, ""));
+  }
+
+  #[test]
+  fn unterminated_string_points_at_the_opening_quote() {
+    let err = parse_error("title:\"abc");
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("unterminated string", 6, "\"abc"));
+  }
+
+  #[test]
+  fn positions_count_characters_not_bytes() {
+    let err = parse_error("title:日本 AND bogus:1");
+    assert_eq!((err.position, err.token.as_str()), (13, "bogus"));
+  }
+
+  #[test]
+  fn nesting_deeper_than_max_depth_is_rejected() {
+    // 条件自体も 1 段に数える
+    let nested = |depth: usize| format!("{}tag:a{}", "(".repeat(depth), ")".repeat(depth));
+    assert_eq!(parse_filter(&nested(MAX_DEPTH - 1)), Ok(tag("a")));
+
+    let err = parse_error(&nested(MAX_DEPTH));
+    assert_eq!((err.message.as_str(), err.position, err.token.as_str()), ("filter is nested too deeply", MAX_DEPTH, "tag"));
+
+    let err = parse_error(&format!("{}tag:a", "NOT ".repeat(MAX_DEPTH)));
+    assert_eq!(err.message, "filter is nested too deeply");
+  }
+
+  #[test]
+  fn query_longer_than_max_length_is_rejected() {
+    // 長さはバイト数ではなく文字数で数える
+    let value = "あ".repeat(MAX_QUERY_LENGTH - "title:".len());
+    assert_eq!(parse_filter(&format!("title:{}", value)), Ok(text(Field::Title, CompareOp::Match, &value)));
+
+    let err = parse_error(&format!("title:{}あ", value));
+    assert_eq!((err.message.as_str(), err.position), ("filter must be at most 1000 characters", MAX_QUERY_LENGTH));
+  }
+}
@@ -0,0 +1,16 @@
+use crate::domain::models::api_key::ApiKey;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+
+#[async_trait]
+pub trait ApiKeyRepository {
+  async fn create(&self, api_key: ApiKey) -> Result<ApiKey, sqlx::Error>;
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, sqlx::Error>;
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiKey>, sqlx::Error>;
+  // 他のユーザーのキーは存在しないものとして RowNotFound を返す
+  async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+  // 最後に使った日時が since より前の場合だけ used_at に更新する
+  async fn touch(&self, id: Uuid, used_at: DateTime<Utc>, since: DateTime<Utc>) -> Result<(), sqlx::Error>;
+}
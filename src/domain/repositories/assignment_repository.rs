@@ -0,0 +1,16 @@
+use crate::domain::models::assignment::AssignmentEvent;
+use crate::domain::models::user::UserSummary;
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait AssignmentRepository {
+  // 担当者を user_ids に置き換え、追加・解除した分を履歴に記録する
+  async fn replace_assignees(&self, todo_id: Uuid, user_ids: &[Uuid], actor_id: Option<Uuid>) -> Result<(), sqlx::Error>;
+  async fn find_history(&self, todo_id: Uuid) -> Result<Vec<AssignmentEvent>, sqlx::Error>;
+  async fn find_watchers(&self, todo_id: Uuid) -> Result<Vec<UserSummary>, sqlx::Error>;
+  // すでにウォッチしている場合は何もしない
+  async fn add_watcher(&self, todo_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+  async fn remove_watcher(&self, todo_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+}
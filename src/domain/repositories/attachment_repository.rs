@@ -0,0 +1,12 @@
+use crate::domain::models::attachment::{Attachment, AttachmentOwner};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait AttachmentRepository {
+  async fn find_by_owner(&self, owner: AttachmentOwner) -> Result<Vec<Attachment>, sqlx::Error>;
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<Attachment>, sqlx::Error>;
+  async fn create(&self, attachment: Attachment) -> Result<Attachment, sqlx::Error>;
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+}
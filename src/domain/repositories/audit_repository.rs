@@ -0,0 +1,16 @@
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::{AuditContext, AuditEntity, AuditEvent, RevertOutcome};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+// 変更履歴そのものは DB のトリガーが書き込む
+#[async_trait]
+pub trait AuditRepository {
+  // 古い順。tenant の組織に属する（していた）Todo・請求書のイベントだけを返す
+  async fn find_by_entity(&self, tenant: Tenant, entity: AuditEntity, entity_id: Uuid) -> Result<Vec<AuditEvent>, sqlx::Error>;
+  // 古い順。tenant の組織に属する（していた）Todo・請求書のイベントだけを返す
+  async fn find_by_operation(&self, tenant: Tenant, operation_id: Uuid) -> Result<Vec<AuditEvent>, sqlx::Error>;
+  // 同じ操作のイベントを新しい順に打ち消す。対象の最新の変更がこの操作でない場合は何もしない
+  async fn revert(&self, events: &[AuditEvent], audit: &AuditContext) -> Result<RevertOutcome, sqlx::Error>;
+}
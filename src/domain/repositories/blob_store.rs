@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+use std::fmt;
+
+pub type BlobStream = BoxStream<'static, Result<Bytes, BlobError>>;
+
+// 取得するバイト範囲（end を含む）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+  pub start: u64,
+  pub end: u64,
+}
+
+impl ByteRange {
+  pub fn len(&self) -> u64 {
+    self.end - self.start + 1
+  }
+}
+
+#[derive(Debug)]
+pub enum BlobError {
+  NotFound,
+  Io(std::io::Error),
+  // S3 など外部ストレージのエラー
+  Backend(String),
+}
+
+impl fmt::Display for BlobError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      BlobError::NotFound => write!(f, "blob not found"),
+      BlobError::Io(err) => write!(f, "io error: {}", err),
+      BlobError::Backend(message) => write!(f, "storage error: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for BlobError {}
+
+impl From<std::io::Error> for BlobError {
+  fn from(err: std::io::Error) -> Self {
+    match err.kind() {
+      std::io::ErrorKind::NotFound => BlobError::NotFound,
+      _ => BlobError::Io(err),
+    }
+  }
+}
+
+// 添付ファイルの本体を保存するストレージ
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+  async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), BlobError>;
+  // range を省略した場合は全体を返す
+  async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<BlobStream, BlobError>;
+  // 存在しないキーの削除は成功として扱う
+  async fn delete(&self, key: &str) -> Result<(), BlobError>;
+}
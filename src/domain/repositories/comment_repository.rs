@@ -0,0 +1,15 @@
+use crate::domain::models::comment::{Comment, CommentRevision};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait CommentRepository {
+  async fn find_by_todo(&self, todo_id: Uuid) -> Result<Vec<Comment>, sqlx::Error>;
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, sqlx::Error>;
+  async fn find_revisions(&self, comment_id: Uuid) -> Result<Vec<CommentRevision>, sqlx::Error>;
+  async fn create(&self, comment: Comment) -> Result<Comment, sqlx::Error>;
+  // 編集前の本文を履歴に残してから更新する
+  async fn update(&self, comment: Comment) -> Result<Comment, sqlx::Error>;
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use std::fmt;
+
+#[derive(Debug)]
+pub struct EmailError(pub String);
+
+impl fmt::Display for EmailError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "email error: {}", self.0)
+  }
+}
+
+impl std::error::Error for EmailError {}
+
+// ユーザーへのメール（メールアドレスの確認など）の送信
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+  async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError>;
+}
@@ -0,0 +1,25 @@
+use crate::domain::models::idempotency::{IdempotencyRecord, StoredResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+
+#[async_trait]
+pub trait IdempotencyRepository {
+  // キーはユーザー（user_id）ごとに別々に扱う。
+  // キーを locked_until まで処理中として登録する。すでに登録されていれば既存の記録を返す。
+  // expired_before より前に登録されたものは期限切れとして、処理中のまま locked_until を過ぎた同じリクエストのものは
+  // 中断されたものとして置き換える
+  async fn claim(
+    &self,
+    user_id: Uuid,
+    key: &str,
+    request_hash: &str,
+    expired_before: DateTime<Utc>,
+    locked_until: DateTime<Utc>,
+  ) -> Result<Option<IdempotencyRecord>, sqlx::Error>;
+  async fn complete(&self, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<(), sqlx::Error>;
+  // 処理に失敗したキーを削除し、再送で処理し直せるようにする
+  async fn release(&self, user_id: Uuid, key: &str) -> Result<(), sqlx::Error>;
+  async fn delete_expired(&self, expired_before: DateTime<Utc>) -> Result<u64, sqlx::Error>;
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use std::fmt;
+
+use crate::domain::models::oidc::IdentityClaims;
+
+#[derive(Debug)]
+pub enum IdentityProviderError {
+  // 認可コードや ID トークンが無効（ログインをやり直せば解決する）
+  InvalidGrant(String),
+  // IdP との通信や応答の形式のエラー
+  Backend(String),
+}
+
+impl fmt::Display for IdentityProviderError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      IdentityProviderError::InvalidGrant(message) => write!(f, "invalid grant: {}", message),
+      IdentityProviderError::Backend(message) => write!(f, "identity provider error: {}", message),
+    }
+  }
+}
+
+impl std::error::Error for IdentityProviderError {}
+
+// 外部の OpenID Connect プロバイダ（認可コードフロー + PKCE）
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+  // ID トークンの iss。ユーザーとの対応付けにも使う
+  fn issuer(&self) -> &str;
+  // ユーザーをリダイレクトする認可エンドポイントの URL
+  fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> String;
+  // 認可コードをトークンに交換し、ID トークンの署名・iss・aud・期限・nonce を検証する
+  async fn exchange_code(&self, code: &str, code_verifier: &str, nonce: &str) -> Result<IdentityClaims, IdentityProviderError>;
+}
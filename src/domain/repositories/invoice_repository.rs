@@ -1,13 +1,28 @@
-use crate::domain::models::invoice::Invoice;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::invoice::{Invoice, InvoicePatch};
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::models::trash::Purged;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use async_trait::async_trait;
 
 
+// 請求書は組織ごとに分かれる。tenant 以外の組織の請求書は存在しないものとして扱う
 #[async_trait]
 pub trait InvoiceRepository {
-  async fn find_all(&self) -> Result<Vec<Invoice>, sqlx::Error>;
-  async fn find_by_id(&self, id: Uuid) -> Result<Option<Invoice>, sqlx::Error>;
-  async fn create(&self, invoice: Invoice) -> Result<Invoice, sqlx::Error>;
-  async fn update(&self, invoice: Invoice) -> Result<Invoice, sqlx::Error>;
-  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+  async fn find_all(&self, tenant: Tenant, page: PageRequest) -> Result<Page<Invoice>, sqlx::Error>;
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Invoice>, sqlx::Error>;
+  async fn create(&self, tenant: Tenant, invoice: Invoice, audit: &AuditContext) -> Result<Invoice, sqlx::Error>;
+  // 読み込んだ時点（invoice.version）から更新されていた場合は RowNotFound
+  async fn update(&self, tenant: Tenant, invoice: Invoice, audit: &AuditContext) -> Result<Invoice, sqlx::Error>;
+  // patch で指定された列だけを更新する。版数が version と異なる場合は RowNotFound
+  async fn patch(&self, tenant: Tenant, id: Uuid, version: i64, patch: &InvoicePatch, audit: &AuditContext) -> Result<Invoice, sqlx::Error>;
+  // ゴミ箱に移動する（以降の取得・更新の対象から外れる）
+  async fn delete(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), sqlx::Error>;
+  async fn find_trashed(&self, tenant: Tenant) -> Result<Vec<Invoice>, sqlx::Error>;
+  // ゴミ箱から戻す。ゴミ箱にない場合は RowNotFound
+  async fn restore(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Invoice, sqlx::Error>;
+  // cutoff より前にゴミ箱に移動したものを、組織によらず物理削除する（パージジョブ用）
+  async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Purged, sqlx::Error>;
 }
@@ -0,0 +1,18 @@
+use crate::domain::models::oidc::LoginState;
+use crate::domain::models::user::User;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+
+#[async_trait]
+pub trait OidcRepository {
+  async fn create_login_state(&self, state: LoginState) -> Result<(), sqlx::Error>;
+  // 一致する state を削除して返す。同じ state は 1 度しか使えない
+  async fn take_login_state(&self, state_hash: &str) -> Result<Option<LoginState>, sqlx::Error>;
+  async fn delete_expired_login_states(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error>;
+  async fn find_user_id_by_identity(&self, issuer: &str, subject: &str) -> Result<Option<Uuid>, sqlx::Error>;
+  // パスワードを持たないユーザーとして登録し、IdP のアカウントと対応付ける
+  async fn create_user_with_identity(&self, user: User, issuer: &str, subject: &str) -> Result<User, sqlx::Error>;
+  async fn link_identity(&self, user_id: Uuid, issuer: &str, subject: &str) -> Result<(), sqlx::Error>;
+}
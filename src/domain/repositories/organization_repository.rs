@@ -0,0 +1,32 @@
+use crate::domain::models::organization::{Invitation, Member, MembershipRole, Organization, UserOrganization};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait OrganizationRepository {
+  // ユーザーが所属する組織。個人用の組織が先頭
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, sqlx::Error>;
+  // ユーザーが所属していない場合は None
+  async fn find_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error>;
+  async fn find_personal(&self, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error>;
+  // 組織を作り、owner_id のユーザーを owner として所属させる
+  async fn create(&self, organization: Organization, owner_id: Uuid) -> Result<Organization, sqlx::Error>;
+  async fn find_members(&self, organization_id: Uuid) -> Result<Vec<Member>, sqlx::Error>;
+  // 最後の owner は外さずに false を返す。所属していない場合は RowNotFound
+  async fn delete_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error>;
+  // 最後の owner を owner 以外にする場合は変えずに None を返す。所属していない場合は RowNotFound
+  async fn update_member_role(&self, organization_id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<Option<Member>, sqlx::Error>;
+  async fn create_invitation(&self, invitation: Invitation) -> Result<Invitation, sqlx::Error>;
+  // 受け入れられていない招待。新しい順
+  async fn find_pending_invitations(&self, organization_id: Uuid) -> Result<Vec<Invitation>, sqlx::Error>;
+  async fn find_invitation_by_hash(&self, token_hash: &str) -> Result<Option<Invitation>, sqlx::Error>;
+  // 受け入れられていない招待を取り消す。該当しない場合は RowNotFound
+  async fn delete_invitation(&self, organization_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+  // 招待を受け入れ済みにして user_id を role で所属させる（所属済みなら役割は変えない）。
+  // すでに受け入れられていた場合は false（何もしない）
+  async fn accept_invitation(&self, id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<bool, sqlx::Error>;
+  // IdP のグループに合わせて user_id を role で所属させる（所属済みなら役割を role にする）。role が None なら外す。
+  // owner の役割は変えず、外しもしない。存在しない組織と個人用の組織には何もしない
+  async fn sync_role(&self, organization_id: Uuid, user_id: Uuid, role: Option<MembershipRole>) -> Result<(), sqlx::Error>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::project::Project;
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+// プロジェクトは組織ごとに分かれる。tenant 以外の組織のプロジェクトは存在しないものとして扱う
+#[async_trait]
+pub trait ProjectRepository {
+  async fn find_all(&self, tenant: Tenant) -> Result<Vec<Project>, sqlx::Error>;
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Project>, sqlx::Error>;
+  // プロジェクト内の Todo が現在使っているステータスのキー（プロジェクトは tenant で確認済みであること）
+  async fn find_statuses_in_use(&self, id: Uuid) -> Result<Vec<String>, sqlx::Error>;
+  async fn create(&self, tenant: Tenant, project: Project) -> Result<Project, sqlx::Error>;
+  async fn update(&self, tenant: Tenant, project: Project) -> Result<Project, sqlx::Error>;
+  async fn delete(&self, tenant: Tenant, id: Uuid) -> Result<(), sqlx::Error>;
+}
\ No newline at end of file
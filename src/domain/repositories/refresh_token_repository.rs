@@ -0,0 +1,15 @@
+use crate::domain::models::auth::RefreshToken;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+
+#[async_trait]
+pub trait RefreshTokenRepository {
+  async fn create(&self, token: RefreshToken) -> Result<(), sqlx::Error>;
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error>;
+  // 有効なトークンを無効にして next に置き換える。すでに無効だった場合は false（何もしない）
+  async fn rotate(&self, id: Uuid, next: RefreshToken) -> Result<bool, sqlx::Error>;
+  async fn revoke_family(&self, family_id: Uuid) -> Result<(), sqlx::Error>;
+  async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error>;
+}
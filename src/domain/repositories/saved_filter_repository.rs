@@ -0,0 +1,16 @@
+use crate::domain::models::saved_filter::SavedFilter;
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+// 他のユーザーのフィルタは存在しないものとして扱う
+#[async_trait]
+pub trait SavedFilterRepository {
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<SavedFilter>, sqlx::Error>;
+  async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<SavedFilter>, sqlx::Error>;
+  async fn create(&self, saved_filter: SavedFilter) -> Result<SavedFilter, sqlx::Error>;
+  // saved_filter.user_id のフィルタでなければ RowNotFound
+  async fn update(&self, saved_filter: SavedFilter) -> Result<SavedFilter, sqlx::Error>;
+  // 該当しない場合は RowNotFound
+  async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+}
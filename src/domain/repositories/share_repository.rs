@@ -0,0 +1,24 @@
+use crate::domain::models::share::{Collaborator, ProjectShare, SharedProject};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait ShareRepository {
+  async fn create(&self, share: ProjectShare) -> Result<ProjectShare, sqlx::Error>;
+  // 有効な共有（リンクと、受け入れられていない招待）。新しい順
+  async fn find_pending(&self, project_id: Uuid) -> Result<Vec<ProjectShare>, sqlx::Error>;
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<ProjectShare>, sqlx::Error>;
+  // 共有を取り消し、その共有で受け入れたユーザーも外す。該当しない場合は RowNotFound
+  async fn delete(&self, project_id: Uuid, id: Uuid) -> Result<(), sqlx::Error>;
+  // user_id を共有の権限でプロジェクトに加える（加わっている場合は view から edit への変更だけを行う）。
+  // 招待がすでに受け入れられていた場合は false（何もしない）
+  async fn accept(&self, share: &ProjectShare, user_id: Uuid) -> Result<bool, sqlx::Error>;
+  async fn find_collaborators(&self, project_id: Uuid) -> Result<Vec<Collaborator>, sqlx::Error>;
+  // 加わっていない場合は RowNotFound
+  async fn delete_collaborator(&self, project_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+  // ユーザーに共有されているプロジェクト。新しく共有された順
+  async fn find_shared_with(&self, user_id: Uuid) -> Result<Vec<SharedProject>, sqlx::Error>;
+  // 共有されていない場合は None
+  async fn find_shared_project(&self, user_id: Uuid, project_id: Uuid) -> Result<Option<SharedProject>, sqlx::Error>;
+}
@@ -1,13 +1,65 @@
-use crate::domain::models::todo::Todo;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::models::search::SearchHit;
+use crate::domain::models::todo::{Todo, TodoPatch};
+use crate::domain::models::trash::Purged;
+use crate::domain::query::TodoQuery;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use async_trait::async_trait;
 
 
+// 基準となる Todo から見た隣接位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankDirection {
+  Before,
+  After,
+}
+
+// 一括操作で書き込む 1 件分の変更
+#[derive(Debug, Clone)]
+pub enum TodoChange {
+  // follow_up（完了にした繰り返し Todo の次回分）は更新と同じセーブポイントで作成する
+  Update { todo: Box<Todo>, follow_up: Option<Box<Todo>> },
+  // ゴミ箱に移動する
+  Delete(Uuid),
+}
+
+// Todo は組織ごとに分かれる。tenant 以外の組織の Todo は存在しないものとして扱う
 #[async_trait]
 pub trait TodoRepository {
-  async fn find_all(&self) -> Result<Vec<Todo>, sqlx::Error>;
-  async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, sqlx::Error>;
-  async fn create(&self, todo: Todo) -> Result<Todo, sqlx::Error>;
-  async fn update(&self, todo: Todo) -> Result<Todo, sqlx::Error>;
-  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
-}
\ No newline at end of file
+  // 手動の並び順で、カーソルの Todo が見つからない（ゴミ箱にある場合を含む）場合は RowNotFound
+  async fn find_all(&self, tenant: Tenant, query: &TodoQuery, page: PageRequest) -> Result<Page<Todo>, sqlx::Error>;
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Todo>, sqlx::Error>;
+  async fn search(&self, tenant: Tenant, terms: &[String], limit: i64) -> Result<Vec<SearchHit<Todo>>, sqlx::Error>;
+  // ボード表示用。project_id が None の場合はプロジェクトに属さない Todo を返す
+  async fn find_by_project(&self, tenant: Tenant, project_id: Option<Uuid>) -> Result<Vec<Todo>, sqlx::Error>;
+  async fn find_by_series(&self, tenant: Tenant, series_id: Uuid) -> Result<Vec<Todo>, sqlx::Error>;
+  // 未完了で、指定したユーザーが担当している Todo
+  async fn find_open_assigned(&self, tenant: Tenant, user_id: Uuid) -> Result<Vec<Todo>, sqlx::Error>;
+  // 未完了で、指定したユーザーが担当せずウォッチしている Todo
+  async fn find_open_watched(&self, tenant: Tenant, user_id: Uuid) -> Result<Vec<Todo>, sqlx::Error>;
+  async fn find_last_rank(&self, tenant: Tenant) -> Result<Option<String>, sqlx::Error>;
+  async fn find_adjacent_rank(&self, tenant: Tenant, anchor: &Todo, direction: RankDirection, exclude_id: Uuid) -> Result<Option<String>, sqlx::Error>;
+  // 並べ替えは変更履歴に記録し、操作の取り消しで元の位置に戻せる
+  async fn update_rank(&self, tenant: Tenant, id: Uuid, rank: &str, audit: &AuditContext) -> Result<Todo, sqlx::Error>;
+  // 組織のすべての Todo の rank を振り直す（tenant.project_id には限らない）
+  async fn rebalance_ranks(&self, tenant: Tenant) -> Result<(), sqlx::Error>;
+  async fn create(&self, tenant: Tenant, todo: Todo, audit: &AuditContext) -> Result<Todo, sqlx::Error>;
+  // 読み込んだ時点（todo.version）から更新されていた場合は RowNotFound。
+  // follow_up（繰り返し Todo の次回分）は同じトランザクションで作成する
+  async fn update(&self, tenant: Tenant, todo: Todo, follow_up: Option<Todo>, audit: &AuditContext) -> Result<Todo, sqlx::Error>;
+  // patch で指定された列だけを更新する。版数が version と異なる場合は RowNotFound。follow_up は update と同じ
+  async fn patch(&self, tenant: Tenant, id: Uuid, version: i64, patch: &TodoPatch, follow_up: Option<Todo>, audit: &AuditContext) -> Result<Todo, sqlx::Error>;
+  // ゴミ箱に移動する（以降の取得・更新の対象から外れる）
+  async fn delete(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), sqlx::Error>;
+  // 1 つのトランザクションで順に書き込み、1 件ごとの結果を返す（削除は None）。
+  // 失敗した変更はその 1 件だけ取り消し、残りは書き込む
+  async fn apply_changes(&self, tenant: Tenant, changes: Vec<TodoChange>, audit: &AuditContext) -> Result<Vec<Result<Option<Todo>, sqlx::Error>>, sqlx::Error>;
+  async fn find_trashed(&self, tenant: Tenant) -> Result<Vec<Todo>, sqlx::Error>;
+  // ゴミ箱から戻す。ゴミ箱にない場合は RowNotFound
+  async fn restore(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Todo, sqlx::Error>;
+  // cutoff より前にゴミ箱に移動したものを、組織によらず物理削除する（パージジョブ用）
+  async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Purged, sqlx::Error>;
+}
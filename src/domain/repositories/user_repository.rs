@@ -0,0 +1,23 @@
+use crate::domain::models::user::{EmailVerification, User};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use async_trait::async_trait;
+
+
+#[async_trait]
+pub trait UserRepository {
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error>;
+  // 指定した id のうち、組織に所属しているもの
+  async fn find_members_by_ids(&self, organization_id: Uuid, ids: &[Uuid]) -> Result<Vec<User>, sqlx::Error>;
+  // ログインできるユーザーとして登録する
+  async fn create_with_password(&self, user: User, password_hash: &str) -> Result<User, sqlx::Error>;
+  // メールアドレス（大文字小文字を区別しない）に一致するユーザーの id とパスワードのハッシュ
+  async fn find_credentials_by_email(&self, email: &str) -> Result<Option<(Uuid, Option<String>)>, sqlx::Error>;
+  // 名前とメールアドレスを更新する。メールアドレスの変更は確認を済ませてから呼ぶ
+  async fn update(&self, user: User) -> Result<User, sqlx::Error>;
+  // ユーザーの確認待ちの変更を置き換える
+  async fn save_email_verification(&self, verification: &EmailVerification) -> Result<(), sqlx::Error>;
+  // トークンが一致して期限内なら確認待ちの変更を消してメールアドレスを変える。該当しなければ RowNotFound
+  async fn confirm_email(&self, user_id: Uuid, token_hash: &str, now: DateTime<Utc>) -> Result<User, sqlx::Error>;
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+// 1 つの項目の検証エラー
+#[derive(Debug, Clone)]
+pub struct FieldError {
+  // エラーのある項目（配列の要素は "tags[2]" のように表す）
+  pub field: String,
+  // 機械向けの種類（required / length / range / format / count）
+  pub code: &'static str,
+  pub message: String,
+}
+
+// 検証エラーを 1 件目で止めずにすべて集める
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+  errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn add(&mut self, field: impl Into<String>, code: &'static str, message: impl Into<String>) {
+    self.errors.push(FieldError {
+      field: field.into(),
+      code,
+      message: message.into(),
+    });
+  }
+
+  pub fn errors(&self) -> &[FieldError] {
+    &self.errors
+  }
+
+  pub fn into_result(self) -> Result<(), ValidationErrors> {
+    if self.errors.is_empty() { Ok(()) } else { Err(self) }
+  }
+
+  // 文字数（バイト数ではない）で長さを検証する。min が 1 以上なら空白だけの値も空とみなす
+  pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) {
+    if min > 0 && value.trim().is_empty() {
+      self.add(field, "required", format!("{} must not be empty", field));
+      return;
+    }
+    let length = value.chars().count();
+    if length > max {
+      self.add(field, "length", format!("{} must not be longer than {} characters", field, max));
+    } else if length < min {
+      self.add(field, "length", format!("{} must be at least {} characters", field, min));
+    }
+  }
+
+  pub fn max_items(&mut self, field: &str, count: usize, max: usize) {
+    if count > max {
+      self.add(field, "count", format!("{} must not have more than {} items", field, max));
+    }
+  }
+
+  // ローカル部とドメインに分かれていることだけを確認する（到達可能かは検証しない）
+  pub fn email(&mut self, field: &str, value: &str, max: usize) {
+    if value.chars().count() > max {
+      self.add(field, "length", format!("{} must not be longer than {} characters", field, max));
+      return;
+    }
+    let valid = match value.split_once('@') {
+      Some((local, domain)) => {
+        !local.is_empty()
+          && !domain.contains('@')
+          && domain.contains('.')
+          && !domain.starts_with('.')
+          && !domain.ends_with('.')
+          && !value.chars().any(char::is_whitespace)
+      }
+      None => false,
+    };
+    if !valid {
+      self.add(field, "format", format!("{} must be a valid email address", field));
+    }
+  }
+}
+
+impl fmt::Display for ValidationErrors {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let messages: Vec<&str> = self.errors.iter().map(|err| err.message.as_str()).collect();
+    f.write_str(&messages.join(", "))
+  }
+}
+
+// リクエストの内容を検証する。エラーはまとめて返す
+pub trait Validate {
+  fn validate(&self) -> Result<(), ValidationErrors>;
+}
@@ -0,0 +1,89 @@
+use crate::domain::models::api_key::ApiKey;
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ApiKeyRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl ApiKeyRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+const API_KEY_COLUMNS: &str = "id, user_id, name, prefix, token_hash, scopes, expires_at, last_used_at, created_at";
+
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {
+  async fn create(&self, api_key: ApiKey) -> Result<ApiKey, sqlx::Error> {
+    let created_api_key = sqlx::query_as::<_, ApiKey>(
+      &format!(
+        "INSERT INTO api_keys (id, user_id, name, prefix, token_hash, scopes, expires_at, created_at)
+          VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+          RETURNING {}",
+        API_KEY_COLUMNS
+      )
+    )
+    .bind(api_key.id)
+    .bind(api_key.user_id)
+    .bind(&api_key.name)
+    .bind(&api_key.prefix)
+    .bind(&api_key.token_hash)
+    .bind(api_key.scopes.as_strings())
+    .bind(api_key.expires_at)
+    .bind(api_key.created_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created_api_key)
+  }
+
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, sqlx::Error> {
+    let api_keys = sqlx::query_as::<_, ApiKey>(
+      &format!("SELECT {} FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC, id", API_KEY_COLUMNS)
+    )
+    .bind(user_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(api_keys)
+  }
+
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiKey>, sqlx::Error> {
+    let api_key = sqlx::query_as::<_, ApiKey>(
+      &format!("SELECT {} FROM api_keys WHERE token_hash = $1", API_KEY_COLUMNS)
+    )
+    .bind(token_hash)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(api_key)
+  }
+
+  async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+    let result = sqlx::query("DELETE FROM api_keys WHERE id = $1 AND user_id = $2")
+      .bind(id)
+      .bind(user_id)
+      .execute(&self.pool)
+      .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+  }
+
+  async fn touch(&self, id: Uuid, used_at: DateTime<Utc>, since: DateTime<Utc>) -> Result<(), sqlx::Error> {
+    sqlx::query(
+      "UPDATE api_keys SET last_used_at = $2 WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < $3)"
+    )
+    .bind(id)
+    .bind(used_at)
+    .bind(since)
+    .execute(&self.pool)
+    .await?;
+    Ok(())
+  }
+}
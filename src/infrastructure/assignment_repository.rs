@@ -0,0 +1,114 @@
+use crate::domain::models::assignment::{AssignmentAction, AssignmentEvent};
+use crate::domain::models::user::UserSummary;
+use crate::domain::repositories::assignment_repository::AssignmentRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct AssignmentRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl AssignmentRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl AssignmentRepository for AssignmentRepositoryImpl {
+  async fn replace_assignees(&self, todo_id: Uuid, user_ids: &[Uuid], actor_id: Option<Uuid>) -> Result<(), sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+
+    // 同じ Todo の担当者を同時に変更した場合に履歴が食い違わないよう、Todo の行をロックする
+    sqlx::query("SELECT id FROM todos WHERE id = $1 FOR UPDATE")
+      .bind(todo_id)
+      .fetch_optional(&mut *tx)
+      .await?
+      .ok_or(sqlx::Error::RowNotFound)?;
+
+    let removed = sqlx::query_scalar::<_, Uuid>(
+      "DELETE FROM todo_assignees WHERE todo_id = $1 AND NOT (user_id = ANY($2)) RETURNING user_id"
+    )
+    .bind(todo_id)
+    .bind(user_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let added = sqlx::query_scalar::<_, Uuid>(
+      "INSERT INTO todo_assignees (todo_id, user_id)
+        SELECT $1, user_id FROM unnest($2::UUID[]) AS u (user_id)
+        ON CONFLICT DO NOTHING
+        RETURNING user_id"
+    )
+    .bind(todo_id)
+    .bind(user_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (users, action) in [(&added, AssignmentAction::Assigned), (&removed, AssignmentAction::Unassigned)] {
+      if users.is_empty() {
+        continue;
+      }
+      let ids: Vec<Uuid> = users.iter().map(|_| Uuid::now_v7()).collect();
+      sqlx::query(
+        "INSERT INTO todo_assignment_history (id, todo_id, user_id, action, actor_id)
+          SELECT id, $1, user_id, $2, $3 FROM unnest($4::UUID[], $5::UUID[]) AS h (id, user_id)"
+      )
+      .bind(todo_id)
+      .bind(action.as_str())
+      .bind(actor_id)
+      .bind(&ids)
+      .bind(users)
+      .execute(&mut *tx)
+      .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+  }
+
+  async fn find_history(&self, todo_id: Uuid) -> Result<Vec<AssignmentEvent>, sqlx::Error> {
+    let events = sqlx::query_as::<_, AssignmentEvent>(
+      "SELECT id, todo_id, user_id, action, actor_id, created_at FROM todo_assignment_history
+        WHERE todo_id = $1
+        ORDER BY created_at, id"
+    )
+    .bind(todo_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(events)
+  }
+
+  async fn find_watchers(&self, todo_id: Uuid) -> Result<Vec<UserSummary>, sqlx::Error> {
+    let watchers = sqlx::query_as::<_, UserSummary>(
+      "SELECT u.id, u.name FROM todo_watchers w JOIN users u ON u.id = w.user_id
+        WHERE w.todo_id = $1
+        ORDER BY w.created_at, u.id"
+    )
+    .bind(todo_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(watchers)
+  }
+
+  async fn add_watcher(&self, todo_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO todo_watchers (todo_id, user_id) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+        .bind(todo_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+
+  async fn remove_watcher(&self, todo_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM todo_watchers WHERE todo_id = $1 AND user_id = $2")
+        .bind(todo_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,76 @@
+use crate::domain::models::attachment::{Attachment, AttachmentOwner};
+use crate::domain::repositories::attachment_repository::AttachmentRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+const ATTACHMENT_COLUMNS: &str = "id, todo_id, invoice_id, file_name, content_type, size, storage_key, created_at";
+
+#[derive(Clone)]
+pub struct AttachmentRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl AttachmentRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl AttachmentRepository for AttachmentRepositoryImpl {
+  async fn find_by_owner(&self, owner: AttachmentOwner) -> Result<Vec<Attachment>, sqlx::Error> {
+    let column = match owner {
+      AttachmentOwner::Todo(_) => "todo_id",
+      AttachmentOwner::Invoice(_) => "invoice_id",
+    };
+    let attachments = sqlx::query_as::<_, Attachment>(
+      &format!("SELECT {} FROM attachments WHERE {} = $1 ORDER BY created_at, id", ATTACHMENT_COLUMNS, column)
+    )
+    .bind(owner.id())
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(attachments)
+  }
+
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<Attachment>, sqlx::Error> {
+    let attachment = sqlx::query_as::<_, Attachment>(
+      &format!("SELECT {} FROM attachments WHERE id = $1", ATTACHMENT_COLUMNS)
+    )
+    .bind(id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(attachment)
+  }
+
+  async fn create(&self, attachment: Attachment) -> Result<Attachment, sqlx::Error> {
+    let created = sqlx::query_as::<_, Attachment>(
+        &format!(
+          "INSERT INTO attachments (id, todo_id, invoice_id, file_name, content_type, size, storage_key, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {}",
+          ATTACHMENT_COLUMNS
+        )
+    )
+    .bind(attachment.id)
+    .bind(attachment.todo_id)
+    .bind(attachment.invoice_id)
+    .bind(&attachment.file_name)
+    .bind(&attachment.content_type)
+    .bind(attachment.size)
+    .bind(&attachment.storage_key)
+    .bind(attachment.created_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created)
+  }
+
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM attachments WHERE id = $1")
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,141 @@
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::{AuditAction, AuditContext, AuditEntity, AuditEvent, RevertOutcome};
+use crate::domain::repositories::audit_repository::AuditRepository;
+use crate::infrastructure::db::{begin_audited, DbPool};
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+const AUDIT_EVENT_COLUMNS: &str = "id, entity_type, entity_id, action, actor_id, operation_id, changes, created_at";
+
+// 取り消しで元の値に戻せる列。id や作成日時などは戻さない
+fn revertible_columns(entity: AuditEntity) -> &'static [&'static str] {
+  match entity {
+    AuditEntity::Todo => &[
+      "title", "description", "completed", "project_id", "status", "priority", "tags", "due_date", "recurrence", "series_id", "rank", "deleted_at",
+    ],
+    AuditEntity::Invoice => &["amount", "paid", "deleted_at"],
+  }
+}
+
+#[derive(Clone)]
+pub struct AuditRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl AuditRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl AuditRepository for AuditRepositoryImpl {
+  async fn find_by_entity(&self, tenant: Tenant, entity: AuditEntity, entity_id: Uuid) -> Result<Vec<AuditEvent>, sqlx::Error> {
+    let events = sqlx::query_as::<_, AuditEvent>(
+      &format!(
+        "SELECT {} FROM audit_events WHERE entity_type = $1 AND entity_id = $2 AND organization_id = $3 ORDER BY created_at, id",
+        AUDIT_EVENT_COLUMNS
+      )
+    )
+    .bind(entity.as_str())
+    .bind(entity_id)
+    .bind(tenant.organization_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(events)
+  }
+
+  async fn find_by_operation(&self, tenant: Tenant, operation_id: Uuid) -> Result<Vec<AuditEvent>, sqlx::Error> {
+    let events = sqlx::query_as::<_, AuditEvent>(
+      &format!("SELECT {} FROM audit_events WHERE operation_id = $1 AND organization_id = $2 ORDER BY created_at, id", AUDIT_EVENT_COLUMNS)
+    )
+    .bind(operation_id)
+    .bind(tenant.organization_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(events)
+  }
+
+  async fn revert(&self, events: &[AuditEvent], audit: &AuditContext) -> Result<RevertOutcome, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+
+    // 対象の行をロックしてから、最新の変更がこの操作のものか確かめる
+    let mut targets: Vec<(AuditEntity, Uuid, Option<Uuid>)> = Vec::new();
+    for event in events {
+      if !targets.iter().any(|(entity, id, _)| *entity == event.entity_type && *id == event.entity_id) {
+        targets.push((event.entity_type, event.entity_id, event.operation_id));
+      }
+    }
+    for (entity, id, operation_id) in targets {
+      let locked = sqlx::query_scalar::<_, Uuid>(&format!("SELECT id FROM {} WHERE id = $1 FOR UPDATE", entity.as_str()))
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+      if locked.is_none() {
+        return Ok(RevertOutcome::Conflict);
+      }
+
+      let latest = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT operation_id FROM audit_events WHERE entity_type = $1 AND entity_id = $2 ORDER BY created_at DESC, id DESC LIMIT 1"
+      )
+      .bind(entity.as_str())
+      .bind(id)
+      .fetch_one(&mut *tx)
+      .await?;
+      if latest != operation_id {
+        return Ok(RevertOutcome::Conflict);
+      }
+    }
+
+    for event in events.iter().rev() {
+      let table = event.entity_type.as_str();
+      match event.action {
+        // 作成の取り消しはゴミ箱への移動とする
+        AuditAction::Create => {
+          sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = (NOW() AT TIME ZONE 'Asia/Tokyo') WHERE id = $1 AND deleted_at IS NULL",
+            table
+          ))
+          .bind(event.entity_id)
+          .execute(&mut *tx)
+          .await?;
+        }
+        AuditAction::Update | AuditAction::Delete | AuditAction::Restore => {
+          let columns: Vec<&str> = revertible_columns(event.entity_type)
+            .iter()
+            .copied()
+            .filter(|column| event.changes.contains_key(*column))
+            .collect();
+          if columns.is_empty() {
+            continue;
+          }
+          let before: Map<String, Value> = columns
+            .iter()
+            .map(|column| (column.to_string(), event.changes[*column].before.clone()))
+            .collect();
+          let assignments: Vec<String> = columns.iter().map(|column| format!("{0} = r.{0}", column)).collect();
+
+          // 変更前の値を JSON から各列の型へ変換して書き戻す
+          sqlx::query(&format!(
+            "UPDATE {0} t SET {1}, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+              FROM jsonb_populate_record(NULL::{0}, $1) r
+              WHERE t.id = $2",
+            table,
+            assignments.join(", ")
+          ))
+          .bind(Json(before))
+          .bind(event.entity_id)
+          .execute(&mut *tx)
+          .await?;
+        }
+        AuditAction::Purge => return Ok(RevertOutcome::Conflict),
+      }
+    }
+
+    tx.commit().await?;
+    Ok(RevertOutcome::Reverted)
+  }
+}
@@ -0,0 +1,113 @@
+use crate::domain::models::comment::{Comment, CommentRevision};
+use crate::domain::repositories::comment_repository::CommentRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+const COMMENT_COLUMNS: &str = "id, todo_id, author_id, author, body, created_at, edited_at";
+
+#[derive(Clone)]
+pub struct CommentRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl CommentRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl CommentRepository for CommentRepositoryImpl {
+  async fn find_by_todo(&self, todo_id: Uuid) -> Result<Vec<Comment>, sqlx::Error> {
+    let comments = sqlx::query_as::<_, Comment>(
+      &format!("SELECT {} FROM comments WHERE todo_id = $1 ORDER BY created_at, id", COMMENT_COLUMNS)
+    )
+    .bind(todo_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(comments)
+  }
+
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<Comment>, sqlx::Error> {
+    let comment = sqlx::query_as::<_, Comment>(
+      &format!("SELECT {} FROM comments WHERE id = $1", COMMENT_COLUMNS)
+    )
+    .bind(id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(comment)
+  }
+
+  async fn find_revisions(&self, comment_id: Uuid) -> Result<Vec<CommentRevision>, sqlx::Error> {
+    let revisions = sqlx::query_as::<_, CommentRevision>(
+      "SELECT id, comment_id, body, created_at FROM comment_revisions WHERE comment_id = $1 ORDER BY created_at, id"
+    )
+    .bind(comment_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(revisions)
+  }
+
+  async fn create(&self, comment: Comment) -> Result<Comment, sqlx::Error> {
+    let created = sqlx::query_as::<_, Comment>(
+        &format!(
+          "INSERT INTO comments (id, todo_id, author_id, author, body, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}",
+          COMMENT_COLUMNS
+        )
+    )
+    .bind(comment.id)
+    .bind(comment.todo_id)
+    .bind(comment.author_id)
+    .bind(&comment.author)
+    .bind(&comment.body)
+    .bind(comment.created_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created)
+  }
+
+  async fn update(&self, comment: Comment) -> Result<Comment, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+
+    // 直前の本文と、それが書かれた日時（作成日時または前回の編集日時）を履歴に移す
+    let archived = sqlx::query(
+        "INSERT INTO comment_revisions (id, comment_id, body, created_at)
+          SELECT $1, id, body, coalesce(edited_at, created_at) FROM comments WHERE id = $2 FOR UPDATE"
+    )
+    .bind(Uuid::now_v7())
+    .bind(comment.id)
+    .execute(&mut *tx)
+    .await?;
+    if archived.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+
+    let updated = sqlx::query_as::<_, Comment>(
+        &format!(
+          "UPDATE comments SET body = $1, edited_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+            WHERE id = $2
+            RETURNING {}",
+          COMMENT_COLUMNS
+        )
+    )
+    .bind(&comment.body)
+    .bind(comment.id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(updated)
+  }
+
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM comments WHERE id = $1")
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+}
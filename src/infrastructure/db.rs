@@ -1,7 +1,21 @@
 // データベース関連の機能を提供するモジュール
 // 必要に応じて実装を追加していく
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::domain::models::audit::AuditContext;
 
 // PostgreSQLプールの型エイリアス
-pub type DbPool = PgPool; 
\ No newline at end of file
+pub type DbPool = PgPool;
+
+// 変更履歴のトリガーが参照する実行者と操作 ID を設定したトランザクションを開始する。
+// 設定はトランザクション内でのみ有効
+pub async fn begin_audited<'a>(pool: &'a DbPool, audit: &AuditContext) -> Result<Transaction<'a, Postgres>, sqlx::Error> {
+  let mut tx = pool.begin().await?;
+  sqlx::query("SELECT set_config('app.actor_id', $1, true), set_config('app.operation_id', $2, true)")
+    .bind(audit.actor_id.map(|id| id.to_string()).unwrap_or_default())
+    .bind(audit.operation_id.to_string())
+    .execute(&mut *tx)
+    .await?;
+  Ok(tx)
+}
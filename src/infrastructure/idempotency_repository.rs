@@ -0,0 +1,102 @@
+use crate::domain::models::idempotency::{IdempotencyRecord, StoredResponse};
+use crate::domain::repositories::idempotency_repository::IdempotencyRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct IdempotencyRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl IdempotencyRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl IdempotencyRepository for IdempotencyRepositoryImpl {
+  async fn claim(
+    &self,
+    user_id: Uuid,
+    key: &str,
+    request_hash: &str,
+    expired_before: DateTime<Utc>,
+    locked_until: DateTime<Utc>,
+  ) -> Result<Option<IdempotencyRecord>, sqlx::Error> {
+    let claimed = sqlx::query_scalar::<_, String>(
+      "INSERT INTO idempotency_keys (user_id, key, request_hash, locked_until) VALUES ($1, $2, $3, $5)
+       ON CONFLICT (user_id, key) DO UPDATE SET
+         request_hash = EXCLUDED.request_hash,
+         status_code = NULL,
+         response_headers = NULL,
+         response_body = NULL,
+         created_at = now(),
+         locked_until = EXCLUDED.locked_until
+       WHERE idempotency_keys.created_at < $4
+         OR (idempotency_keys.status_code IS NULL AND idempotency_keys.locked_until < now()
+           AND idempotency_keys.request_hash = EXCLUDED.request_hash)
+       RETURNING key"
+    )
+    .bind(user_id)
+    .bind(key)
+    .bind(request_hash)
+    .bind(expired_before)
+    .bind(locked_until)
+    .fetch_optional(&self.pool)
+    .await?;
+    if claimed.is_some() {
+      return Ok(None);
+    }
+
+    // 競合した処理が失敗してキーが削除されていた場合は、処理中として扱う
+    let existing = sqlx::query_as::<_, IdempotencyRecord>(
+      "SELECT request_hash, status_code, response_headers, response_body FROM idempotency_keys WHERE user_id = $1 AND key = $2"
+    )
+    .bind(user_id)
+    .bind(key)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(Some(existing.unwrap_or_else(|| IdempotencyRecord {
+      request_hash: request_hash.to_string(),
+      status_code: None,
+      response_headers: None,
+      response_body: None,
+    })))
+  }
+
+  async fn complete(&self, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<(), sqlx::Error> {
+    sqlx::query(
+      "UPDATE idempotency_keys SET status_code = $1, response_headers = $2, response_body = $3 WHERE user_id = $4 AND key = $5"
+    )
+    .bind(response.status as i16)
+    .bind(Json(&response.headers))
+    .bind(&response.body)
+    .bind(user_id)
+    .bind(key)
+    .execute(&self.pool)
+    .await?;
+    Ok(())
+  }
+
+  async fn release(&self, user_id: Uuid, key: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE user_id = $1 AND key = $2 AND status_code IS NULL")
+      .bind(user_id)
+      .bind(key)
+      .execute(&self.pool)
+      .await?;
+    Ok(())
+  }
+
+  async fn delete_expired(&self, expired_before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM idempotency_keys WHERE created_at < $1")
+      .bind(expired_before)
+      .execute(&self.pool)
+      .await?;
+    Ok(result.rows_affected())
+  }
+}
@@ -1,9 +1,22 @@
-use crate::domain::models::invoice::Invoice;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::invoice::{Invoice, InvoicePatch};
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::models::trash::Purged;
 use crate::domain::repositories::invoice_repository::InvoiceRepository;
-use crate::infrastructure::db::DbPool;
+use crate::infrastructure::db::{begin_audited, DbPool};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder};
 use uuid::Uuid;
 
+const INVOICE_COLUMNS: &str = "id, amount, paid, created_at, updated_at, version, deleted_at";
+
+// ゴミ箱の請求書を保持期間後に物理削除し、あわせて消える添付ファイルのキーを返す
+const PURGE_SQL: &str = "WITH purged AS (DELETE FROM invoices WHERE deleted_at < $1 RETURNING id)
+  SELECT (SELECT count(*) FROM purged) AS count,
+    coalesce((SELECT array_agg(a.storage_key) FROM attachments a JOIN purged p ON a.invoice_id = p.id), '{}') AS storage_keys";
+
 #[derive(Clone)]
 pub struct InvoiceRepositoryImpl {
   pub pool: DbPool,
@@ -18,60 +31,156 @@ impl InvoiceRepositoryImpl {
 
 #[async_trait]
 impl InvoiceRepository for InvoiceRepositoryImpl {
-  async fn find_all(&self) -> Result<Vec<Invoice>, sqlx::Error> {
+  async fn find_all(&self, tenant: Tenant, page: PageRequest) -> Result<Page<Invoice>, sqlx::Error> {
     let invoices = sqlx::query_as::<_, Invoice>(
-      "SELECT id, amount, paid, created_at, updated_at FROM invoices"
+      &format!(
+        "SELECT {} FROM invoices
+          WHERE organization_id = $1 AND deleted_at IS NULL AND ($2::UUID IS NULL OR id > $2)
+          ORDER BY id
+          LIMIT $3",
+        INVOICE_COLUMNS
+      )
     )
+    .bind(tenant.organization_id)
+    .bind(page.cursor)
+    .bind(page.fetch_limit())
     .fetch_all(&self.pool)
     .await?;
-    Ok(invoices)
+    Ok(Page::from_rows(invoices, page, |invoice| invoice.id))
   }
 
-  async fn find_by_id(&self, id: Uuid) -> Result<Option<Invoice>, sqlx::Error> {
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Invoice>, sqlx::Error> {
     let invoice = sqlx::query_as::<_, Invoice>(
-      "SELECT id, amount, paid, created_at, updated_at FROM invoices WHERE id = $1"
+      &format!("SELECT {} FROM invoices WHERE id = $1 AND organization_id = $2 AND deleted_at IS NULL", INVOICE_COLUMNS)
     )
     .bind(id)
+    .bind(tenant.organization_id)
     .fetch_optional(&self.pool)
     .await?;
     Ok(invoice)
   }
 
-  async fn create(&self, invoice: Invoice) -> Result<Invoice, sqlx::Error> {
+  async fn create(&self, tenant: Tenant, invoice: Invoice, audit: &AuditContext) -> Result<Invoice, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
     let created_invoice = sqlx::query_as::<_, Invoice>(
-        "INSERT INTO invoices (id, amount, paid, created_at, updated_at)
-          VALUES ($1, $2, $3, $4, $5)
-          RETURNING id, amount, paid, created_at, updated_at"
+        &format!(
+          "INSERT INTO invoices (id, amount, paid, created_at, updated_at, organization_id, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {}",
+          INVOICE_COLUMNS
+        )
     )
     .bind(invoice.id)
-    .bind(&invoice.amount)
+    .bind(invoice.amount)
     .bind(invoice.paid)
     .bind(invoice.created_at)
     .bind(invoice.updated_at)
-    .fetch_one(&self.pool)
+    .bind(tenant.organization_id)
+    .bind(tenant.user_id)
+    .fetch_one(&mut *tx)
     .await?;
+    tx.commit().await?;
     Ok(created_invoice)
   }
 
-  async fn update(&self, invoice: Invoice) -> Result<Invoice, sqlx::Error> {
+  async fn update(&self, tenant: Tenant, invoice: Invoice, audit: &AuditContext) -> Result<Invoice, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
     let updated_invoice = sqlx::query_as::<_, Invoice>(
-        "UPDATE invoices SET amount = $1, paid = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
-          WHERE id = $3
-          RETURNING id, amount, paid, created_at, updated_at"
+        &format!(
+          "UPDATE invoices SET amount = $1, paid = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+            WHERE id = $3 AND version = $4 AND organization_id = $5 AND deleted_at IS NULL
+            RETURNING {}",
+          INVOICE_COLUMNS
+        )
     )
-    .bind(&invoice.amount)
+    .bind(invoice.amount)
     .bind(invoice.paid)
     .bind(invoice.id)
-    .fetch_one(&self.pool)
+    .bind(invoice.version)
+    .bind(tenant.organization_id)
+    .fetch_one(&mut *tx)
     .await?;
+    tx.commit().await?;
     Ok(updated_invoice)
   }
 
-  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query("DELETE FROM invoices WHERE id = $1")
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+  async fn patch(&self, tenant: Tenant, id: Uuid, version: i64, patch: &InvoicePatch, audit: &AuditContext) -> Result<Invoice, sqlx::Error> {
+    let mut builder = QueryBuilder::<Postgres>::new("UPDATE invoices SET updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')");
+    if let Some(amount) = patch.amount {
+      builder.push(", amount = ").push_bind(amount);
+    }
+    if let Some(paid) = patch.paid {
+      builder.push(", paid = ").push_bind(paid);
+    }
+    builder
+      .push(" WHERE id = ")
+      .push_bind(id)
+      .push(" AND version = ")
+      .push_bind(version)
+      .push(" AND organization_id = ")
+      .push_bind(tenant.organization_id)
+      .push(" AND deleted_at IS NULL RETURNING ")
+      .push(INVOICE_COLUMNS);
+
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let patched_invoice = builder
+      .build_query_as::<Invoice>()
+      .fetch_one(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(patched_invoice)
+  }
+
+  // ゴミ箱に移動する。添付ファイルはパージの際に ON DELETE CASCADE で消える
+  async fn delete(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let result = sqlx::query(
+      "UPDATE invoices SET deleted_at = (NOW() AT TIME ZONE 'Asia/Tokyo') WHERE id = $1 AND organization_id = $2 AND deleted_at IS NULL"
+    )
+    .bind(id)
+    .bind(tenant.organization_id)
+    .execute(&mut *tx)
+    .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    tx.commit().await?;
     Ok(())
   }
+
+  async fn find_trashed(&self, tenant: Tenant) -> Result<Vec<Invoice>, sqlx::Error> {
+    let invoices = sqlx::query_as::<_, Invoice>(
+      &format!("SELECT {} FROM invoices WHERE organization_id = $1 AND deleted_at IS NOT NULL ORDER BY deleted_at DESC, id", INVOICE_COLUMNS)
+    )
+    .bind(tenant.organization_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(invoices)
+  }
+
+  async fn restore(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Invoice, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let restored_invoice = sqlx::query_as::<_, Invoice>(
+      &format!(
+        "UPDATE invoices SET deleted_at = NULL, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $1 AND organization_id = $2 AND deleted_at IS NOT NULL
+          RETURNING {}",
+        INVOICE_COLUMNS
+      )
+    )
+    .bind(id)
+    .bind(tenant.organization_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(restored_invoice)
+  }
+
+  async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Purged, sqlx::Error> {
+    let purged = sqlx::query_as::<_, Purged>(PURGE_SQL)
+      .bind(cutoff)
+      .fetch_one(&self.pool)
+      .await?;
+    Ok(purged)
+  }
 }
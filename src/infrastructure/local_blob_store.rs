@@ -0,0 +1,79 @@
+use crate::domain::repositories::blob_store::{BlobError, BlobStore, BlobStream, ByteRange};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt};
+use std::io::SeekFrom;
+use std::path::{Component, Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+// ローカルのディレクトリにキーをそのまま相対パスとして保存する
+#[derive(Clone)]
+pub struct LocalBlobStore {
+  root: PathBuf,
+}
+
+impl LocalBlobStore {
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self { root: root.into() }
+  }
+
+  // ルートの外を指すキー（絶対パスや ..）は受け付けない
+  fn path_for(&self, key: &str) -> Result<PathBuf, BlobError> {
+    let relative = Path::new(key);
+    if key.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+      return Err(BlobError::Backend(format!("invalid blob key '{}'", key)));
+    }
+    Ok(self.root.join(relative))
+  }
+}
+
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+  async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> Result<(), BlobError> {
+    let path = self.path_for(key)?;
+    if let Some(parent) = path.parent() {
+      tokio::fs::create_dir_all(parent).await?;
+    }
+
+    // 書きかけのファイルが読まれないよう、一時ファイルに書いてから置き換える
+    let tmp_path = path.with_extension(format!("{}.tmp", Uuid::now_v7()));
+    let mut file = tokio::fs::File::create(&tmp_path).await?;
+    let written = async {
+      file.write_all(&data).await?;
+      file.sync_all().await
+    }
+    .await;
+    if let Err(err) = written {
+      let _ = tokio::fs::remove_file(&tmp_path).await;
+      return Err(err.into());
+    }
+    tokio::fs::rename(&tmp_path, &path).await?;
+    Ok(())
+  }
+
+  async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<BlobStream, BlobError> {
+    let path = self.path_for(key)?;
+    let mut file = tokio::fs::File::open(&path).await?;
+
+    let stream = match range {
+      Some(range) => {
+        file.seek(SeekFrom::Start(range.start)).await?;
+        ReaderStream::new(file.take(range.len())).map_err(BlobError::from).boxed()
+      }
+      None => ReaderStream::new(file).map_err(BlobError::from).boxed(),
+    };
+    Ok(stream)
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), BlobError> {
+    let path = self.path_for(key)?;
+    match tokio::fs::remove_file(&path).await {
+      Ok(()) => Ok(()),
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
@@ -0,0 +1,16 @@
+use crate::domain::repositories::email_sender::{EmailError, EmailSender};
+use async_trait::async_trait;
+use tracing::info;
+
+// メールを送らずにログへ出力する（送信サーバーのない開発環境向け）
+#[derive(Clone, Default)]
+pub struct LogEmailSender;
+
+
+#[async_trait]
+impl EmailSender for LogEmailSender {
+  async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), EmailError> {
+    info!("email to {}: {}\n{}", to, subject, body);
+    Ok(())
+  }
+}
@@ -1,3 +1,20 @@
 pub mod db;
 pub mod todo_repository;
-pub mod invoice_repository;
\ No newline at end of file
+pub mod invoice_repository;
+pub mod api_key_repository;
+pub mod assignment_repository;
+pub mod attachment_repository;
+pub mod audit_repository;
+pub mod comment_repository;
+pub mod idempotency_repository;
+pub mod local_blob_store;
+pub mod log_email_sender;
+pub mod oidc_provider;
+pub mod oidc_repository;
+pub mod organization_repository;
+pub mod project_repository;
+pub mod refresh_token_repository;
+pub mod s3_blob_store;
+pub mod saved_filter_repository;
+pub mod share_repository;
+pub mod user_repository;
\ No newline at end of file
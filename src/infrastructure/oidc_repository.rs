@@ -0,0 +1,99 @@
+use crate::domain::models::oidc::LoginState;
+use crate::domain::models::user::User;
+use crate::domain::repositories::oidc_repository::OidcRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct OidcRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl OidcRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+const INSERT_IDENTITY: &str = "INSERT INTO user_identities (issuer, subject, user_id) VALUES ($1, $2, $3)";
+
+
+#[async_trait]
+impl OidcRepository for OidcRepositoryImpl {
+  async fn create_login_state(&self, state: LoginState) -> Result<(), sqlx::Error> {
+    sqlx::query(
+      "INSERT INTO oidc_login_states (state_hash, code_verifier, nonce, link_user_id, expires_at) VALUES ($1, $2, $3, $4, $5)"
+    )
+      .bind(&state.state_hash)
+      .bind(&state.code_verifier)
+      .bind(&state.nonce)
+      .bind(state.link_user_id)
+      .bind(state.expires_at)
+      .execute(&self.pool)
+      .await?;
+    Ok(())
+  }
+
+  async fn take_login_state(&self, state_hash: &str) -> Result<Option<LoginState>, sqlx::Error> {
+    let state = sqlx::query_as::<_, LoginState>(
+      "DELETE FROM oidc_login_states WHERE state_hash = $1 RETURNING state_hash, code_verifier, nonce, link_user_id, expires_at"
+    )
+    .bind(state_hash)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(state)
+  }
+
+  async fn delete_expired_login_states(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM oidc_login_states WHERE expires_at <= $1")
+      .bind(now)
+      .execute(&self.pool)
+      .await?;
+    Ok(result.rows_affected())
+  }
+
+  async fn find_user_id_by_identity(&self, issuer: &str, subject: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    let user_id = sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM user_identities WHERE issuer = $1 AND subject = $2")
+      .bind(issuer)
+      .bind(subject)
+      .fetch_optional(&self.pool)
+      .await?;
+    Ok(user_id)
+  }
+
+  async fn create_user_with_identity(&self, user: User, issuer: &str, subject: &str) -> Result<User, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    let created_user = sqlx::query_as::<_, User>(
+        "INSERT INTO users (id, name, email, created_at, updated_at)
+          VALUES ($1, $2, $3, $4, $5)
+          RETURNING id, name, email, created_at, updated_at"
+    )
+    .bind(user.id)
+    .bind(&user.name)
+    .bind(&user.email)
+    .bind(user.created_at)
+    .bind(user.updated_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    sqlx::query(INSERT_IDENTITY)
+      .bind(issuer)
+      .bind(subject)
+      .bind(created_user.id)
+      .execute(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(created_user)
+  }
+
+  async fn link_identity(&self, user_id: Uuid, issuer: &str, subject: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(INSERT_IDENTITY)
+      .bind(issuer)
+      .bind(subject)
+      .bind(user_id)
+      .execute(&self.pool)
+      .await?;
+    Ok(())
+  }
+}
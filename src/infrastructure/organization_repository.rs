@@ -0,0 +1,266 @@
+use crate::domain::models::organization::{Invitation, Member, MembershipRole, Organization, UserOrganization};
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct OrganizationRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl OrganizationRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+const USER_ORGANIZATION_SELECT: &str = "SELECT o.id, o.name, o.personal_user_id, o.created_at, o.updated_at, m.role
+  FROM organizations o JOIN organization_memberships m ON m.organization_id = o.id";
+
+const INVITATION_COLUMNS: &str = "id, organization_id, email, role, token_hash, invited_by, expires_at, accepted_at, created_at";
+
+// user_id が組織の最後の owner かどうか。所属していない場合は RowNotFound。
+// 同じ組織のメンバーの変更を直列にして、owner が同時に外されて（降格されて）いなくなるのを防ぐ
+async fn is_last_owner(conn: &mut PgConnection, organization_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+  sqlx::query("SELECT id FROM organizations WHERE id = $1 FOR UPDATE")
+    .bind(organization_id)
+    .fetch_one(&mut *conn)
+    .await?;
+  let role = sqlx::query_scalar::<_, String>(
+    "SELECT role FROM organization_memberships WHERE organization_id = $1 AND user_id = $2"
+  )
+  .bind(organization_id)
+  .bind(user_id)
+  .fetch_one(&mut *conn)
+  .await?;
+  if role != MembershipRole::Owner.as_str() {
+    return Ok(false);
+  }
+  let owners = sqlx::query_scalar::<_, i64>(
+    "SELECT count(*) FROM organization_memberships WHERE organization_id = $1 AND role = $2"
+  )
+  .bind(organization_id)
+  .bind(MembershipRole::Owner.as_str())
+  .fetch_one(&mut *conn)
+  .await?;
+  Ok(owners <= 1)
+}
+
+
+#[async_trait]
+impl OrganizationRepository for OrganizationRepositoryImpl {
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, sqlx::Error> {
+    let organizations = sqlx::query_as::<_, UserOrganization>(
+      &format!("{} WHERE m.user_id = $1 ORDER BY o.personal_user_id IS NULL, o.name, o.id", USER_ORGANIZATION_SELECT)
+    )
+    .bind(user_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(organizations)
+  }
+
+  async fn find_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error> {
+    let organization = sqlx::query_as::<_, UserOrganization>(
+      &format!("{} WHERE o.id = $1 AND m.user_id = $2", USER_ORGANIZATION_SELECT)
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(organization)
+  }
+
+  async fn find_personal(&self, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error> {
+    let organization = sqlx::query_as::<_, UserOrganization>(
+      &format!("{} WHERE o.personal_user_id = $1 AND m.user_id = $1", USER_ORGANIZATION_SELECT)
+    )
+    .bind(user_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(organization)
+  }
+
+  async fn create(&self, organization: Organization, owner_id: Uuid) -> Result<Organization, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    let created_organization = sqlx::query_as::<_, Organization>(
+        "INSERT INTO organizations (id, name, created_at, updated_at)
+          VALUES ($1, $2, $3, $4)
+          RETURNING id, name, personal_user_id, created_at, updated_at"
+    )
+    .bind(organization.id)
+    .bind(&organization.name)
+    .bind(organization.created_at)
+    .bind(organization.updated_at)
+    .fetch_one(&mut *tx)
+    .await?;
+    sqlx::query("INSERT INTO organization_memberships (organization_id, user_id, role) VALUES ($1, $2, $3)")
+      .bind(created_organization.id)
+      .bind(owner_id)
+      .bind(MembershipRole::Owner.as_str())
+      .execute(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(created_organization)
+  }
+
+  async fn find_members(&self, organization_id: Uuid) -> Result<Vec<Member>, sqlx::Error> {
+    let members = sqlx::query_as::<_, Member>(
+      "SELECT u.id AS user_id, u.name, u.email, m.role, m.created_at
+        FROM organization_memberships m JOIN users u ON u.id = m.user_id
+        WHERE m.organization_id = $1 ORDER BY u.name, u.id"
+    )
+    .bind(organization_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(members)
+  }
+
+  async fn delete_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    if is_last_owner(&mut tx, organization_id, user_id).await? {
+      return Ok(false);
+    }
+    sqlx::query("DELETE FROM organization_memberships WHERE organization_id = $1 AND user_id = $2")
+      .bind(organization_id)
+      .bind(user_id)
+      .execute(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(true)
+  }
+
+  async fn update_member_role(&self, organization_id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<Option<Member>, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    if role != MembershipRole::Owner && is_last_owner(&mut tx, organization_id, user_id).await? {
+      return Ok(None);
+    }
+    sqlx::query("UPDATE organization_memberships SET role = $1 WHERE organization_id = $2 AND user_id = $3")
+      .bind(role.as_str())
+      .bind(organization_id)
+      .bind(user_id)
+      .execute(&mut *tx)
+      .await?;
+    let member = sqlx::query_as::<_, Member>(
+      "SELECT u.id AS user_id, u.name, u.email, m.role, m.created_at
+        FROM organization_memberships m JOIN users u ON u.id = m.user_id
+        WHERE m.organization_id = $1 AND m.user_id = $2"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Some(member))
+  }
+
+  async fn create_invitation(&self, invitation: Invitation) -> Result<Invitation, sqlx::Error> {
+    let created_invitation = sqlx::query_as::<_, Invitation>(
+      &format!(
+        "INSERT INTO organization_invitations (id, organization_id, email, role, token_hash, invited_by, expires_at, created_at)
+          VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+          RETURNING {}",
+        INVITATION_COLUMNS
+      )
+    )
+    .bind(invitation.id)
+    .bind(invitation.organization_id)
+    .bind(&invitation.email)
+    .bind(invitation.role.as_str())
+    .bind(&invitation.token_hash)
+    .bind(invitation.invited_by)
+    .bind(invitation.expires_at)
+    .bind(invitation.created_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created_invitation)
+  }
+
+  async fn find_pending_invitations(&self, organization_id: Uuid) -> Result<Vec<Invitation>, sqlx::Error> {
+    let invitations = sqlx::query_as::<_, Invitation>(
+      &format!(
+        "SELECT {} FROM organization_invitations WHERE organization_id = $1 AND accepted_at IS NULL ORDER BY created_at DESC, id",
+        INVITATION_COLUMNS
+      )
+    )
+    .bind(organization_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(invitations)
+  }
+
+  async fn find_invitation_by_hash(&self, token_hash: &str) -> Result<Option<Invitation>, sqlx::Error> {
+    let invitation = sqlx::query_as::<_, Invitation>(
+      &format!("SELECT {} FROM organization_invitations WHERE token_hash = $1", INVITATION_COLUMNS)
+    )
+    .bind(token_hash)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(invitation)
+  }
+
+  async fn delete_invitation(&self, organization_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+    let result = sqlx::query(
+      "DELETE FROM organization_invitations WHERE id = $1 AND organization_id = $2 AND accepted_at IS NULL"
+    )
+    .bind(id)
+    .bind(organization_id)
+    .execute(&self.pool)
+    .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+  }
+
+  async fn accept_invitation(&self, id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<bool, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    // 同時に同じ招待が使われた場合は、先に受け入れ済みにした方だけが所属する
+    let organization_id = sqlx::query_scalar::<_, Uuid>(
+      "UPDATE organization_invitations SET accepted_at = now() WHERE id = $1 AND accepted_at IS NULL RETURNING organization_id"
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(organization_id) = organization_id else {
+      return Ok(false);
+    };
+    sqlx::query(
+      "INSERT INTO organization_memberships (organization_id, user_id, role) VALUES ($1, $2, $3)
+        ON CONFLICT (organization_id, user_id) DO NOTHING"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(role.as_str())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+  }
+
+  async fn sync_role(&self, organization_id: Uuid, user_id: Uuid, role: Option<MembershipRole>) -> Result<(), sqlx::Error> {
+    let Some(role) = role else {
+      sqlx::query("DELETE FROM organization_memberships WHERE organization_id = $1 AND user_id = $2 AND role <> $3")
+        .bind(organization_id)
+        .bind(user_id)
+        .bind(MembershipRole::Owner.as_str())
+        .execute(&self.pool)
+        .await?;
+      return Ok(());
+    };
+    sqlx::query(
+      "INSERT INTO organization_memberships (organization_id, user_id, role)
+        SELECT id, $2, $3 FROM organizations WHERE id = $1 AND personal_user_id IS NULL
+        ON CONFLICT (organization_id, user_id) DO UPDATE SET role = EXCLUDED.role
+        WHERE organization_memberships.role <> $4"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(role.as_str())
+    .bind(MembershipRole::Owner.as_str())
+    .execute(&self.pool)
+    .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,114 @@
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::project::Project;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ProjectRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl ProjectRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+// 共有されたプロジェクトとしてのアクセス（tenant.project_id がある場合）では、そのプロジェクトだけを参照できる
+#[async_trait]
+impl ProjectRepository for ProjectRepositoryImpl {
+  async fn find_all(&self, tenant: Tenant) -> Result<Vec<Project>, sqlx::Error> {
+    let projects = sqlx::query_as::<_, Project>(
+      "SELECT id, name, workflow, created_at, updated_at FROM projects
+        WHERE organization_id = $1 AND ($2::uuid IS NULL OR id = $2) ORDER BY id"
+    )
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(projects)
+  }
+
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Project>, sqlx::Error> {
+    let project = sqlx::query_as::<_, Project>(
+      "SELECT id, name, workflow, created_at, updated_at FROM projects
+        WHERE id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR id = $3)"
+    )
+    .bind(id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(project)
+  }
+
+  async fn find_statuses_in_use(&self, id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    let statuses = sqlx::query_scalar::<_, String>(
+      "SELECT DISTINCT status FROM todos WHERE project_id = $1"
+    )
+    .bind(id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(statuses)
+  }
+
+  async fn create(&self, tenant: Tenant, project: Project) -> Result<Project, sqlx::Error> {
+    let created_project = sqlx::query_as::<_, Project>(
+        "INSERT INTO projects (id, name, workflow, created_at, updated_at, organization_id)
+          VALUES ($1, $2, $3, $4, $5, $6)
+          RETURNING id, name, workflow, created_at, updated_at"
+    )
+    .bind(project.id)
+    .bind(&project.name)
+    .bind(&project.workflow)
+    .bind(project.created_at)
+    .bind(project.updated_at)
+    .bind(tenant.organization_id)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created_project)
+  }
+
+  async fn update(&self, tenant: Tenant, project: Project) -> Result<Project, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+
+    let updated_project = sqlx::query_as::<_, Project>(
+        "UPDATE projects SET name = $1, workflow = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $3 AND organization_id = $4
+          RETURNING id, name, workflow, created_at, updated_at"
+    )
+    .bind(&project.name)
+    .bind(&project.workflow)
+    .bind(project.id)
+    .bind(tenant.organization_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // ステータスの完了扱いが変わった場合に備えて、導出値の completed を揃える
+    let done_statuses: Vec<String> = project.workflow.statuses
+      .iter()
+      .filter(|s| s.done)
+      .map(|s| s.key.clone())
+      .collect();
+    sqlx::query("UPDATE todos SET completed = (status = ANY($1)) WHERE project_id = $2")
+      .bind(&done_statuses)
+      .bind(project.id)
+      .execute(&mut *tx)
+      .await?;
+
+    tx.commit().await?;
+    Ok(updated_project)
+  }
+
+  async fn delete(&self, tenant: Tenant, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM projects WHERE id = $1 AND organization_id = $2")
+        .bind(id)
+        .bind(tenant.organization_id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,84 @@
+use crate::domain::models::auth::RefreshToken;
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct RefreshTokenRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl RefreshTokenRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+const INSERT_REFRESH_TOKEN: &str =
+  "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at) VALUES ($1, $2, $3, $4, $5)";
+
+
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+  async fn create(&self, token: RefreshToken) -> Result<(), sqlx::Error> {
+    sqlx::query(INSERT_REFRESH_TOKEN)
+      .bind(token.id)
+      .bind(token.user_id)
+      .bind(token.family_id)
+      .bind(&token.token_hash)
+      .bind(token.expires_at)
+      .execute(&self.pool)
+      .await?;
+    Ok(())
+  }
+
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error> {
+    let token = sqlx::query_as::<_, RefreshToken>(
+      "SELECT id, user_id, family_id, token_hash, expires_at, revoked_at FROM refresh_tokens WHERE token_hash = $1"
+    )
+    .bind(token_hash)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(token)
+  }
+
+  async fn rotate(&self, id: Uuid, next: RefreshToken) -> Result<bool, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    // 同時に同じトークンで更新された場合は、先に無効にした方だけが置き換えられる
+    let revoked = sqlx::query("UPDATE refresh_tokens SET revoked_at = now() WHERE id = $1 AND revoked_at IS NULL")
+      .bind(id)
+      .execute(&mut *tx)
+      .await?;
+    if revoked.rows_affected() == 0 {
+      return Ok(false);
+    }
+    sqlx::query(INSERT_REFRESH_TOKEN)
+      .bind(next.id)
+      .bind(next.user_id)
+      .bind(next.family_id)
+      .bind(&next.token_hash)
+      .bind(next.expires_at)
+      .execute(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(true)
+  }
+
+  async fn revoke_family(&self, family_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE refresh_tokens SET revoked_at = now() WHERE family_id = $1 AND revoked_at IS NULL")
+      .bind(family_id)
+      .execute(&self.pool)
+      .await?;
+    Ok(())
+  }
+
+  async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM refresh_tokens WHERE expires_at < $1")
+      .bind(now)
+      .execute(&self.pool)
+      .await?;
+    Ok(result.rows_affected())
+  }
+}
@@ -0,0 +1,85 @@
+use crate::domain::repositories::blob_store::{BlobError, BlobStore, BlobStream, ByteRange};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt};
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, GetOptions, GetRange, ObjectStore, PutOptions, PutPayload};
+use std::sync::Arc;
+
+// S3 互換ストレージ。endpoint を指定すると MinIO などのローカル環境にも接続できる
+#[derive(Clone)]
+pub struct S3BlobStore {
+  store: Arc<AmazonS3>,
+}
+
+pub struct S3Config {
+  pub bucket: String,
+  pub region: String,
+  // 省略時は AWS のエンドポイント
+  pub endpoint: Option<String>,
+  pub access_key_id: String,
+  pub secret_access_key: String,
+}
+
+impl S3BlobStore {
+  pub fn new(config: S3Config) -> Result<Self, BlobError> {
+    let mut builder = AmazonS3Builder::new()
+      .with_bucket_name(config.bucket)
+      .with_region(config.region)
+      .with_access_key_id(config.access_key_id)
+      .with_secret_access_key(config.secret_access_key);
+    if let Some(endpoint) = config.endpoint {
+      // MinIO などはパス形式のリクエストと http のみに対応していることが多い
+      builder = builder
+        .with_allow_http(endpoint.starts_with("http://"))
+        .with_virtual_hosted_style_request(false)
+        .with_endpoint(endpoint);
+    }
+
+    let store = builder.build().map_err(BlobError::from)?;
+    Ok(Self { store: Arc::new(store) })
+  }
+}
+
+impl From<object_store::Error> for BlobError {
+  fn from(err: object_store::Error) -> Self {
+    match err {
+      object_store::Error::NotFound { .. } => BlobError::NotFound,
+      err => BlobError::Backend(err.to_string()),
+    }
+  }
+}
+
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+  async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), BlobError> {
+    let mut attributes = Attributes::new();
+    attributes.insert(Attribute::ContentType, content_type.to_string().into());
+    let options = PutOptions {
+      attributes,
+      ..Default::default()
+    };
+
+    self.store.put_opts(&Path::from(key), PutPayload::from(data), options).await?;
+    Ok(())
+  }
+
+  async fn get(&self, key: &str, range: Option<ByteRange>) -> Result<BlobStream, BlobError> {
+    let options = GetOptions {
+      range: range.map(|range| GetRange::Bounded(range.start..range.end + 1)),
+      ..Default::default()
+    };
+
+    let result = self.store.get_opts(&Path::from(key), options).await?;
+    Ok(result.into_stream().map_err(BlobError::from).boxed())
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), BlobError> {
+    match self.store.delete(&Path::from(key)).await {
+      Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+      Err(err) => Err(err.into()),
+    }
+  }
+}
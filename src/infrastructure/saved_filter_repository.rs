@@ -0,0 +1,85 @@
+use crate::domain::models::saved_filter::SavedFilter;
+use crate::domain::repositories::saved_filter_repository::SavedFilterRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SavedFilterRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl SavedFilterRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl SavedFilterRepository for SavedFilterRepositoryImpl {
+  async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<SavedFilter>, sqlx::Error> {
+    let saved_filters = sqlx::query_as::<_, SavedFilter>(
+      "SELECT id, user_id, name, query, created_at, updated_at FROM saved_filters WHERE user_id = $1 ORDER BY name, id"
+    )
+    .bind(user_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(saved_filters)
+  }
+
+  async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<SavedFilter>, sqlx::Error> {
+    let saved_filter = sqlx::query_as::<_, SavedFilter>(
+      "SELECT id, user_id, name, query, created_at, updated_at FROM saved_filters WHERE id = $1 AND user_id = $2"
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(saved_filter)
+  }
+
+  async fn create(&self, saved_filter: SavedFilter) -> Result<SavedFilter, sqlx::Error> {
+    let created = sqlx::query_as::<_, SavedFilter>(
+        "INSERT INTO saved_filters (id, user_id, name, query, created_at, updated_at)
+          VALUES ($1, $2, $3, $4, $5, $6)
+          RETURNING id, user_id, name, query, created_at, updated_at"
+    )
+    .bind(saved_filter.id)
+    .bind(saved_filter.user_id)
+    .bind(&saved_filter.name)
+    .bind(&saved_filter.query)
+    .bind(saved_filter.created_at)
+    .bind(saved_filter.updated_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created)
+  }
+
+  async fn update(&self, saved_filter: SavedFilter) -> Result<SavedFilter, sqlx::Error> {
+    let updated = sqlx::query_as::<_, SavedFilter>(
+        "UPDATE saved_filters SET name = $1, query = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $3 AND user_id = $4
+          RETURNING id, user_id, name, query, created_at, updated_at"
+    )
+    .bind(&saved_filter.name)
+    .bind(&saved_filter.query)
+    .bind(saved_filter.id)
+    .bind(saved_filter.user_id)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(updated)
+  }
+
+  async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+    let result = sqlx::query("DELETE FROM saved_filters WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+  }
+}
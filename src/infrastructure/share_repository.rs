@@ -0,0 +1,157 @@
+use crate::domain::models::share::{Collaborator, ProjectShare, SharedProject};
+use crate::domain::repositories::share_repository::ShareRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct ShareRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl ShareRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+const SHARE_COLUMNS: &str = "id, project_id, permission, email, token_hash, created_by, expires_at, accepted_at, created_at";
+
+const SHARED_PROJECT_SELECT: &str = "SELECT p.id AS project_id, p.name, o.id AS organization_id, o.name AS organization_name,
+    c.permission, c.created_at AS shared_at
+  FROM project_collaborators c
+  JOIN projects p ON p.id = c.project_id
+  JOIN organizations o ON o.id = p.organization_id";
+
+
+#[async_trait]
+impl ShareRepository for ShareRepositoryImpl {
+  async fn create(&self, share: ProjectShare) -> Result<ProjectShare, sqlx::Error> {
+    let created_share = sqlx::query_as::<_, ProjectShare>(
+      &format!(
+        "INSERT INTO project_shares (id, project_id, permission, email, token_hash, created_by, expires_at, created_at)
+          VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+          RETURNING {}",
+        SHARE_COLUMNS
+      )
+    )
+    .bind(share.id)
+    .bind(share.project_id)
+    .bind(share.permission.as_str())
+    .bind(&share.email)
+    .bind(&share.token_hash)
+    .bind(share.created_by)
+    .bind(share.expires_at)
+    .bind(share.created_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created_share)
+  }
+
+  async fn find_pending(&self, project_id: Uuid) -> Result<Vec<ProjectShare>, sqlx::Error> {
+    let shares = sqlx::query_as::<_, ProjectShare>(
+      &format!(
+        "SELECT {} FROM project_shares WHERE project_id = $1 AND accepted_at IS NULL ORDER BY created_at DESC, id",
+        SHARE_COLUMNS
+      )
+    )
+    .bind(project_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(shares)
+  }
+
+  async fn find_by_hash(&self, token_hash: &str) -> Result<Option<ProjectShare>, sqlx::Error> {
+    let share = sqlx::query_as::<_, ProjectShare>(
+      &format!("SELECT {} FROM project_shares WHERE token_hash = $1", SHARE_COLUMNS)
+    )
+    .bind(token_hash)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(share)
+  }
+
+  async fn delete(&self, project_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+    let result = sqlx::query("DELETE FROM project_shares WHERE id = $1 AND project_id = $2")
+      .bind(id)
+      .bind(project_id)
+      .execute(&self.pool)
+      .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+  }
+
+  async fn accept(&self, share: &ProjectShare, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    // 同時に同じ招待が使われた場合は、先に受け入れ済みにした方だけが加わる
+    if share.email.is_some() {
+      let accepted = sqlx::query("UPDATE project_shares SET accepted_at = now() WHERE id = $1 AND accepted_at IS NULL")
+        .bind(share.id)
+        .execute(&mut *tx)
+        .await?;
+      if accepted.rows_affected() == 0 {
+        return Ok(false);
+      }
+    }
+    sqlx::query(
+      "INSERT INTO project_collaborators (project_id, user_id, share_id, permission) VALUES ($1, $2, $3, $4)
+        ON CONFLICT (project_id, user_id) DO UPDATE SET share_id = EXCLUDED.share_id, permission = EXCLUDED.permission
+        WHERE project_collaborators.permission = 'view' AND EXCLUDED.permission = 'edit'"
+    )
+    .bind(share.project_id)
+    .bind(user_id)
+    .bind(share.id)
+    .bind(share.permission.as_str())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+  }
+
+  async fn find_collaborators(&self, project_id: Uuid) -> Result<Vec<Collaborator>, sqlx::Error> {
+    let collaborators = sqlx::query_as::<_, Collaborator>(
+      "SELECT u.id AS user_id, u.name, u.email, c.permission, c.created_at
+        FROM project_collaborators c JOIN users u ON u.id = c.user_id
+        WHERE c.project_id = $1 ORDER BY c.created_at, u.id"
+    )
+    .bind(project_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(collaborators)
+  }
+
+  async fn delete_collaborator(&self, project_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+    let result = sqlx::query("DELETE FROM project_collaborators WHERE project_id = $1 AND user_id = $2")
+      .bind(project_id)
+      .bind(user_id)
+      .execute(&self.pool)
+      .await?;
+    if result.rows_affected() == 0 {
+      return Err(sqlx::Error::RowNotFound);
+    }
+    Ok(())
+  }
+
+  async fn find_shared_with(&self, user_id: Uuid) -> Result<Vec<SharedProject>, sqlx::Error> {
+    let projects = sqlx::query_as::<_, SharedProject>(
+      &format!("{} WHERE c.user_id = $1 ORDER BY c.created_at DESC, p.id", SHARED_PROJECT_SELECT)
+    )
+    .bind(user_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(projects)
+  }
+
+  async fn find_shared_project(&self, user_id: Uuid, project_id: Uuid) -> Result<Option<SharedProject>, sqlx::Error> {
+    let project = sqlx::query_as::<_, SharedProject>(
+      &format!("{} WHERE c.user_id = $1 AND c.project_id = $2", SHARED_PROJECT_SELECT)
+    )
+    .bind(user_id)
+    .bind(project_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(project)
+  }
+}
@@ -1,9 +1,242 @@
-use crate::domain::models::todo::Todo;
-use crate::domain::repositories::todo_repository::TodoRepository;
-use crate::infrastructure::db::DbPool;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::models::search::SearchHit;
+use crate::domain::models::todo::{Todo, TodoPatch};
+use crate::domain::models::trash::Purged;
+use crate::domain::query::ast::{CompareOp, Condition, Field, Filter, Value};
+use crate::domain::query::{TodoOrder, TodoQuery};
+use crate::domain::repositories::todo_repository::{RankDirection, TodoChange, TodoRepository};
+use crate::infrastructure::db::{begin_audited, DbPool};
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use sqlx::{Acquire, FromRow, PgConnection, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+const TODO_COLUMNS: &str = "id, title, description, completed, project_id, status, priority, tags, due_date, recurrence, series_id, rank, created_at, updated_at, version, deleted_at,
+  (SELECT count(*) FROM comments WHERE comments.todo_id = todos.id) AS comment_count,
+  (SELECT coalesce(json_agg(json_build_object('id', u.id, 'name', u.name) ORDER BY a.assigned_at, u.id), '[]')
+    FROM todo_assignees a JOIN users u ON u.id = a.user_id WHERE a.todo_id = todos.id) AS assignees";
+
+// 自分の作業一覧の並び順。期日が近いもの、優先度が高いものから
+const WORK_ORDER: &str = "ORDER BY due_date NULLS LAST, priority DESC, rank, id";
+
+// ゴミ箱の Todo を保持期間後に物理削除し、あわせて消える添付ファイルのキーを返す。
+// 同じスナップショットで読むため、カスケード削除される前の attachments を参照できる
+const PURGE_SQL: &str = "WITH purged AS (DELETE FROM todos WHERE deleted_at < $1 RETURNING id)
+  SELECT (SELECT count(*) FROM purged) AS count,
+    coalesce((SELECT array_agg(a.storage_key) FROM attachments a JOIN purged p ON a.todo_id = p.id), '{}') AS storage_keys";
+
+// 組織内の Todo の rank を (rank, id) 順のまま等間隔のキーに振り直す。マイグレーションの初期値と同じ形式
+const REBALANCE_RANKS_SQL: &str = "UPDATE todos t SET rank = lpad(to_hex(o.n), 8, '0') || '8'
+  FROM (SELECT id, row_number() OVER (ORDER BY rank, id) AS n FROM todos WHERE organization_id = $1) o
+  WHERE t.id = o.id";
+
+#[derive(FromRow)]
+struct TodoSearchRow {
+  #[sqlx(flatten)]
+  todo: Todo,
+  // todos.rank（並び順）と名前が重ならないように別名で受け取る
+  #[sqlx(rename = "search_rank")]
+  rank: f32,
+}
+
+// LIKE のワイルドカードをエスケープして部分一致パターンにする
+fn like_pattern(term: &str) -> String {
+  let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+  format!("%{}%", escaped)
+}
+
+// 日付のみの指定は日本時間のその日の 0 時から翌日 0 時までとして扱う
+fn day_range(date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+  let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+  let start = jst
+    .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+    .unwrap()
+    .with_timezone(&Utc);
+  (start, start + Duration::days(1))
+}
+
+fn column(field: Field) -> &'static str {
+  match field {
+    Field::Completed => "completed",
+    Field::Tag => "tags",
+    Field::Priority => "priority",
+    Field::Due => "due_date",
+    Field::Created => "created_at",
+    Field::Updated => "updated_at",
+    Field::Title => "title",
+    Field::Description => "description",
+  }
+}
+
+fn comparison(op: CompareOp) -> &'static str {
+  match op {
+    CompareOp::Match | CompareOp::Eq => " = ",
+    CompareOp::NotEq => " <> ",
+    CompareOp::Lt => " < ",
+    CompareOp::Le => " <= ",
+    CompareOp::Gt => " > ",
+    CompareOp::Ge => " >= ",
+  }
+}
+
+// フィルタの構文木を、値をすべてバインドパラメータにした WHERE 句へ変換する
+fn push_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &Filter) {
+  match filter {
+    Filter::And(left, right) | Filter::Or(left, right) => {
+      let joiner = if matches!(filter, Filter::And(..)) { " AND " } else { " OR " };
+      builder.push("(");
+      push_filter(builder, left);
+      builder.push(joiner);
+      push_filter(builder, right);
+      builder.push(")");
+    }
+    Filter::Not(inner) => {
+      builder.push("NOT (");
+      push_filter(builder, inner);
+      builder.push(")");
+    }
+    Filter::Condition(condition) => push_condition(builder, condition),
+  }
+}
+
+fn push_condition(builder: &mut QueryBuilder<'_, Postgres>, condition: &Condition) {
+  let column = column(condition.field);
+  let op = condition.op;
+
+  match &condition.value {
+    Value::Bool(value) => {
+      builder.push(column).push(comparison(op)).push_bind(*value);
+    }
+    Value::Priority(priority) => {
+      builder.push(column).push(comparison(op)).push_bind(*priority);
+    }
+    Value::Text(text) if condition.field == Field::Tag => {
+      if op == CompareOp::NotEq {
+        builder.push("NOT ");
+      }
+      builder.push_bind(text.clone()).push(" = ANY(tags)");
+    }
+    Value::Text(text) if op == CompareOp::Match => {
+      builder.push(column).push(" ILIKE ").push_bind(like_pattern(text));
+    }
+    Value::Text(text) if op == CompareOp::NotEq => {
+      builder.push(column).push(" IS DISTINCT FROM ").push_bind(text.clone());
+    }
+    Value::Text(text) => {
+      builder.push(column).push(" = ").push_bind(text.clone());
+    }
+    Value::Timestamp(timestamp) => {
+      builder.push(column).push(comparison(op)).push_bind(*timestamp);
+    }
+    Value::Date(date) => {
+      let (start, end) = day_range(*date);
+      match op {
+        CompareOp::Match | CompareOp::Eq => {
+          builder.push("(").push(column).push(" >= ").push_bind(start);
+          builder.push(" AND ").push(column).push(" < ").push_bind(end).push(")");
+        }
+        CompareOp::NotEq => {
+          builder.push("NOT (").push(column).push(" >= ").push_bind(start);
+          builder.push(" AND ").push(column).push(" < ").push_bind(end).push(")");
+        }
+        CompareOp::Lt => {
+          builder.push(column).push(" < ").push_bind(start);
+        }
+        CompareOp::Le => {
+          builder.push(column).push(" < ").push_bind(end);
+        }
+        CompareOp::Gt => {
+          builder.push(column).push(" >= ").push_bind(end);
+        }
+        CompareOp::Ge => {
+          builder.push(column).push(" >= ").push_bind(start);
+        }
+      }
+    }
+    Value::Null => {
+      let check = if op == CompareOp::NotEq { " IS NOT NULL" } else { " IS NULL" };
+      builder.push(column).push(check);
+    }
+  }
+}
+
+async fn update_todo(conn: &mut PgConnection, tenant: Tenant, todo: &Todo) -> Result<Todo, sqlx::Error> {
+  sqlx::query_as::<_, Todo>(
+    &format!(
+      "UPDATE todos SET title = $1, description = $2, completed = $3, project_id = $4, status = $5,
+        priority = $6, tags = $7, due_date = $8, recurrence = $9, series_id = $10,
+        updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+        WHERE id = $11 AND version = $12 AND organization_id = $13 AND ($14::uuid IS NULL OR project_id = $14) AND deleted_at IS NULL
+        RETURNING {}",
+      TODO_COLUMNS
+    )
+  )
+  .bind(&todo.title)
+  .bind(&todo.description)
+  .bind(todo.completed)
+  .bind(todo.project_id)
+  .bind(&todo.status)
+  .bind(todo.priority)
+  .bind(&todo.tags)
+  .bind(todo.due_date)
+  .bind(&todo.recurrence)
+  .bind(todo.series_id)
+  .bind(todo.id)
+  .bind(todo.version)
+  .bind(tenant.organization_id)
+  .bind(tenant.project_id)
+  .fetch_one(conn)
+  .await
+}
+
+async fn insert_todo(conn: &mut PgConnection, tenant: Tenant, todo: &Todo) -> Result<Todo, sqlx::Error> {
+  sqlx::query_as::<_, Todo>(
+    &format!(
+      "INSERT INTO todos (id, title, description, completed, project_id, status, priority, tags, due_date, recurrence, series_id, rank, created_at, updated_at, organization_id, owner_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        RETURNING {}",
+      TODO_COLUMNS
+    )
+  )
+  .bind(todo.id)
+  .bind(&todo.title)
+  .bind(&todo.description)
+  .bind(todo.completed)
+  .bind(todo.project_id)
+  .bind(&todo.status)
+  .bind(todo.priority)
+  .bind(&todo.tags)
+  .bind(todo.due_date)
+  .bind(&todo.recurrence)
+  .bind(todo.series_id)
+  .bind(&todo.rank)
+  .bind(todo.created_at)
+  .bind(todo.updated_at)
+  .bind(tenant.organization_id)
+  .bind(tenant.user_id)
+  .fetch_one(conn)
+  .await
+}
+
+// ゴミ箱に移動する。対象がない（ゴミ箱にある）場合は RowNotFound
+async fn trash_todo(conn: &mut PgConnection, tenant: Tenant, id: Uuid) -> Result<(), sqlx::Error> {
+  let result = sqlx::query(
+    "UPDATE todos SET deleted_at = (NOW() AT TIME ZONE 'Asia/Tokyo') WHERE id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3)
+      AND deleted_at IS NULL"
+  )
+  .bind(id)
+  .bind(tenant.organization_id)
+  .bind(tenant.project_id)
+  .execute(conn)
+  .await?;
+  if result.rows_affected() == 0 {
+    return Err(sqlx::Error::RowNotFound);
+  }
+  Ok(())
+}
+
 #[derive(Clone)]
 pub struct TodoRepositoryImpl {
   pub pool: DbPool,
@@ -16,64 +249,393 @@ impl TodoRepositoryImpl {
 }
 
 
+// 各クエリは組織（tenant.organization_id）の範囲に加えて、共有されたプロジェクトとしてのアクセス
+// （tenant.project_id がある場合）ではそのプロジェクトの Todo だけを対象にする。
+// rank は組織全体で 1 つの並び順だが、隣や末尾の rank もプロジェクトの Todo の中から探し、
+// 他の Todo の並びを見せない。振り直しだけは組織全体を対象にする（ユースケースが組織のメンバーに限る）
 #[async_trait]
 impl TodoRepository for TodoRepositoryImpl {
-  async fn find_all(&self) -> Result<Vec<Todo>, sqlx::Error> {
+  async fn find_all(&self, tenant: Tenant, query: &TodoQuery, page: PageRequest) -> Result<Page<Todo>, sqlx::Error> {
+    // 手動の並び順では、カーソルの Todo の rank から続きを探す。見えない Todo の rank は使わない
+    let cursor_rank = match (page.cursor, query.order) {
+      (Some(cursor), TodoOrder::Manual) => {
+        let rank = sqlx::query_scalar::<_, String>(
+          "SELECT rank FROM todos WHERE id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NULL"
+        )
+        .bind(cursor)
+        .bind(tenant.organization_id)
+        .bind(tenant.project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Some(rank.ok_or(sqlx::Error::RowNotFound)?)
+      }
+      _ => None,
+    };
+
+    let mut builder = QueryBuilder::<Postgres>::new(format!("SELECT {} FROM todos WHERE deleted_at IS NULL AND organization_id = ", TODO_COLUMNS));
+    builder.push_bind(tenant.organization_id);
+    if let Some(project_id) = tenant.project_id {
+      builder.push(" AND project_id = ").push_bind(project_id);
+    }
+    if let Some(cursor) = page.cursor {
+      match query.order {
+        TodoOrder::Created => {
+          builder.push(" AND id > ").push_bind(cursor);
+        }
+        TodoOrder::Manual => {
+          builder.push(" AND (rank, id) > (").push_bind(cursor_rank.clone()).push(", ").push_bind(cursor).push(")");
+        }
+      }
+    }
+    if let Some(filter) = &query.filter {
+      builder.push(" AND ");
+      push_filter(&mut builder, filter);
+    }
+    if let Some(assignee) = query.assignee {
+      builder
+        .push(" AND EXISTS (SELECT 1 FROM todo_assignees WHERE todo_assignees.todo_id = todos.id AND todo_assignees.user_id = ")
+        .push_bind(assignee)
+        .push(")");
+    }
+    match query.order {
+      TodoOrder::Created => builder.push(" ORDER BY id"),
+      TodoOrder::Manual => builder.push(" ORDER BY rank, id"),
+    };
+    builder.push(" LIMIT ").push_bind(page.fetch_limit());
+
+    let todos = builder
+      .build_query_as::<Todo>()
+      .fetch_all(&self.pool)
+      .await?;
+    Ok(Page::from_rows(todos, page, |todo| todo.id))
+  }
+
+  async fn find_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Todo>, sqlx::Error> {
+    let todo = sqlx::query_as::<_, Todo>(
+      &format!("SELECT {} FROM todos WHERE id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NULL", TODO_COLUMNS)
+    )
+    .bind(id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(todo)
+  }
+
+  async fn search(&self, tenant: Tenant, terms: &[String], limit: i64) -> Result<Vec<SearchHit<Todo>>, sqlx::Error> {
+    let query = terms.join(" ");
+
+    // tsvector の一致に加えて、CJK テキスト向けにトライグラム索引の部分一致でも拾う。
+    // ILIKE ALL (配列) では索引が使われないため、語ごとに ILIKE を並べる
+    let mut builder = QueryBuilder::<Postgres>::new(format!("SELECT {}, (ts_rank(search_vector, websearch_to_tsquery('simple', ", TODO_COLUMNS));
+    builder
+      .push_bind(query.clone())
+      .push(")) + word_similarity(")
+      .push_bind(query.clone())
+      .push(", title || ' ' || coalesce(description, '')))::REAL AS search_rank FROM todos WHERE deleted_at IS NULL AND organization_id = ")
+      .push_bind(tenant.organization_id);
+    if let Some(project_id) = tenant.project_id {
+      builder.push(" AND project_id = ").push_bind(project_id);
+    }
+    builder.push(" AND (search_vector @@ websearch_to_tsquery('simple', ").push_bind(query).push(") OR (");
+    for (i, term) in terms.iter().enumerate() {
+      if i > 0 {
+        builder.push(" AND ");
+      }
+      builder.push("(title || ' ' || coalesce(description, '')) ILIKE ").push_bind(like_pattern(term));
+    }
+    builder.push("))");
+    builder.push(" ORDER BY search_rank DESC, id DESC LIMIT ").push_bind(limit);
+
+    let rows = builder.build_query_as::<TodoSearchRow>().fetch_all(&self.pool).await?;
+
+    Ok(rows
+      .into_iter()
+      .map(|row| SearchHit { item: row.todo, rank: row.rank, snippet: None })
+      .collect())
+  }
+
+  async fn find_by_project(&self, tenant: Tenant, project_id: Option<Uuid>) -> Result<Vec<Todo>, sqlx::Error> {
     let todos = sqlx::query_as::<_, Todo>(
-      "SELECT id, title, description, completed, created_at, updated_at FROM todos"
+      &format!(
+        "SELECT {} FROM todos WHERE project_id IS NOT DISTINCT FROM $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3)
+          AND deleted_at IS NULL ORDER BY rank, id",
+        TODO_COLUMNS
+      )
     )
+    .bind(project_id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
     .fetch_all(&self.pool)
     .await?;
     Ok(todos)
   }
 
-  async fn find_by_id(&self, id: Uuid) -> Result<Option<Todo>, sqlx::Error> {
-    let todo = sqlx::query_as::<_, Todo>(
-      "SELECT id, title, description, completed, created_at, updated_at FROM todos WHERE id = $1"
+  async fn find_by_series(&self, tenant: Tenant, series_id: Uuid) -> Result<Vec<Todo>, sqlx::Error> {
+    let todos = sqlx::query_as::<_, Todo>(
+      &format!(
+        "SELECT {} FROM todos WHERE series_id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NULL ORDER BY due_date, id",
+        TODO_COLUMNS
+      )
     )
-    .bind(id)
-    .fetch_optional(&self.pool)
+    .bind(series_id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_all(&self.pool)
     .await?;
-    Ok(todo)
+    Ok(todos)
   }
 
-  async fn create(&self, todo: Todo) -> Result<Todo, sqlx::Error> {
-    let created_todo = sqlx::query_as::<_, Todo>(
-        "INSERT INTO todos (id, title, description, completed, created_at, updated_at)
-          VALUES ($1, $2, $3, $4, $5, $6)
-          RETURNING id, title, description, completed, created_at, updated_at"
+  async fn find_open_assigned(&self, tenant: Tenant, user_id: Uuid) -> Result<Vec<Todo>, sqlx::Error> {
+    let todos = sqlx::query_as::<_, Todo>(
+      &format!(
+        "SELECT {} FROM todos
+          WHERE NOT completed AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NULL
+            AND EXISTS (SELECT 1 FROM todo_assignees a WHERE a.todo_id = todos.id AND a.user_id = $1)
+          {}",
+        TODO_COLUMNS, WORK_ORDER
+      )
     )
-    .bind(todo.id)
-    .bind(&todo.title)
-    .bind(&todo.description)
-    .bind(todo.completed)
-    .bind(todo.created_at)
-    .bind(todo.updated_at)
-    .fetch_one(&self.pool)
+    .bind(user_id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_all(&self.pool)
     .await?;
-    Ok(created_todo)
+    Ok(todos)
+  }
+
+  async fn find_open_watched(&self, tenant: Tenant, user_id: Uuid) -> Result<Vec<Todo>, sqlx::Error> {
+    let todos = sqlx::query_as::<_, Todo>(
+      &format!(
+        "SELECT {} FROM todos
+          WHERE NOT completed AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NULL
+            AND EXISTS (SELECT 1 FROM todo_watchers w WHERE w.todo_id = todos.id AND w.user_id = $1)
+            AND NOT EXISTS (SELECT 1 FROM todo_assignees a WHERE a.todo_id = todos.id AND a.user_id = $1)
+          {}",
+        TODO_COLUMNS, WORK_ORDER
+      )
+    )
+    .bind(user_id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(todos)
+  }
+
+  async fn find_last_rank(&self, tenant: Tenant) -> Result<Option<String>, sqlx::Error> {
+    let rank = sqlx::query_scalar::<_, String>(
+      "SELECT rank FROM todos WHERE organization_id = $1 AND ($2::uuid IS NULL OR project_id = $2) ORDER BY rank DESC, id DESC LIMIT 1"
+    )
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_optional(&self.pool)
+      .await?;
+    Ok(rank)
   }
 
-  async fn update(&self, todo: Todo) -> Result<Todo, sqlx::Error> {
+  async fn find_adjacent_rank(&self, tenant: Tenant, anchor: &Todo, direction: RankDirection, exclude_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+    let sql = match direction {
+      RankDirection::Before => {
+        "SELECT rank FROM todos WHERE (rank, id) < ($1, $2) AND id <> $3 AND organization_id = $4 AND ($5::uuid IS NULL OR project_id = $5)
+          ORDER BY rank DESC, id DESC LIMIT 1"
+      }
+      RankDirection::After => {
+        "SELECT rank FROM todos WHERE (rank, id) > ($1, $2) AND id <> $3 AND organization_id = $4 AND ($5::uuid IS NULL OR project_id = $5)
+          ORDER BY rank, id LIMIT 1"
+      }
+    };
+    let rank = sqlx::query_scalar::<_, String>(sql)
+      .bind(&anchor.rank)
+      .bind(anchor.id)
+      .bind(exclude_id)
+      .bind(tenant.organization_id)
+      .bind(tenant.project_id)
+      .fetch_optional(&self.pool)
+      .await?;
+    Ok(rank)
+  }
+
+  async fn update_rank(&self, tenant: Tenant, id: Uuid, rank: &str, audit: &AuditContext) -> Result<Todo, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
     let updated_todo = sqlx::query_as::<_, Todo>(
-        "UPDATE todos SET title = $1, description = $2, completed = $3, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
-          WHERE id = $4
-          RETURNING id, title, description, completed, created_at, updated_at"
+      &format!(
+        "UPDATE todos SET rank = $1, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $2 AND organization_id = $3 AND ($4::uuid IS NULL OR project_id = $4) AND deleted_at IS NULL
+          RETURNING {}",
+        TODO_COLUMNS
+      )
     )
-    .bind(&todo.title)
-    .bind(&todo.description)
-    .bind(todo.completed)
-    .bind(todo.id)
-    .fetch_one(&self.pool)
+    .bind(rank)
+    .bind(id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_one(&mut *tx)
     .await?;
+    tx.commit().await?;
     Ok(updated_todo)
   }
 
-  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
-    sqlx::query("DELETE FROM todos WHERE id = $1")
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+  async fn rebalance_ranks(&self, tenant: Tenant) -> Result<(), sqlx::Error> {
+    // 振り直しは並び順を変えないため、変更履歴に記録しない
+    let mut tx = self.pool.begin().await?;
+    sqlx::query("SELECT set_config('app.audit_ignored_columns', 'rank', true)")
+      .execute(&mut *tx)
+      .await?;
+    sqlx::query(REBALANCE_RANKS_SQL)
+      .bind(tenant.organization_id)
+      .execute(&mut *tx)
+      .await?;
+    tx.commit().await?;
+    Ok(())
+  }
+
+  async fn create(&self, tenant: Tenant, todo: Todo, audit: &AuditContext) -> Result<Todo, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let created_todo = insert_todo(&mut tx, tenant, &todo).await?;
+    tx.commit().await?;
+    Ok(created_todo)
+  }
+
+  async fn update(&self, tenant: Tenant, todo: Todo, follow_up: Option<Todo>, audit: &AuditContext) -> Result<Todo, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let updated_todo = update_todo(&mut tx, tenant, &todo).await?;
+    if let Some(follow_up) = &follow_up {
+      insert_todo(&mut tx, tenant, follow_up).await?;
+    }
+    tx.commit().await?;
+    Ok(updated_todo)
+  }
+
+  async fn patch(&self, tenant: Tenant, id: Uuid, version: i64, patch: &TodoPatch, follow_up: Option<Todo>, audit: &AuditContext) -> Result<Todo, sqlx::Error> {
+    let mut builder = QueryBuilder::<Postgres>::new("UPDATE todos SET updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')");
+    if let Some(title) = &patch.title {
+      builder.push(", title = ").push_bind(title.clone());
+    }
+    if let Some(description) = &patch.description {
+      builder.push(", description = ").push_bind(description.clone());
+    }
+    if let Some(project_id) = patch.project_id {
+      builder.push(", project_id = ").push_bind(project_id);
+    }
+    if let Some(status) = &patch.status {
+      builder.push(", status = ").push_bind(status.clone());
+    }
+    if let Some(completed) = patch.completed {
+      builder.push(", completed = ").push_bind(completed);
+    }
+    if let Some(priority) = patch.priority {
+      builder.push(", priority = ").push_bind(priority);
+    }
+    if let Some(tags) = &patch.tags {
+      builder.push(", tags = ").push_bind(tags.clone());
+    }
+    if let Some(due_date) = patch.due_date {
+      builder.push(", due_date = ").push_bind(due_date);
+    }
+    if let Some(recurrence) = &patch.recurrence {
+      builder.push(", recurrence = ").push_bind(recurrence.clone());
+    }
+    if let Some(series_id) = patch.series_id {
+      builder.push(", series_id = ").push_bind(series_id);
+    }
+    builder
+      .push(" WHERE id = ")
+      .push_bind(id)
+      .push(" AND version = ")
+      .push_bind(version)
+      .push(" AND organization_id = ")
+      .push_bind(tenant.organization_id);
+    if let Some(project_id) = tenant.project_id {
+      builder.push(" AND project_id = ").push_bind(project_id);
+    }
+    builder.push(" AND deleted_at IS NULL RETURNING ").push(TODO_COLUMNS);
+
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let patched_todo = builder
+      .build_query_as::<Todo>()
+      .fetch_one(&mut *tx)
+      .await?;
+    if let Some(follow_up) = &follow_up {
+      insert_todo(&mut tx, tenant, follow_up).await?;
+    }
+    tx.commit().await?;
+    Ok(patched_todo)
+  }
+
+  // ゴミ箱に移動する。コメントなどの関連データは物理削除（パージ）の際に ON DELETE CASCADE で消える
+  async fn delete(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    trash_todo(&mut tx, tenant, id).await?;
+    tx.commit().await?;
     Ok(())
   }
+
+  async fn apply_changes(&self, tenant: Tenant, changes: Vec<TodoChange>, audit: &AuditContext) -> Result<Vec<Result<Option<Todo>, sqlx::Error>>, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let mut results = Vec::with_capacity(changes.len());
+    for change in changes {
+      // 1 件ごとにセーブポイントを置き、失敗した変更だけを取り消す
+      let mut savepoint = tx.begin().await?;
+      let result = match &change {
+        TodoChange::Update { todo, follow_up } => match update_todo(&mut savepoint, tenant, todo).await {
+          Ok(updated) => match follow_up {
+            Some(follow_up) => insert_todo(&mut savepoint, tenant, follow_up).await.map(|_| Some(updated)),
+            None => Ok(Some(updated)),
+          },
+          Err(err) => Err(err),
+        },
+        TodoChange::Delete(id) => trash_todo(&mut savepoint, tenant, *id).await.map(|_| None),
+      };
+      if result.is_ok() {
+        savepoint.commit().await?;
+      } else {
+        savepoint.rollback().await?;
+      }
+      results.push(result);
+    }
+    tx.commit().await?;
+    Ok(results)
+  }
+
+  async fn find_trashed(&self, tenant: Tenant) -> Result<Vec<Todo>, sqlx::Error> {
+    let todos = sqlx::query_as::<_, Todo>(
+      &format!(
+        "SELECT {} FROM todos WHERE organization_id = $1 AND ($2::uuid IS NULL OR project_id = $2) AND deleted_at IS NOT NULL ORDER BY deleted_at DESC, id",
+        TODO_COLUMNS
+      )
+    )
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(todos)
+  }
+
+  async fn restore(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Todo, sqlx::Error> {
+    let mut tx = begin_audited(&self.pool, audit).await?;
+    let restored_todo = sqlx::query_as::<_, Todo>(
+      &format!(
+        "UPDATE todos SET deleted_at = NULL, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $1 AND organization_id = $2 AND ($3::uuid IS NULL OR project_id = $3) AND deleted_at IS NOT NULL
+          RETURNING {}",
+        TODO_COLUMNS
+      )
+    )
+    .bind(id)
+    .bind(tenant.organization_id)
+    .bind(tenant.project_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(restored_todo)
+  }
+
+  async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<Purged, sqlx::Error> {
+    let purged = sqlx::query_as::<_, Purged>(PURGE_SQL)
+      .bind(cutoff)
+      .fetch_one(&self.pool)
+      .await?;
+    Ok(purged)
+  }
 }
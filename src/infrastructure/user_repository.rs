@@ -0,0 +1,131 @@
+use crate::domain::models::user::{EmailVerification, User};
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::infrastructure::db::DbPool;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct UserRepositoryImpl {
+  pub pool: DbPool,
+}
+
+impl UserRepositoryImpl {
+  pub fn new(pool: DbPool) -> Self {
+    Self { pool }
+  }
+}
+
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+  async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+    let user = sqlx::query_as::<_, User>(
+      "SELECT id, name, email, created_at, updated_at FROM users WHERE id = $1"
+    )
+    .bind(id)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(user)
+  }
+
+  async fn find_members_by_ids(&self, organization_id: Uuid, ids: &[Uuid]) -> Result<Vec<User>, sqlx::Error> {
+    let users = sqlx::query_as::<_, User>(
+      "SELECT u.id, u.name, u.email, u.created_at, u.updated_at FROM users u
+        JOIN organization_memberships m ON m.user_id = u.id
+        WHERE m.organization_id = $1 AND u.id = ANY($2) ORDER BY u.name, u.id"
+    )
+    .bind(organization_id)
+    .bind(ids)
+    .fetch_all(&self.pool)
+    .await?;
+    Ok(users)
+  }
+
+  async fn create_with_password(&self, user: User, password_hash: &str) -> Result<User, sqlx::Error> {
+    let created_user = sqlx::query_as::<_, User>(
+        "INSERT INTO users (id, name, email, password_hash, created_at, updated_at)
+          VALUES ($1, $2, $3, $4, $5, $6)
+          RETURNING id, name, email, created_at, updated_at"
+    )
+    .bind(user.id)
+    .bind(&user.name)
+    .bind(&user.email)
+    .bind(password_hash)
+    .bind(user.created_at)
+    .bind(user.updated_at)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(created_user)
+  }
+
+  async fn find_credentials_by_email(&self, email: &str) -> Result<Option<(Uuid, Option<String>)>, sqlx::Error> {
+    let credentials = sqlx::query_as::<_, (Uuid, Option<String>)>(
+      "SELECT id, password_hash FROM users WHERE lower(email) = lower($1)"
+    )
+    .bind(email)
+    .fetch_optional(&self.pool)
+    .await?;
+    Ok(credentials)
+  }
+
+  async fn update(&self, user: User) -> Result<User, sqlx::Error> {
+    let updated_user = sqlx::query_as::<_, User>(
+        "UPDATE users SET name = $1, email = $2, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+          WHERE id = $3
+          RETURNING id, name, email, created_at, updated_at"
+    )
+    .bind(&user.name)
+    .bind(&user.email)
+    .bind(user.id)
+    .fetch_one(&self.pool)
+    .await?;
+    Ok(updated_user)
+  }
+
+  async fn save_email_verification(&self, verification: &EmailVerification) -> Result<(), sqlx::Error> {
+    sqlx::query(
+      "INSERT INTO email_verifications (user_id, email, token_hash, expires_at) VALUES ($1, $2, $3, $4)
+        ON CONFLICT (user_id) DO UPDATE SET
+          email = EXCLUDED.email, token_hash = EXCLUDED.token_hash, expires_at = EXCLUDED.expires_at, created_at = now()"
+    )
+    .bind(verification.user_id)
+    .bind(&verification.email)
+    .bind(&verification.token_hash)
+    .bind(verification.expires_at)
+    .execute(&self.pool)
+    .await?;
+    Ok(())
+  }
+
+  async fn confirm_email(&self, user_id: Uuid, token_hash: &str, now: DateTime<Utc>) -> Result<User, sqlx::Error> {
+    let mut tx = self.pool.begin().await?;
+    let email = sqlx::query_scalar::<_, String>(
+      "DELETE FROM email_verifications WHERE user_id = $1 AND token_hash = $2 AND expires_at > $3 RETURNING email"
+    )
+    .bind(user_id)
+    .bind(token_hash)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+    let updated_user = sqlx::query_as::<_, User>(
+      "UPDATE users SET email = $1, updated_at = (NOW() AT TIME ZONE 'Asia/Tokyo')
+        WHERE id = $2
+        RETURNING id, name, email, created_at, updated_at"
+    )
+    .bind(&email)
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(updated_user)
+  }
+
+  async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+    Ok(())
+  }
+}
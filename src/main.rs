@@ -1,20 +1,72 @@
-use axum::{Router, routing::get};
+use axum::{Router, middleware, routing::get};
 use dotenvy::dotenv;
 use sqlx::PgPool;
 use std::env;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::net::TcpListener;
-use tracing::{info, Level};
+use tracing::{error, info, Level};
 use tracing_subscriber::FmtSubscriber;
-use utoipa::OpenApi;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::infrastructure::todo_repository::TodoRepositoryImpl;
 use crate::infrastructure::invoice_repository::InvoiceRepositoryImpl;
+use crate::infrastructure::api_key_repository::ApiKeyRepositoryImpl;
+use crate::infrastructure::assignment_repository::AssignmentRepositoryImpl;
+use crate::infrastructure::attachment_repository::AttachmentRepositoryImpl;
+use crate::infrastructure::audit_repository::AuditRepositoryImpl;
+use crate::infrastructure::comment_repository::CommentRepositoryImpl;
+use crate::infrastructure::idempotency_repository::IdempotencyRepositoryImpl;
+use crate::infrastructure::local_blob_store::LocalBlobStore;
+use crate::infrastructure::log_email_sender::LogEmailSender;
+use crate::infrastructure::oidc_provider::{OidcConfig, OidcProvider};
+use crate::infrastructure::oidc_repository::OidcRepositoryImpl;
+use crate::infrastructure::organization_repository::OrganizationRepositoryImpl;
+use crate::infrastructure::s3_blob_store::{S3BlobStore, S3Config};
+use crate::infrastructure::share_repository::ShareRepositoryImpl;
+use crate::infrastructure::user_repository::UserRepositoryImpl;
+use crate::domain::models::oidc::GroupRoleMapping;
+use crate::domain::repositories::blob_store::BlobStore;
+use crate::domain::repositories::identity_provider::IdentityProvider;
+use crate::infrastructure::project_repository::ProjectRepositoryImpl;
+use crate::infrastructure::refresh_token_repository::RefreshTokenRepositoryImpl;
+use crate::infrastructure::saved_filter_repository::SavedFilterRepositoryImpl;
 use crate::presentation::handlers::todo_handler::create_todo_router;
 use crate::presentation::handlers::invoice_handler::create_invoice_router;
+use crate::presentation::handlers::api_key_handler::create_api_key_router;
+use crate::presentation::handlers::assignment_handler::create_assignment_router;
+use crate::presentation::handlers::attachment_handler::{create_attachment_router, MULTIPART_OVERHEAD};
+use crate::presentation::handlers::audit_handler::create_audit_router;
+use crate::presentation::handlers::auth_handler::create_auth_router;
+use crate::presentation::handlers::comment_handler::create_comment_router;
+use crate::presentation::handlers::oidc_handler::create_oidc_router;
+use crate::presentation::handlers::organization_handler::create_organization_router;
+use crate::presentation::handlers::project_handler::create_project_router;
+use crate::presentation::handlers::saved_filter_handler::create_saved_filter_router;
+use crate::presentation::handlers::share_handler::{create_share_router, create_shared_project_router};
+use crate::presentation::handlers::trash_handler::create_trash_router;
+use crate::presentation::handlers::user_handler::create_user_router;
+use crate::presentation::auth::{authenticate, require_authentication, AuthState};
+use crate::presentation::idempotency::{idempotency, IdempotencyState};
+use crate::presentation::tenant::{resolve_tenant, TenantState};
 use crate::usecase::todo_usecase::TodoUsecase;
 use crate::usecase::invoice_usecase::InvoiceUsecase;
+use crate::usecase::api_key_usecase::ApiKeyUsecase;
+use crate::usecase::assignment_usecase::AssignmentUsecase;
+use crate::usecase::attachment_usecase::AttachmentUsecase;
+use crate::usecase::audit_usecase::AuditUsecase;
+use crate::usecase::auth_usecase::{AuthService, AuthUsecase, JwtKeys};
+use crate::usecase::comment_usecase::CommentUsecase;
+use crate::usecase::idempotency_usecase::{IdempotencyService, IdempotencyUsecase};
+use crate::usecase::oidc_usecase::OidcUsecase;
+use crate::usecase::organization_usecase::OrganizationUsecase;
+use crate::usecase::project_usecase::ProjectUsecase;
+use crate::usecase::saved_filter_usecase::SavedFilterUsecase;
+use crate::usecase::share_usecase::ShareUsecase;
+use crate::usecase::trash_usecase::{TrashService, TrashUsecase};
+use crate::usecase::user_usecase::UserUsecase;
 
 mod domain;
 mod infrastructure;
@@ -26,22 +78,237 @@ mod usecase;
     paths(
         presentation::handlers::todo_handler::get_all_todos,
         presentation::handlers::todo_handler::get_todo_by_id,
+        presentation::handlers::todo_handler::get_todo_series,
+        presentation::handlers::todo_handler::search_todos,
+        presentation::handlers::todo_handler::get_board,
         presentation::handlers::todo_handler::create_todo,
         presentation::handlers::todo_handler::update_todo,
+        presentation::handlers::todo_handler::patch_todo,
+        presentation::handlers::todo_handler::move_todo,
+        presentation::handlers::todo_handler::bulk_update_todos,
+        presentation::handlers::todo_handler::change_todo_status,
         presentation::handlers::todo_handler::delete_todo,
+        presentation::handlers::todo_handler::restore_todo,
         presentation::handlers::invoice_handler::get_all_invoices,
         presentation::handlers::invoice_handler::get_invoice_by_id,
         presentation::handlers::invoice_handler::create_invoice,
         presentation::handlers::invoice_handler::update_invoice,
+        presentation::handlers::invoice_handler::patch_invoice,
         presentation::handlers::invoice_handler::delete_invoice,
+        presentation::handlers::invoice_handler::restore_invoice,
+        presentation::handlers::api_key_handler::get_api_keys,
+        presentation::handlers::api_key_handler::create_api_key,
+        presentation::handlers::api_key_handler::revoke_api_key,
+        presentation::handlers::assignment_handler::set_assignees,
+        presentation::handlers::assignment_handler::get_assignment_history,
+        presentation::handlers::assignment_handler::get_watchers,
+        presentation::handlers::assignment_handler::watch_todo,
+        presentation::handlers::assignment_handler::unwatch_todo,
+        presentation::handlers::assignment_handler::get_my_work,
+        presentation::handlers::attachment_handler::get_todo_attachments,
+        presentation::handlers::attachment_handler::upload_todo_attachment,
+        presentation::handlers::attachment_handler::download_todo_attachment,
+        presentation::handlers::attachment_handler::delete_todo_attachment,
+        presentation::handlers::attachment_handler::get_invoice_attachments,
+        presentation::handlers::attachment_handler::upload_invoice_attachment,
+        presentation::handlers::attachment_handler::download_invoice_attachment,
+        presentation::handlers::attachment_handler::delete_invoice_attachment,
+        presentation::handlers::audit_handler::get_todo_history,
+        presentation::handlers::audit_handler::get_invoice_history,
+        presentation::handlers::audit_handler::undo_operation,
+        presentation::handlers::auth_handler::register,
+        presentation::handlers::auth_handler::login,
+        presentation::handlers::auth_handler::refresh,
+        presentation::handlers::auth_handler::logout,
+        presentation::handlers::oidc_handler::begin_oidc_login,
+        presentation::handlers::oidc_handler::complete_oidc_login,
+        presentation::handlers::oidc_handler::begin_oidc_link,
+        presentation::handlers::comment_handler::get_comments,
+        presentation::handlers::comment_handler::get_comment,
+        presentation::handlers::comment_handler::get_comment_revisions,
+        presentation::handlers::comment_handler::create_comment,
+        presentation::handlers::comment_handler::update_comment,
+        presentation::handlers::comment_handler::delete_comment,
+        presentation::handlers::organization_handler::get_organizations,
+        presentation::handlers::organization_handler::create_organization,
+        presentation::handlers::organization_handler::get_members,
+        presentation::handlers::organization_handler::change_member_role,
+        presentation::handlers::organization_handler::remove_member,
+        presentation::handlers::organization_handler::get_invitations,
+        presentation::handlers::organization_handler::create_invitation,
+        presentation::handlers::organization_handler::revoke_invitation,
+        presentation::handlers::organization_handler::accept_invitation,
+        presentation::handlers::project_handler::get_all_projects,
+        presentation::handlers::project_handler::get_project_by_id,
+        presentation::handlers::project_handler::create_project,
+        presentation::handlers::project_handler::update_project,
+        presentation::handlers::project_handler::delete_project,
+        presentation::handlers::saved_filter_handler::get_all_saved_filters,
+        presentation::handlers::saved_filter_handler::get_saved_filter_by_id,
+        presentation::handlers::saved_filter_handler::create_saved_filter,
+        presentation::handlers::saved_filter_handler::update_saved_filter,
+        presentation::handlers::saved_filter_handler::delete_saved_filter,
+        presentation::handlers::share_handler::get_shares,
+        presentation::handlers::share_handler::create_share,
+        presentation::handlers::share_handler::revoke_share,
+        presentation::handlers::share_handler::get_collaborators,
+        presentation::handlers::share_handler::remove_collaborator,
+        presentation::handlers::share_handler::get_shared_projects,
+        presentation::handlers::share_handler::accept_share,
+        presentation::handlers::share_handler::leave_shared_project,
+        presentation::handlers::trash_handler::get_trash,
+        presentation::handlers::user_handler::get_user_by_id,
+        presentation::handlers::user_handler::update_user,
+        presentation::handlers::user_handler::verify_email,
+        presentation::handlers::user_handler::delete_user,
     ),
     tags(
         (name = "todos", description = "Todo API"),
-        (name = "invoices", description = "Invoice API")
-    )
+        (name = "invoices", description = "Invoice API"),
+        (name = "api-keys", description = "Personal API key API (keys cannot manage themselves)"),
+        (name = "assignments", description = "Todo assignee and watcher API"),
+        (name = "attachments", description = "Todo and invoice attachment API"),
+        (name = "auth", description = "Registration, login (password or OpenID Connect) and token refresh API"),
+        (name = "comments", description = "Todo comment API"),
+        (name = "history", description = "Todo and invoice change history and undo API"),
+        (name = "organizations", description = "Organization, membership and invitation API"),
+        (name = "projects", description = "Project and workflow API"),
+        (name = "saved-filters", description = "Saved filter (smart list) API"),
+        (name = "shares", description = "Project sharing API (send X-Shared-Project-Id to work in a project shared with you)"),
+        (name = "trash", description = "Trash API"),
+        (name = "users", description = "Own account API (email changes take effect after verification)")
+    ),
+    modifiers(&SecurityAddon),
+    security(("bearer_auth" = []))
 )]
 struct ApiDoc;
 
+// /api/auth 以外は Authorization: Bearer のアクセストークンが必要。API キー（tak_ で始まる）も同じヘッダで指定できる
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new()
+                .scheme(HttpAuthScheme::Bearer)
+                .bearer_format("JWT")
+                .description(Some("JWT access token, or an API key (tak_...) limited to its scopes"))
+                .build()),
+        );
+    }
+}
+
+// 添付ファイルの既定の上限（10 MiB）
+const DEFAULT_ATTACHMENT_MAX_BYTES: usize = 10 * 1024 * 1024;
+
+// ゴミ箱の既定の保持期間と、パージジョブの実行間隔
+const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+const DEFAULT_TRASH_PURGE_INTERVAL_SECS: u64 = 60 * 60;
+
+// 一括操作 1 回あたりの既定の操作数の上限
+const DEFAULT_BULK_MAX_OPERATIONS: usize = 100;
+
+// 操作を取り消せる既定の期間
+const DEFAULT_UNDO_WINDOW_SECS: i64 = 5 * 60;
+
+// Idempotency-Key を記録しておく既定の期間と、処理中のキーを中断されたとみなすまでの既定の期間、
+// 期限切れのキーを削除する間隔
+const DEFAULT_IDEMPOTENCY_KEY_TTL_SECS: i64 = 24 * 60 * 60;
+const DEFAULT_IDEMPOTENCY_LEASE_SECS: i64 = 60;
+const IDEMPOTENCY_PURGE_INTERVAL_SECS: u64 = 60 * 60;
+
+// アクセストークンとリフレッシュトークンの既定の有効期間と、期限切れのリフレッシュトークンを削除する間隔
+const DEFAULT_ACCESS_TOKEN_TTL_SECS: i64 = 15 * 60;
+const DEFAULT_REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60;
+const REFRESH_TOKEN_PURGE_INTERVAL_SECS: u64 = 60 * 60;
+
+// HS256 の署名鍵として短すぎる値は受け付けない
+const JWT_SECRET_MIN_BYTES: usize = 32;
+
+// OpenID Connect で要求する既定のスコープと、グループの一覧が入る既定のクレーム名
+const DEFAULT_OIDC_SCOPES: &str = "openid email profile";
+const DEFAULT_OIDC_GROUPS_CLAIM: &str = "groups";
+
+// BLOB_STORE=s3 の場合は S3 互換ストレージ、それ以外はローカルのディレクトリに保存する
+fn blob_store_from_env() -> Result<Arc<dyn BlobStore>, Box<dyn std::error::Error>> {
+    match env::var("BLOB_STORE").as_deref() {
+        Ok("s3") => {
+            let store = S3BlobStore::new(S3Config {
+                bucket: env::var("S3_BUCKET")?,
+                region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                endpoint: env::var("S3_ENDPOINT").ok(),
+                access_key_id: env::var("S3_ACCESS_KEY_ID")?,
+                secret_access_key: env::var("S3_SECRET_ACCESS_KEY")?,
+            })?;
+            Ok(Arc::new(store))
+        }
+        _ => {
+            let root = env::var("BLOB_STORE_PATH").unwrap_or_else(|_| "./storage".to_string());
+            Ok(Arc::new(LocalBlobStore::new(root)))
+        }
+    }
+}
+
+// OIDC_ISSUER_URL を設定した場合だけ OpenID Connect でのログインを有効にする。起動時にディスカバリを行う
+async fn identity_provider_from_env() -> Result<Option<Arc<dyn IdentityProvider>>, Box<dyn std::error::Error>> {
+    let Ok(issuer_url) = env::var("OIDC_ISSUER_URL") else {
+        return Ok(None);
+    };
+    let provider = OidcProvider::discover(OidcConfig {
+        issuer_url,
+        client_id: env::var("OIDC_CLIENT_ID")?,
+        client_secret: env::var("OIDC_CLIENT_SECRET").ok(),
+        redirect_uri: env::var("OIDC_REDIRECT_URI")?,
+        scopes: env::var("OIDC_SCOPES").unwrap_or_else(|_| DEFAULT_OIDC_SCOPES.to_string()),
+        groups_claim: env::var("OIDC_GROUPS_CLAIM").unwrap_or_else(|_| DEFAULT_OIDC_GROUPS_CLAIM.to_string()),
+    })
+    .await?;
+    Ok(Some(Arc::new(provider)))
+}
+
+// 保持期間を過ぎたゴミ箱の Todo・請求書を定期的に物理削除する
+fn spawn_trash_purge_job<T: TrashService + Send + Sync + 'static>(trash_service: T, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match trash_service.purge_expired().await {
+                Ok(summary) if summary.todos > 0 || summary.invoices > 0 => {
+                    info!("Purged {} todos and {} invoices from trash", summary.todos, summary.invoices);
+                }
+                Ok(_) => {}
+                Err(err) => error!("Failed to purge trash: {}", err),
+            }
+        }
+    });
+}
+
+fn spawn_idempotency_purge_job<T: IdempotencyService + Send + Sync + 'static>(idempotency_service: Arc<T>, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = idempotency_service.purge_expired().await {
+                error!("Failed to purge idempotency keys: {}", err);
+            }
+        }
+    });
+}
+
+fn spawn_refresh_token_purge_job<T: AuthService + Send + Sync + 'static>(auth_service: Arc<T>, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = auth_service.purge_expired().await {
+                error!("Failed to purge refresh tokens: {}", err);
+            }
+        }
+    });
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
@@ -54,17 +321,185 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let database_url = env::var("DATABASE_URL")?;
     let pool = PgPool::connect(&database_url).await?;
 
+    let project_repository = ProjectRepositoryImpl::new(pool.clone());
+    let project_service = ProjectUsecase::new(project_repository.clone());
+    let share_service = Arc::new(ShareUsecase::new(
+        ShareRepositoryImpl::new(pool.clone()),
+        project_repository.clone(),
+        UserRepositoryImpl::new(pool.clone()),
+    ));
+
     let todo_repository = TodoRepositoryImpl::new(pool.clone());
-    let todo_service = TodoUsecase::new(todo_repository);
+    let todo_service = TodoUsecase::new(todo_repository.clone(), project_repository);
+
+    let user_repository = UserRepositoryImpl::new(pool.clone());
+    let user_service = UserUsecase::new(user_repository.clone(), Arc::new(LogEmailSender));
+
+    let organization_service = Arc::new(OrganizationUsecase::new(OrganizationRepositoryImpl::new(pool.clone()), user_repository.clone()));
+
+    let jwt_secret = env::var("JWT_SECRET")?;
+    if jwt_secret.len() < JWT_SECRET_MIN_BYTES {
+        return Err(format!("JWT_SECRET must be at least {} bytes", JWT_SECRET_MIN_BYTES).into());
+    }
+    let access_token_ttl_secs = env::var("ACCESS_TOKEN_TTL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ACCESS_TOKEN_TTL_SECS);
+    let refresh_token_ttl_secs = env::var("REFRESH_TOKEN_TTL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_REFRESH_TOKEN_TTL_SECS);
+    let auth_service = Arc::new(AuthUsecase::new(
+        user_repository.clone(),
+        RefreshTokenRepositoryImpl::new(pool.clone()),
+        JwtKeys::new(jwt_secret.as_bytes()),
+        chrono::Duration::seconds(access_token_ttl_secs),
+        chrono::Duration::seconds(refresh_token_ttl_secs),
+    ));
+    spawn_refresh_token_purge_job(auth_service.clone(), std::time::Duration::from_secs(REFRESH_TOKEN_PURGE_INTERVAL_SECS));
+
+    let api_key_service = Arc::new(ApiKeyUsecase::new(ApiKeyRepositoryImpl::new(pool.clone())));
+    let auth_state = AuthState {
+        auth_service: auth_service.clone(),
+        api_key_service: api_key_service.clone(),
+    };
+
+    // OIDC_GROUP_ROLES は "group=organization_id:role" をカンマで区切ったもの。
+    // 設定した組織での owner 以外の役割は、ログインのたびにグループに合わせる
+    let oidc_router = match identity_provider_from_env().await? {
+        Some(provider) => {
+            let group_role_mappings = GroupRoleMapping::parse_list(&env::var("OIDC_GROUP_ROLES").unwrap_or_default())?;
+            let oidc_service = OidcUsecase::new(
+                OidcRepositoryImpl::new(pool.clone()),
+                user_repository.clone(),
+                OrganizationRepositoryImpl::new(pool.clone()),
+                provider,
+                group_role_mappings,
+            );
+            create_oidc_router(oidc_service, auth_service.clone())
+        }
+        None => Router::new(),
+    };
+
+    let assignment_repository = AssignmentRepositoryImpl::new(pool.clone());
+    let assignment_service = AssignmentUsecase::new(assignment_repository, todo_repository.clone(), user_repository.clone());
+
+    let comment_repository = CommentRepositoryImpl::new(pool.clone());
+    let comment_service = CommentUsecase::new(comment_repository, todo_repository.clone(), user_repository);
 
     let invoice_repository = InvoiceRepositoryImpl::new(pool.clone());
-    let invoice_service = InvoiceUsecase::new(invoice_repository);
+    let invoice_service = InvoiceUsecase::new(invoice_repository.clone());
+
+    let blob_store = blob_store_from_env()?;
+
+    let attachment_repository = AttachmentRepositoryImpl::new(pool.clone());
+    let attachment_service = AttachmentUsecase::new(
+        attachment_repository,
+        todo_repository.clone(),
+        invoice_repository.clone(),
+        blob_store.clone(),
+    );
+    let attachment_max_bytes = env::var("ATTACHMENT_MAX_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ATTACHMENT_MAX_BYTES);
+
+    let trash_retention_days = env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS);
+    let trash_purge_interval = env::var("TRASH_PURGE_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_PURGE_INTERVAL_SECS);
+    let trash_service = TrashUsecase::new(
+        todo_repository,
+        invoice_repository,
+        blob_store,
+        chrono::Duration::days(trash_retention_days),
+    );
+    spawn_trash_purge_job(trash_service.clone(), std::time::Duration::from_secs(trash_purge_interval));
+
+    let audit_repository = AuditRepositoryImpl::new(pool.clone());
+    let undo_window_secs = env::var("UNDO_WINDOW_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_UNDO_WINDOW_SECS);
+    let audit_service = AuditUsecase::new(audit_repository, chrono::Duration::seconds(undo_window_secs));
+
+    let bulk_max_operations = env::var("BULK_MAX_OPERATIONS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_BULK_MAX_OPERATIONS);
+
+    let saved_filter_repository = SavedFilterRepositoryImpl::new(pool.clone());
+    let saved_filter_service = SavedFilterUsecase::new(saved_filter_repository);
+
+    let idempotency_key_ttl_secs = env::var("IDEMPOTENCY_KEY_TTL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_IDEMPOTENCY_KEY_TTL_SECS);
+    let idempotency_lease_secs = env::var("IDEMPOTENCY_LEASE_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_IDEMPOTENCY_LEASE_SECS);
+    let idempotency_service = Arc::new(IdempotencyUsecase::new(
+        IdempotencyRepositoryImpl::new(pool.clone()),
+        chrono::Duration::seconds(idempotency_key_ttl_secs),
+        chrono::Duration::seconds(idempotency_lease_secs),
+    ));
+    spawn_idempotency_purge_job(idempotency_service.clone(), std::time::Duration::from_secs(IDEMPOTENCY_PURGE_INTERVAL_SECS));
+    let idempotency_state = IdempotencyState {
+        idempotency_service,
+        // 添付ファイルのアップロードも比較できるようにする
+        max_body_bytes: attachment_max_bytes + MULTIPART_OVERHEAD,
+    };
+
+    // 組織ごとに分かれるデータは、認証の後に対象の組織を決める。
+    // プロジェクトに属するデータは、共有されたプロジェクト（X-Shared-Project-Id）としても扱える
+    let tenant_state = TenantState {
+        organization_service: organization_service.clone(),
+        share_service: share_service.clone(),
+        allow_shared: true,
+    };
+    let shareable_routes = create_todo_router(todo_service, bulk_max_operations)
+        .merge(create_assignment_router(assignment_service))
+        .merge(create_attachment_router(attachment_service, attachment_max_bytes))
+        .merge(create_comment_router(comment_service))
+        .merge(create_project_router(project_service))
+        .route_layer(middleware::from_fn_with_state(
+            tenant_state.clone(),
+            resolve_tenant::<OrganizationUsecase<OrganizationRepositoryImpl, UserRepositoryImpl>, ShareUsecase<ShareRepositoryImpl, ProjectRepositoryImpl, UserRepositoryImpl>>,
+        ));
+    let organization_routes = create_invoice_router(invoice_service)
+        .merge(create_audit_router(audit_service))
+        .merge(create_share_router(share_service.clone()))
+        .merge(create_trash_router(trash_service))
+        .route_layer(middleware::from_fn_with_state(
+            TenantState { allow_shared: false, ..tenant_state },
+            resolve_tenant::<OrganizationUsecase<OrganizationRepositoryImpl, UserRepositoryImpl>, ShareUsecase<ShareRepositoryImpl, ProjectRepositoryImpl, UserRepositoryImpl>>,
+        ));
 
     let app = Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .route("/", get(|| async { "Hello, Axum!!!!" }))
-        .nest("/api", create_todo_router(todo_service)
-            .merge(create_invoice_router(invoice_service)));
+        .nest("/api", shareable_routes
+            .merge(organization_routes)
+            .merge(create_api_key_router(api_key_service))
+            .merge(create_organization_router(organization_service))
+            .merge(create_saved_filter_router(saved_filter_service))
+            .merge(create_shared_project_router(share_service))
+            .merge(create_user_router(user_service))
+            .route_layer(middleware::from_fn(require_authentication))
+            .layer(middleware::from_fn_with_state(idempotency_state, idempotency::<IdempotencyUsecase<IdempotencyRepositoryImpl>>))
+            // ログインやトークン更新のレスポンス（トークン）を記録しないように、Idempotency-Key の対象から外す
+            .merge(create_auth_router(auth_service))
+            .merge(oidc_router)
+            // Idempotency-Key の照合にユーザーを使うため、認証を先に行う
+            .layer(middleware::from_fn_with_state(
+                auth_state,
+                authenticate::<AuthUsecase<UserRepositoryImpl, RefreshTokenRepositoryImpl>, ApiKeyUsecase<ApiKeyRepositoryImpl>>,
+            )));
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 3001));
     info!("Server running at http://{}", addr);
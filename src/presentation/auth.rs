@@ -0,0 +1,132 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::{header, HeaderValue, Method};
+use std::sync::Arc;
+
+use crate::domain::error::AppError;
+use crate::domain::models::api_key::{ApiScope, ApiScopes, TOKEN_PREFIX};
+use crate::presentation::current_user::CurrentUser;
+use crate::usecase::api_key_usecase::ApiKeyService;
+use crate::usecase::auth_usecase::AuthService;
+
+#[derive(Clone)]
+pub struct AuthState<T: AuthService, K: ApiKeyService> {
+  pub auth_service: Arc<T>,
+  pub api_key_service: Arc<K>,
+}
+
+// 認証に失敗した場合のレスポンス。RFC 6750 の WWW-Authenticate ヘッダを付ける
+fn unauthorized(err: AppError) -> Response {
+  let mut response = err.into_response();
+  response.headers_mut().insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+  response
+}
+
+// API キーで呼び出すのに必要なスコープ。パスは /api を除いたもの。
+// 対応するスコープがないエンドポイント（API キー自体の管理や組織の管理など）は API キーでは呼び出せない。
+// プロジェクトの共有の管理（/projects/{id}/shares・/projects/{id}/collaborators）は組織の外のユーザーへのアクセスを
+// 左右するため、projects のスコープに含めない
+fn required_scope(method: &Method, path: &str) -> Option<ApiScope> {
+  let read = matches!(*method, Method::GET | Method::HEAD);
+  let mut segments = path.trim_start_matches('/').split('/');
+  let resource = segments.next().unwrap_or_default();
+  if resource == "projects" && matches!(segments.nth(1), Some("shares" | "collaborators")) {
+    return None;
+  }
+  match (resource, read) {
+    ("todos" | "me", true) => Some(ApiScope::TodosRead),
+    ("todos" | "me", false) => Some(ApiScope::TodosWrite),
+    ("invoices", true) => Some(ApiScope::InvoicesRead),
+    ("invoices", false) => Some(ApiScope::InvoicesWrite),
+    ("projects", true) => Some(ApiScope::ProjectsRead),
+    ("projects", false) => Some(ApiScope::ProjectsWrite),
+    _ => None,
+  }
+}
+
+fn authorize_scope(request: &Request, scopes: &ApiScopes) -> Result<(), AppError> {
+  match required_scope(request.method(), request.uri().path()) {
+    Some(required) if scopes.allows(required) => Ok(()),
+    Some(required) => Err(AppError::Forbidden(format!("The API key does not have the {} scope", required.as_str()))),
+    None => Err(AppError::Forbidden("This endpoint cannot be called with an API key".to_string())),
+  }
+}
+
+// Authorization: Bearer のアクセストークンまたは API キーを検証し、ユーザーを CurrentUser として extensions に入れる。
+// API キーの場合は、ここでスコープも確認する（組織内の役割による制限はアクセストークンと同じ）。
+// ヘッダがなければそのまま通し、認証が必要かどうかは require_authentication と各エクストラクタに任せる
+pub async fn authenticate<T, K>(
+  State(state): State<AuthState<T, K>>,
+  mut request: Request,
+  next: Next,
+) -> Response
+where
+  T: AuthService + Send + Sync + 'static,
+  K: ApiKeyService + Send + Sync + 'static,
+{
+  let Some(value) = request.headers().get(header::AUTHORIZATION) else {
+    return next.run(request).await;
+  };
+  let Some(token) = value.to_str().ok().and_then(|value| value.strip_prefix("Bearer ")) else {
+    return unauthorized(AppError::Unauthorized("Authorization header must be a Bearer token".to_string()));
+  };
+  let token = token.trim();
+
+  let user_id = if token.starts_with(TOKEN_PREFIX) {
+    let api_key = match state.api_key_service.authenticate(token).await {
+      Ok(api_key) => api_key,
+      Err(err @ AppError::Unauthorized(_)) => return unauthorized(err),
+      Err(err) => return err.into_response(),
+    };
+    if let Err(err) = authorize_scope(&request, &api_key.scopes) {
+      return err.into_response();
+    }
+    api_key.user_id
+  } else {
+    match state.auth_service.authenticate(token) {
+      Ok(user_id) => user_id,
+      Err(err) => return unauthorized(err),
+    }
+  };
+  request.extensions_mut().insert(CurrentUser(user_id));
+  next.run(request).await
+}
+
+// 認証していないリクエストを 401 で拒否する
+pub async fn require_authentication(request: Request, next: Next) -> Response {
+  if request.extensions().get::<CurrentUser>().is_none() {
+    return unauthorized(AppError::Unauthorized("Authentication required".to_string()));
+  }
+  next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn required_scope_maps_resources_to_scopes() {
+    assert_eq!(required_scope(&Method::GET, "/todos/1"), Some(ApiScope::TodosRead));
+    assert_eq!(required_scope(&Method::POST, "/todos"), Some(ApiScope::TodosWrite));
+    assert_eq!(required_scope(&Method::HEAD, "/invoices"), Some(ApiScope::InvoicesRead));
+    assert_eq!(required_scope(&Method::PUT, "/projects/1"), Some(ApiScope::ProjectsWrite));
+    assert_eq!(required_scope(&Method::GET, "/projects"), Some(ApiScope::ProjectsRead));
+  }
+
+  #[test]
+  fn required_scope_rejects_unscoped_endpoints() {
+    assert_eq!(required_scope(&Method::POST, "/api-keys"), None);
+    assert_eq!(required_scope(&Method::GET, "/organizations"), None);
+    assert_eq!(required_scope(&Method::POST, "/shared-projects/accept"), None);
+  }
+
+  #[test]
+  fn required_scope_rejects_project_sharing() {
+    assert_eq!(required_scope(&Method::GET, "/projects/1/shares"), None);
+    assert_eq!(required_scope(&Method::POST, "/projects/1/shares"), None);
+    assert_eq!(required_scope(&Method::DELETE, "/projects/1/shares/2"), None);
+    assert_eq!(required_scope(&Method::GET, "/projects/1/collaborators"), None);
+    assert_eq!(required_scope(&Method::DELETE, "/projects/1/collaborators/2"), None);
+  }
+}
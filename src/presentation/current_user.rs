@@ -0,0 +1,41 @@
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use http::request::Parts;
+use uuid::Uuid;
+
+use crate::domain::error::AppError;
+use crate::domain::models::audit::AuditContext;
+
+// 認証したユーザー。presentation::auth::authenticate がリクエストの extensions に入れる
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentUser(pub Uuid);
+
+impl<S: Send + Sync> FromRequestParts<S> for CurrentUser {
+  type Rejection = AppError;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    parts
+      .extensions
+      .get::<CurrentUser>()
+      .copied()
+      .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))
+  }
+}
+
+// ユーザーが分かれば使う（履歴の記録など）エンドポイント向け
+impl<S: Send + Sync> OptionalFromRequestParts<S> for CurrentUser {
+  type Rejection = AppError;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+    Ok(parts.extensions.get::<CurrentUser>().copied())
+  }
+}
+
+// 変更履歴に記録する実行者。リクエストごとに新しい操作 ID を割り当てる
+impl<S: Send + Sync> FromRequestParts<S> for AuditContext {
+  type Rejection = AppError;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    let actor_id = parts.extensions.get::<CurrentUser>().map(|CurrentUser(id)| *id);
+    Ok(AuditContext::new(actor_id))
+  }
+}
@@ -0,0 +1,41 @@
+use http::header::{ETAG, IF_MATCH, IF_NONE_MATCH};
+use http::{HeaderMap, HeaderName};
+
+// 版数を強い ETag にする
+pub fn etag(version: i64) -> String {
+  format!("\"{}\"", version)
+}
+
+pub fn etag_header(version: i64) -> [(HeaderName, String); 1] {
+  [(ETAG, etag(version))]
+}
+
+// If-Match で指定された版数。ヘッダがない場合と "*" は None。
+// 単一の強い ETag のみ扱い、それ以外は一致しないものとして Err を返す
+pub fn if_match_version(headers: &HeaderMap) -> Result<Option<i64>, ()> {
+  let Some(value) = headers.get(IF_MATCH) else {
+    return Ok(None);
+  };
+  let value = value.to_str().map_err(|_| ())?.trim();
+  if value == "*" {
+    return Ok(None);
+  }
+  value
+    .strip_prefix('"')
+    .and_then(|value| value.strip_suffix('"'))
+    .and_then(|version| version.parse().ok())
+    .map(Some)
+    .ok_or(())
+}
+
+// If-None-Match が現在の版数に一致するか（弱い比較）
+pub fn if_none_match(headers: &HeaderMap, version: i64) -> bool {
+  let Some(value) = headers.get(IF_NONE_MATCH).and_then(|value| value.to_str().ok()) else {
+    return false;
+  };
+  let current = etag(version);
+  value
+    .split(',')
+    .map(|tag| tag.trim())
+    .any(|tag| tag == "*" || tag.trim_start_matches("W/") == current)
+}
@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::models::api_key::{ApiKey, ApiScope, IssuedApiKey, DEFAULT_TTL_DAYS, MAX_TTL_DAYS, NAME_MAX_LENGTH};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::idempotency::ConfidentialResponse;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::usecase::api_key_usecase::ApiKeyService;
+
+
+pub struct AppState<T: ApiKeyService> {
+  pub api_key_service: Arc<T>,
+}
+
+// 認証のミドルウェアと同じ Arc を共有するため、T: Clone は求めない
+impl<T: ApiKeyService> Clone for AppState<T> {
+  fn clone(&self) -> Self {
+    Self { api_key_service: self.api_key_service.clone() }
+  }
+}
+
+pub fn create_api_key_router<T: ApiKeyService + Send + Sync + 'static>(api_key_service: Arc<T>) -> Router {
+  let state = AppState { api_key_service };
+
+  Router::new()
+    .route("/api-keys", get(get_api_keys::<T>).post(create_api_key::<T>))
+    .route("/api-keys/{id}", delete(revoke_api_key::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ApiKeyRequest {
+  name: String,
+  /// todos:read / todos:write / invoices:read / invoices:write / projects:read / projects:write（write は read を含む）
+  #[schema(value_type = Vec<String>, example = json!(["todos:write"]))]
+  scopes: Vec<ApiScope>,
+  /// 有効期間の日数（1〜365、省略時は 90）
+  expires_in_days: Option<i64>,
+}
+
+impl Validate for ApiKeyRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", self.name.trim(), 1, NAME_MAX_LENGTH);
+    if self.scopes.is_empty() {
+      errors.add("scopes", "required", "scopes must not be empty");
+    }
+    if self.expires_in_days.is_some_and(|days| !(1..=MAX_TTL_DAYS).contains(&days)) {
+      errors.add("expires_in_days", "range", format!("expires_in_days must be between 1 and {}", MAX_TTL_DAYS));
+    }
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+  id: Uuid,
+  name: String,
+  /// キーの先頭の数文字。一覧でキーを見分けるために使う
+  prefix: String,
+  #[schema(value_type = Vec<String>, example = json!(["todos:write"]))]
+  scopes: Vec<ApiScope>,
+  expires_at: DateTime<Utc>,
+  /// 最後に使われた日時（1 分単位）
+  last_used_at: Option<DateTime<Utc>>,
+  created_at: DateTime<Utc>,
+  /// Authorization: Bearer に指定するキー。作成時だけ返す
+  #[serde(skip_serializing_if = "Option::is_none")]
+  token: Option<String>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+  fn from(api_key: ApiKey) -> Self {
+    Self {
+      id: api_key.id,
+      name: api_key.name,
+      prefix: api_key.prefix,
+      scopes: api_key.scopes.0,
+      expires_at: api_key.expires_at,
+      last_used_at: api_key.last_used_at,
+      created_at: api_key.created_at,
+      token: None,
+    }
+  }
+}
+
+impl From<IssuedApiKey> for ApiKeyResponse {
+  fn from(issued: IssuedApiKey) -> Self {
+    Self {
+      token: Some(issued.token),
+      ..Self::from(issued.api_key)
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/api-keys",
+    responses(
+        (status = 200, description = "自分の API キーを取得（期限切れを含む、新しい順）", body = Vec<ApiKeyResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API キーでは呼び出せない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "api-keys"
+)]
+pub async fn get_api_keys<T: ApiKeyService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+) -> impl IntoResponse {
+  match state.api_key_service.get_api_keys(user_id).await {
+    Ok(api_keys) => {
+      let response: Vec<ApiKeyResponse> = api_keys.into_iter().map(ApiKeyResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/api-keys",
+    request_body = ApiKeyRequest,
+    responses(
+        (status = 201, description = "API キーを発行（キーはこのレスポンスでだけ返し、Idempotency-Key の再送用にも記録しない）", body = ApiKeyResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API キーでは呼び出せない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "api-keys"
+)]
+pub async fn create_api_key<T: ApiKeyService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  ValidatedJson(payload): ValidatedJson<ApiKeyRequest>,
+) -> impl IntoResponse {
+  let ttl = Duration::days(payload.expires_in_days.unwrap_or(DEFAULT_TTL_DAYS));
+  match state.api_key_service.create_api_key(user_id, payload.name.trim().to_string(), payload.scopes, ttl).await {
+    // キーを Idempotency-Key の記録に残さない
+    Ok(issued) => (StatusCode::CREATED, Extension(ConfidentialResponse), Json(ApiKeyResponse::from(issued))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/api-keys/{id}",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 204, description = "API キーを取り消す（以降このキーでは認証できない）"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API キーでは呼び出せない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "API キーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "api-keys"
+)]
+pub async fn revoke_api_key<T: ApiKeyService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.api_key_service.revoke_api_key(user_id, id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,243 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::domain::models::assignment::{AssignmentAction, AssignmentEvent, MyWork, MAX_ASSIGNEES};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::domain::models::organization::Tenant;
+use crate::presentation::handlers::todo_handler::TodoResponse;
+use crate::presentation::handlers::user_handler::UserSummaryResponse;
+use crate::usecase::assignment_usecase::AssignmentService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: AssignmentService> {
+  pub assignment_service: Arc<T>,
+}
+
+pub fn create_assignment_router<T: AssignmentService + Send + Sync + 'static + Clone>(assignment_service: T) -> Router {
+  let state = AppState {
+    assignment_service: Arc::new(assignment_service),
+  };
+
+  Router::new()
+    .route("/todos/{todo_id}/assignees", put(set_assignees::<T>))
+    .route("/todos/{todo_id}/assignees/history", get(get_assignment_history::<T>))
+    .route("/todos/{todo_id}/watchers", get(get_watchers::<T>))
+    .route("/todos/{todo_id}/watchers/me", put(watch_todo::<T>).delete(unwatch_todo::<T>))
+    .route("/me/work", get(get_my_work::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetAssigneesRequest {
+  /// 担当者の一覧（空にすると全員の担当を解除する）
+  user_ids: Vec<Uuid>,
+}
+
+impl Validate for SetAssigneesRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.max_items("user_ids", self.user_ids.len(), MAX_ASSIGNEES);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct AssignmentEventResponse {
+  id: Uuid,
+  user_id: Uuid,
+  /// assigned / unassigned
+  #[schema(value_type = String, example = "assigned")]
+  action: AssignmentAction,
+  /// 変更したユーザー（認証なしで変更された場合は null）
+  actor_id: Option<Uuid>,
+  created_at: DateTime<Utc>,
+}
+
+impl From<AssignmentEvent> for AssignmentEventResponse {
+  fn from(event: AssignmentEvent) -> Self {
+    Self {
+      id: event.id,
+      user_id: event.user_id,
+      action: event.action,
+      actor_id: event.actor_id,
+      created_at: event.created_at,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct MyWorkResponse {
+  /// 自分が担当している未完了の Todo
+  assigned: Vec<TodoResponse>,
+  /// 担当していないがウォッチしている未完了の Todo
+  watching: Vec<TodoResponse>,
+}
+
+impl From<MyWork> for MyWorkResponse {
+  fn from(work: MyWork) -> Self {
+    Self {
+      assigned: work.assigned.into_iter().map(TodoResponse::from).collect(),
+      watching: work.watching.into_iter().map(TodoResponse::from).collect(),
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    put,
+    path = "/api/todos/{todo_id}/assignees",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    request_body = SetAssigneesRequest,
+    responses(
+        (status = 200, description = "担当者を置き換え（変更は担当履歴に記録される）", body = TodoResponse),
+        (status = 400, description = "組織に所属していないユーザーが含まれている", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn set_assignees<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<SetAssigneesRequest>,
+) -> impl IntoResponse {
+  match state.assignment_service.set_assignees(tenant, todo_id, payload.user_ids, Some(tenant.user_id)).await {
+    Ok(todo) => Json(TodoResponse::from(todo)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/assignees/history",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "担当者の変更履歴を古い順に取得", body = Vec<AssignmentEventResponse>),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn get_assignment_history<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.assignment_service.get_assignment_history(tenant, todo_id).await {
+    Ok(events) => {
+      let response: Vec<AssignmentEventResponse> = events.into_iter().map(AssignmentEventResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/watchers",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "ウォッチしているユーザーを取得", body = Vec<UserSummaryResponse>),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn get_watchers<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.assignment_service.get_watchers(tenant, todo_id).await {
+    Ok(watchers) => {
+      let response: Vec<UserSummaryResponse> = watchers.into_iter().map(UserSummaryResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/todos/{todo_id}/watchers/me",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 204, description = "Todoをウォッチ"),
+        (status = 400, description = "ユーザーが存在しない", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn watch_todo<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  Path(todo_id): Path<Uuid>,
+  tenant: Tenant,
+) -> impl IntoResponse {
+  match state.assignment_service.watch_todo(tenant, todo_id, tenant.user_id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{todo_id}/watchers/me",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 204, description = "Todoのウォッチを解除"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn unwatch_todo<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  Path(todo_id): Path<Uuid>,
+  tenant: Tenant,
+) -> impl IntoResponse {
+  match state.assignment_service.unwatch_todo(tenant, todo_id, tenant.user_id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/work",
+    responses(
+        (status = 200, description = "自分が担当・ウォッチしている未完了のTodoを取得", body = MyWorkResponse),
+        (status = 400, description = "ユーザーが存在しない", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "assignments"
+)]
+pub async fn get_my_work<T: AssignmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+) -> impl IntoResponse {
+  match state.assignment_service.get_my_work(tenant).await {
+    Ok(work) => Json(MyWorkResponse::from(work)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,462 @@
+use axum::{
+    body::Body,
+    extract::{multipart::MultipartError, DefaultBodyLimit, Multipart, Path, State},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use http::{header, HeaderMap, HeaderValue, StatusCode};
+use bytes::BytesMut;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::error::AppError;
+use crate::presentation::problem::Problem;
+use crate::domain::models::attachment::{Attachment, AttachmentOwner};
+use crate::domain::repositories::blob_store::ByteRange;
+use crate::domain::models::organization::Tenant;
+use crate::usecase::attachment_usecase::AttachmentService;
+
+// multipart の境界やヘッダの分として、本体の上限に上乗せする
+pub const MULTIPART_OVERHEAD: usize = 64 * 1024;
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+
+#[derive(Clone)]
+pub struct AppState<T: AttachmentService> {
+  pub attachment_service: Arc<T>,
+  pub max_upload_bytes: usize,
+}
+
+pub fn create_attachment_router<T: AttachmentService + Send + Sync + 'static + Clone>(
+  attachment_service: T,
+  max_upload_bytes: usize,
+) -> Router {
+  let state = AppState {
+    attachment_service: Arc::new(attachment_service),
+    max_upload_bytes,
+  };
+
+  Router::new()
+    .route("/todos/{todo_id}/attachments", get(get_todo_attachments::<T>).post(upload_todo_attachment::<T>))
+    .route("/todos/{todo_id}/attachments/{id}", get(download_todo_attachment::<T>)
+      .delete(delete_todo_attachment::<T>))
+    .route("/invoices/{invoice_id}/attachments", get(get_invoice_attachments::<T>).post(upload_invoice_attachment::<T>))
+    .route("/invoices/{invoice_id}/attachments/{id}", get(download_invoice_attachment::<T>)
+      .delete(delete_invoice_attachment::<T>))
+    .layer(DefaultBodyLimit::max(max_upload_bytes + MULTIPART_OVERHEAD))
+    .with_state(state)
+}
+
+/// `file` フィールドにファイルを 1 つ指定する
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct UploadAttachmentForm {
+  #[schema(value_type = String, format = Binary)]
+  file: Vec<u8>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct AttachmentResponse {
+  id: Uuid,
+  file_name: String,
+  /// アップロードされた内容から判定した Content-Type
+  content_type: String,
+  size: i64,
+  created_at: DateTime<Utc>,
+}
+
+impl From<Attachment> for AttachmentResponse {
+  fn from(attachment: Attachment) -> Self {
+    Self {
+      id: attachment.id,
+      file_name: attachment.file_name,
+      content_type: attachment.content_type,
+      size: attachment.size,
+      created_at: attachment.created_at,
+    }
+  }
+}
+
+enum RangeRequest {
+  Full,
+  Partial(ByteRange),
+  Unsatisfiable,
+}
+
+// 単一範囲の `Range: bytes=...` を解釈する。複数範囲や解釈できない指定は無視して全体を返す
+fn parse_range(headers: &HeaderMap, size: u64) -> RangeRequest {
+  let Some(spec) = headers
+    .get(header::RANGE)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.trim().strip_prefix("bytes="))
+  else {
+    return RangeRequest::Full;
+  };
+  if spec.contains(',') {
+    return RangeRequest::Full;
+  }
+  let Some((start, end)) = spec.trim().split_once('-') else {
+    return RangeRequest::Full;
+  };
+
+  if start.is_empty() {
+    // 末尾から n バイト
+    return match end.parse::<u64>() {
+      Ok(0) => RangeRequest::Unsatisfiable,
+      Ok(_) if size == 0 => RangeRequest::Unsatisfiable,
+      Ok(suffix) => RangeRequest::Partial(ByteRange { start: size.saturating_sub(suffix), end: size - 1 }),
+      Err(_) => RangeRequest::Full,
+    };
+  }
+
+  let Ok(start) = start.parse::<u64>() else {
+    return RangeRequest::Full;
+  };
+  let end = match end {
+    "" => None,
+    end => match end.parse::<u64>() {
+      Ok(end) if end >= start => Some(end),
+      _ => return RangeRequest::Full,
+    },
+  };
+  if start >= size {
+    return RangeRequest::Unsatisfiable;
+  }
+  RangeRequest::Partial(ByteRange { start, end: end.map_or(size - 1, |end| end.min(size - 1)) })
+}
+
+// パス区切りと制御文字を取り除き、長すぎる名前は切り詰める
+fn sanitize_file_name(file_name: Option<&str>) -> String {
+  let name = file_name
+    .unwrap_or_default()
+    .rsplit(['/', '\\'])
+    .next()
+    .unwrap_or_default();
+  let name: String = name.chars().filter(|c| !c.is_control()).take(MAX_FILE_NAME_LENGTH).collect();
+  let name = name.trim();
+  if name.is_empty() || name == "." || name == ".." {
+    "file".to_string()
+  } else {
+    name.to_string()
+  }
+}
+
+// RFC 6266。ASCII 以外の名前は filename* で渡し、filename には置き換えた名前を入れる
+fn content_disposition(file_name: &str) -> HeaderValue {
+  let fallback: String = file_name
+    .chars()
+    .map(|c| if c.is_ascii_graphic() && c != '"' && c != '\\' || c == ' ' { c } else { '_' })
+    .collect();
+  let encoded: String = file_name
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+      b => format!("%{:02X}", b),
+    })
+    .collect();
+  HeaderValue::from_str(&format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", fallback, encoded))
+    .unwrap_or_else(|_| HeaderValue::from_static("attachment"))
+}
+
+// 本体が大きすぎる場合以外は、multipart の形式の誤りとして扱う
+fn multipart_error(err: MultipartError) -> AppError {
+  if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+    AppError::PayloadTooLarge(err.body_text())
+  } else {
+    AppError::BadRequest(err.body_text())
+  }
+}
+
+
+async fn list_attachments<T: AttachmentService>(state: AppState<T>, tenant: Tenant, owner: AttachmentOwner) -> Response {
+  match state.attachment_service.get_attachments(tenant, owner).await {
+    Ok(attachments) => {
+      let response: Vec<AttachmentResponse> = attachments.into_iter().map(AttachmentResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+// `file` フィールドを上限まで読み込んで保存する。上限を超えた時点で読むのをやめる
+async fn upload_attachment<T: AttachmentService>(state: AppState<T>, tenant: Tenant, owner: AttachmentOwner, mut multipart: Multipart) -> Response {
+  loop {
+    let mut field = match multipart.next_field().await {
+      Ok(Some(field)) => field,
+      Ok(None) => return AppError::BadRequest("Missing file field".to_string()).into_response(),
+      Err(err) => return multipart_error(err).into_response(),
+    };
+    if field.name() != Some("file") {
+      continue;
+    }
+
+    let file_name = sanitize_file_name(field.file_name());
+    let mut data = BytesMut::new();
+    loop {
+      match field.chunk().await {
+        Ok(Some(chunk)) => {
+          if data.len() + chunk.len() > state.max_upload_bytes {
+            let message = format!("File exceeds the maximum size of {} bytes", state.max_upload_bytes);
+            return AppError::PayloadTooLarge(message).into_response();
+          }
+          data.extend_from_slice(&chunk);
+        }
+        Ok(None) => break,
+        Err(err) => return multipart_error(err).into_response(),
+      }
+    }
+    if data.is_empty() {
+      return AppError::BadRequest("File must not be empty".to_string()).into_response();
+    }
+
+    return match state.attachment_service.upload_attachment(tenant, owner, file_name, data.freeze()).await {
+      Ok(attachment) => (StatusCode::CREATED, Json(AttachmentResponse::from(attachment))).into_response(),
+      Err(err) => err.into_response(),
+    };
+  }
+}
+
+async fn download_attachment<T: AttachmentService>(state: AppState<T>, tenant: Tenant, owner: AttachmentOwner, id: Uuid, headers: HeaderMap) -> Response {
+  let attachment = match state.attachment_service.get_attachment(tenant, owner, id).await {
+    Ok(attachment) => attachment,
+    Err(err) => return err.into_response(),
+  };
+  let size = attachment.size as u64;
+
+  let range = match parse_range(&headers, size) {
+    RangeRequest::Full => None,
+    RangeRequest::Partial(range) => Some(range),
+    RangeRequest::Unsatisfiable => {
+      return (
+        StatusCode::RANGE_NOT_SATISFIABLE,
+        [(header::CONTENT_RANGE, format!("bytes */{}", size))],
+      )
+        .into_response();
+    }
+  };
+
+  let stream = match state.attachment_service.download_attachment(&attachment, range).await {
+    Ok(stream) => stream,
+    Err(err) => return err.into_response(),
+  };
+
+  let mut response = Response::new(Body::from_stream(stream));
+  let response_headers = response.headers_mut();
+  if let Ok(content_type) = HeaderValue::from_str(&attachment.content_type) {
+    response_headers.insert(header::CONTENT_TYPE, content_type);
+  }
+  // 保存時に判定した Content-Type 以外としてブラウザに解釈させない
+  response_headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+  response_headers.insert(header::CONTENT_DISPOSITION, content_disposition(&attachment.file_name));
+  response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+  match range {
+    Some(range) => {
+      *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+      let response_headers = response.headers_mut();
+      response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(range.len()));
+      if let Ok(content_range) = HeaderValue::from_str(&format!("bytes {}-{}/{}", range.start, range.end, size)) {
+        response_headers.insert(header::CONTENT_RANGE, content_range);
+      }
+    }
+    None => {
+      response.headers_mut().insert(header::CONTENT_LENGTH, HeaderValue::from(size));
+    }
+  }
+  response
+}
+
+async fn remove_attachment<T: AttachmentService>(state: AppState<T>, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Response {
+  match state.attachment_service.delete_attachment(tenant, owner, id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/attachments",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todoの添付ファイル一覧を取得", body = Vec<AttachmentResponse>),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn get_todo_attachments<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+) -> impl IntoResponse {
+  list_attachments(state, tenant, AttachmentOwner::Todo(todo_id)).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/{todo_id}/attachments",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    request_body(content = UploadAttachmentForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Todoにファイルを添付", body = AttachmentResponse),
+        (status = 400, description = "file フィールドがない、または空", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 413, description = "ファイルサイズが上限を超えている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn upload_todo_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+  multipart: Multipart,
+) -> impl IntoResponse {
+  upload_attachment(state, tenant, AttachmentOwner::Todo(todo_id), multipart).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/attachments/{id}",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Attachment ID"),
+        ("Range" = Option<String>, Header, description = "単一範囲のみ対応（例: bytes=0-1023）")
+    ),
+    responses(
+        (status = 200, description = "添付ファイルをダウンロード"),
+        (status = 206, description = "指定範囲をダウンロード"),
+        (status = 404, description = "Todoまたは添付ファイルが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 416, description = "範囲がファイルサイズを超えている"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn download_todo_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+  headers: HeaderMap,
+) -> impl IntoResponse {
+  download_attachment(state, tenant, AttachmentOwner::Todo(todo_id), id, headers).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{todo_id}/attachments/{id}",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 204, description = "添付ファイルを削除"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたは添付ファイルが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn delete_todo_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  remove_attachment(state, tenant, AttachmentOwner::Todo(todo_id), id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/{invoice_id}/attachments",
+    params(("invoice_id" = Uuid, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "請求書の添付ファイル一覧を取得", body = Vec<AttachmentResponse>),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn get_invoice_attachments<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(invoice_id): Path<Uuid>,
+) -> impl IntoResponse {
+  list_attachments(state, tenant, AttachmentOwner::Invoice(invoice_id)).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/invoices/{invoice_id}/attachments",
+    params(("invoice_id" = Uuid, Path, description = "Invoice ID")),
+    request_body(content = UploadAttachmentForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "請求書にファイルを添付", body = AttachmentResponse),
+        (status = 400, description = "file フィールドがない、または空", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 413, description = "ファイルサイズが上限を超えている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn upload_invoice_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(invoice_id): Path<Uuid>,
+  multipart: Multipart,
+) -> impl IntoResponse {
+  upload_attachment(state, tenant, AttachmentOwner::Invoice(invoice_id), multipart).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/{invoice_id}/attachments/{id}",
+    params(
+        ("invoice_id" = Uuid, Path, description = "Invoice ID"),
+        ("id" = Uuid, Path, description = "Attachment ID"),
+        ("Range" = Option<String>, Header, description = "単一範囲のみ対応（例: bytes=0-1023）")
+    ),
+    responses(
+        (status = 200, description = "添付ファイルをダウンロード"),
+        (status = 206, description = "指定範囲をダウンロード"),
+        (status = 404, description = "請求書または添付ファイルが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 416, description = "範囲がファイルサイズを超えている"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn download_invoice_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((invoice_id, id)): Path<(Uuid, Uuid)>,
+  headers: HeaderMap,
+) -> impl IntoResponse {
+  download_attachment(state, tenant, AttachmentOwner::Invoice(invoice_id), id, headers).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/invoices/{invoice_id}/attachments/{id}",
+    params(
+        ("invoice_id" = Uuid, Path, description = "Invoice ID"),
+        ("id" = Uuid, Path, description = "Attachment ID")
+    ),
+    responses(
+        (status = 204, description = "添付ファイルを削除"),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "請求書または添付ファイルが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "attachments"
+)]
+pub async fn delete_invoice_attachment<T: AttachmentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((invoice_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  remove_attachment(state, tenant, AttachmentOwner::Invoice(invoice_id), id).await
+}
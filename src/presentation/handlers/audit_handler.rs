@@ -0,0 +1,165 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::presentation::problem::Problem;
+use crate::domain::models::audit::{AuditAction, AuditContext, AuditEntity, AuditEvent, FieldChange};
+use crate::domain::models::organization::Tenant;
+use crate::presentation::operation::operation_header;
+use crate::usecase::audit_usecase::AuditService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: AuditService> {
+  pub audit_service: Arc<T>,
+}
+
+pub fn create_audit_router<T: AuditService + Send + Sync + 'static + Clone>(audit_service: T) -> Router {
+  let state = AppState {
+    audit_service: Arc::new(audit_service),
+  };
+
+  Router::new()
+    .route("/todos/{id}/history", get(get_todo_history::<T>))
+    .route("/invoices/{id}/history", get(get_invoice_history::<T>))
+    .route("/undo/{operation_id}", post(undo_operation::<T>))
+    .with_state(state)
+}
+
+#[derive(Serialize, ToSchema)]
+struct FieldChangeResponse {
+  /// 変更前の値（作成時は null）
+  #[schema(value_type = Object)]
+  before: serde_json::Value,
+  /// 変更後の値（物理削除時は null）
+  #[schema(value_type = Object)]
+  after: serde_json::Value,
+}
+
+impl From<FieldChange> for FieldChangeResponse {
+  fn from(change: FieldChange) -> Self {
+    Self {
+      before: change.before,
+      after: change.after,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct AuditEventResponse {
+  id: Uuid,
+  /// create / update / delete（ゴミ箱へ移動） / restore / purge（物理削除）
+  #[schema(value_type = String, example = "update")]
+  action: AuditAction,
+  /// 変更したユーザー（認証なしで変更された場合は null）
+  actor_id: Option<Uuid>,
+  /// 同じリクエストで記録されたイベントに共通の ID
+  operation_id: Option<Uuid>,
+  /// 列名ごとの変更前後の値
+  changes: BTreeMap<String, FieldChangeResponse>,
+  created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+  fn from(event: AuditEvent) -> Self {
+    Self {
+      id: event.id,
+      action: event.action,
+      actor_id: event.actor_id,
+      operation_id: event.operation_id,
+      changes: event
+        .changes
+        .0
+        .into_iter()
+        .map(|(field, change)| (field, FieldChangeResponse::from(change)))
+        .collect(),
+      created_at: event.created_at,
+    }
+  }
+}
+
+async fn history_response<T: AuditService>(state: &AppState<T>, tenant: Tenant, entity: AuditEntity, id: Uuid) -> Response {
+  match state.audit_service.get_history(tenant, entity, id).await {
+    Ok(events) => {
+      let response: Vec<AuditEventResponse> = events.into_iter().map(AuditEventResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{id}/history",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todoの変更履歴を古い順に取得", body = Vec<AuditEventResponse>),
+        (status = 404, description = "Todoの履歴が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "history"
+)]
+pub async fn get_todo_history<T: AuditService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  history_response(&state, tenant, AuditEntity::Todo, id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/invoices/{id}/history",
+    params(("id" = Uuid, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "請求書の変更履歴を古い順に取得", body = Vec<AuditEventResponse>),
+        (status = 404, description = "請求書の履歴が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "history"
+)]
+pub async fn get_invoice_history<T: AuditService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  history_response(&state, tenant, AuditEntity::Invoice, id).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/undo/{operation_id}",
+    params(("operation_id" = Uuid, Path, description = "変更系の Todo API が X-Operation-Id ヘッダで返した操作 ID")),
+    responses(
+        (status = 204, description = "操作を取り消した", headers(("x-operation-id" = Uuid, description = "取り消し自体の操作 ID（やり直しに使える）"))),
+        (status = 400, description = "取り消せない種類の操作", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "操作が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "対象のTodoがその後に変更されている", body = Problem, content_type = "application/problem+json"),
+        (status = 410, description = "取り消せる期間を過ぎている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "history"
+)]
+pub async fn undo_operation<T: AuditService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(operation_id): Path<Uuid>,
+  audit: AuditContext,
+) -> impl IntoResponse {
+  match state.audit_service.undo(tenant, operation_id, &audit).await {
+    Ok(()) => (StatusCode::NO_CONTENT, operation_header(&audit)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
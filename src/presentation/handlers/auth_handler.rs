@@ -0,0 +1,207 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::domain::models::auth::{TokenPair, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH};
+use crate::domain::models::user::{EMAIL_MAX_LENGTH, NAME_MAX_LENGTH};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::presentation::handlers::user_handler::UserResponse;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::usecase::auth_usecase::AuthService;
+
+
+pub struct AppState<T: AuthService> {
+  pub auth_service: Arc<T>,
+}
+
+// 認証ミドルウェアと同じ Arc を共有するため、T: Clone は求めない
+impl<T: AuthService> Clone for AppState<T> {
+  fn clone(&self) -> Self {
+    Self { auth_service: self.auth_service.clone() }
+  }
+}
+
+// 認証なしで呼べるエンドポイント
+pub fn create_auth_router<T: AuthService + Send + Sync + 'static>(auth_service: Arc<T>) -> Router {
+  let state = AppState { auth_service };
+
+  Router::new()
+    .route("/auth/register", post(register::<T>))
+    .route("/auth/login", post(login::<T>))
+    .route("/auth/refresh", post(refresh::<T>))
+    .route("/auth/logout", post(logout::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RegisterRequest {
+  name: String,
+  email: String,
+  /// 8 文字以上 128 文字以下
+  password: String,
+}
+
+impl Validate for RegisterRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", self.name.trim(), 1, NAME_MAX_LENGTH);
+    errors.email("email", self.email.trim(), EMAIL_MAX_LENGTH);
+    errors.length("password", &self.password, PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct LoginRequest {
+  email: String,
+  password: String,
+}
+
+impl Validate for LoginRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("email", self.email.trim(), 1, EMAIL_MAX_LENGTH);
+    errors.length("password", &self.password, 1, PASSWORD_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RefreshTokenRequest {
+  refresh_token: String,
+}
+
+impl Validate for RefreshTokenRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("refresh_token", &self.refresh_token, 1, 256);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TokenResponse {
+  /// Authorization: Bearer ヘッダに指定する JWT
+  access_token: String,
+  #[schema(example = "Bearer")]
+  token_type: &'static str,
+  /// アクセストークンの有効期間（秒）
+  expires_in: i64,
+  /// 一度使うと無効になる。新しいリフレッシュトークンはレスポンスで受け取る
+  refresh_token: String,
+  refresh_token_expires_at: DateTime<Utc>,
+}
+
+impl From<TokenPair> for TokenResponse {
+  fn from(pair: TokenPair) -> Self {
+    Self {
+      access_token: pair.access_token,
+      token_type: "Bearer",
+      expires_in: (pair.access_token_expires_at - Utc::now()).num_seconds().max(0),
+      refresh_token: pair.refresh_token,
+      refresh_token_expires_at: pair.refresh_token_expires_at,
+    }
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "ユーザーを登録", body = UserResponse),
+        (status = 409, description = "メールアドレスが使用済み", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn register<T: AuthService>(
+  State(state): State<AppState<T>>,
+  ValidatedJson(payload): ValidatedJson<RegisterRequest>,
+) -> impl IntoResponse {
+  let name = payload.name.trim().to_string();
+  let email = payload.email.trim().to_string();
+  match state.auth_service.register(name, email, payload.password).await {
+    Ok(user) => (StatusCode::CREATED, Json(UserResponse::from(user))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "アクセストークンとリフレッシュトークンを発行", body = TokenResponse),
+        (status = 401, description = "メールアドレスまたはパスワードが違う", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn login<T: AuthService>(
+  State(state): State<AppState<T>>,
+  ValidatedJson(payload): ValidatedJson<LoginRequest>,
+) -> impl IntoResponse {
+  match state.auth_service.login(payload.email.trim(), payload.password).await {
+    Ok(pair) => Json(TokenResponse::from(pair)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "トークンを更新（使ったリフレッシュトークンは無効になる）", body = TokenResponse),
+        (status = 401, description = "リフレッシュトークンが無効または期限切れ", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn refresh<T: AuthService>(
+  State(state): State<AppState<T>>,
+  ValidatedJson(payload): ValidatedJson<RefreshTokenRequest>,
+) -> impl IntoResponse {
+  match state.auth_service.refresh(&payload.refresh_token).await {
+    Ok(pair) => Json(TokenResponse::from(pair)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 204, description = "リフレッシュトークンを無効にする（同じログインから発行したものすべて）"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn logout<T: AuthService>(
+  State(state): State<AppState<T>>,
+  ValidatedJson(payload): ValidatedJson<RefreshTokenRequest>,
+) -> impl IntoResponse {
+  match state.auth_service.logout(&payload.refresh_token).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
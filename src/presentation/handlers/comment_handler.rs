@@ -0,0 +1,280 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::models::organization::Tenant;
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::domain::models::comment::{Comment, CommentRevision, BODY_MAX_LENGTH};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::usecase::comment_usecase::CommentService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: CommentService> {
+  pub comment_service: Arc<T>,
+}
+
+pub fn create_comment_router<T: CommentService + Send + Sync + 'static + Clone>(comment_service: T) -> Router {
+  let state = AppState {
+    comment_service: Arc::new(comment_service),
+  };
+
+  Router::new()
+    .route("/todos/{todo_id}/comments", get(get_comments::<T>).post(create_comment::<T>))
+    .route("/todos/{todo_id}/comments/{id}", get(get_comment::<T>)
+      .put(update_comment::<T>)
+      .delete(delete_comment::<T>))
+    .route("/todos/{todo_id}/comments/{id}/revisions", get(get_comment_revisions::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateCommentRequest {
+  /// Markdown
+  body: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateCommentRequest {
+  /// Markdown
+  body: String,
+}
+
+impl Validate for CreateCommentRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("body", &self.body, 1, BODY_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+impl Validate for UpdateCommentRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("body", &self.body, 1, BODY_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct CommentResponse {
+  id: Uuid,
+  todo_id: Uuid,
+  /// 投稿したユーザー。退会したユーザーと、投稿者を記録する前のコメントは null
+  author_id: Option<Uuid>,
+  /// 投稿時点のユーザー名
+  author: String,
+  /// Markdown
+  body: String,
+  created_at: DateTime<Utc>,
+  /// 一度も編集されていない場合は null
+  edited_at: Option<DateTime<Utc>>,
+}
+
+impl From<Comment> for CommentResponse {
+  fn from(comment: Comment) -> Self {
+    Self {
+      id: comment.id,
+      todo_id: comment.todo_id,
+      author_id: comment.author_id,
+      author: comment.author,
+      body: comment.body,
+      created_at: comment.created_at,
+      edited_at: comment.edited_at,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct CommentRevisionResponse {
+  id: Uuid,
+  /// 編集前の本文
+  body: String,
+  /// この本文が書かれた日時
+  created_at: DateTime<Utc>,
+}
+
+impl From<CommentRevision> for CommentRevisionResponse {
+  fn from(revision: CommentRevision) -> Self {
+    Self {
+      id: revision.id,
+      body: revision.body,
+      created_at: revision.created_at,
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/comments",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todoのコメントを古い順に取得", body = Vec<CommentResponse>),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn get_comments<T: CommentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.comment_service.get_comments(tenant, todo_id).await {
+    Ok(comments) => {
+      let response: Vec<CommentResponse> = comments.into_iter().map(CommentResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/comments/{id}",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "コメントを取得", body = CommentResponse),
+        (status = 404, description = "Todoまたはコメントが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn get_comment<T: CommentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.comment_service.get_comment(tenant, todo_id, id).await {
+    Ok(comment) => Json(CommentResponse::from(comment)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/{todo_id}/comments/{id}/revisions",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "コメントの編集履歴を古い順に取得", body = Vec<CommentRevisionResponse>),
+        (status = 404, description = "Todoまたはコメントが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn get_comment_revisions<T: CommentService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.comment_service.get_comment_revisions(tenant, todo_id, id).await {
+    Ok(revisions) => {
+      let response: Vec<CommentRevisionResponse> = revisions.into_iter().map(CommentRevisionResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/{todo_id}/comments",
+    params(("todo_id" = Uuid, Path, description = "Todo ID")),
+    request_body = CreateCommentRequest,
+    responses(
+        (status = 201, description = "ログイン中のユーザーを投稿者としてコメントを作成", body = CommentResponse),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn create_comment<T: CommentService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  tenant: Tenant,
+  Path(todo_id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<CreateCommentRequest>,
+) -> impl IntoResponse {
+  match state.comment_service.create_comment(tenant, user_id, todo_id, payload.body).await {
+    Ok(comment) => (StatusCode::CREATED, Json(CommentResponse::from(comment))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/todos/{todo_id}/comments/{id}",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    request_body = UpdateCommentRequest,
+    responses(
+        (status = 200, description = "コメントを編集（編集前の本文は履歴に残る）", body = CommentResponse),
+        (status = 403, description = "viewer は Todo を変更できない。他のユーザーのコメントは owner・admin だけが編集できる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたはコメントが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn update_comment<T: CommentService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+  ValidatedJson(payload): ValidatedJson<UpdateCommentRequest>,
+) -> impl IntoResponse {
+  match state.comment_service.update_comment(tenant, user_id, todo_id, id, payload.body).await {
+    Ok(comment) => Json(CommentResponse::from(comment)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/todos/{todo_id}/comments/{id}",
+    params(
+        ("todo_id" = Uuid, Path, description = "Todo ID"),
+        ("id" = Uuid, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 204, description = "コメントを削除（編集履歴も削除される）"),
+        (status = 403, description = "viewer は Todo を変更できない。他のユーザーのコメントは owner・admin だけが削除できる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたはコメントが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "comments"
+)]
+pub async fn delete_comment<T: CommentService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  tenant: Tenant,
+  Path((todo_id, id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.comment_service.delete_comment(tenant, user_id, todo_id, id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
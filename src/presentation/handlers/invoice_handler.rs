@@ -1,17 +1,27 @@
 use axum::{
-    extract::{Path, State},
+    extract::{OriginalUri, Path, Query, State},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
-use http::StatusCode;
+use http::{HeaderMap, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 use utoipa::ToSchema;
 
+use crate::domain::error::AppError;
+use crate::presentation::problem::Problem;
+use crate::domain::models::organization::Tenant;
+use crate::presentation::etag::{etag_header, if_match_version, if_none_match};
+use crate::presentation::merge_patch::{nullable, reject_null};
+use crate::presentation::pagination::{page_response, PageQuery, PageResponse};
+use crate::presentation::validation::ValidatedJson;
 use crate::usecase::invoice_usecase::InvoiceService;
-use crate::domain::models::invoice::Invoice;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::invoice::{validate_amount, Invoice, InvoicePatch};
+use crate::domain::models::page::PageRequest;
+use crate::domain::validation::{Validate, ValidationErrors};
 
 
 #[derive(Clone)]
@@ -28,23 +38,76 @@ pub fn create_invoice_router<T: InvoiceService + Send + Sync + 'static + Clone>(
     .route("/invoices", get(get_all_invoices::<T>).post(create_invoice::<T>))
     .route("/invoices/{id}", get(get_invoice_by_id::<T>)
       .put(update_invoice::<T>)
+      .patch(patch_invoice::<T>)
       .delete(delete_invoice::<T>))
+    .route("/invoices/{id}/restore", post(restore_invoice::<T>))
     .with_state(state)
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct CreateInvoiceRequest {
+  /// 0 以上
   amount: i32,
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct UpdateInvoiceRequest {
+  /// 0 以上
   amount: i32,
   paid: bool,
 }
 
+/// JSON Merge Patch（RFC 7396）。含めた項目だけを変更する
+#[derive(Deserialize, ToSchema)]
+pub struct PatchInvoiceRequest {
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = i32, required = false)]
+  amount: Option<Option<i32>>,
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = bool, required = false)]
+  paid: Option<Option<bool>>,
+}
+
+impl PatchInvoiceRequest {
+  // null にできない項目の null は validate で弾いている
+  fn into_patch(self) -> InvoicePatch {
+    InvoicePatch {
+      amount: self.amount.flatten(),
+      paid: self.paid.flatten(),
+    }
+  }
+}
+
+impl Validate for CreateInvoiceRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    validate_amount(&mut errors, self.amount);
+    errors.into_result()
+  }
+}
+
+impl Validate for UpdateInvoiceRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    validate_amount(&mut errors, self.amount);
+    errors.into_result()
+  }
+}
+
+impl Validate for PatchInvoiceRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    reject_null(&mut errors, &self.amount, "amount");
+    reject_null(&mut errors, &self.paid, "paid");
+    if let Some(Some(amount)) = self.amount {
+      validate_amount(&mut errors, amount);
+    }
+    errors.into_result()
+  }
+}
+
 #[derive(Serialize, ToSchema)]
-struct InvoiceResponse {
+pub struct InvoiceResponse {
   id: Uuid,
   amount: i32,
   paid: bool,
@@ -64,108 +127,199 @@ impl From<Invoice> for InvoiceResponse {
 #[utoipa::path(
     get,
     path = "/api/invoices",
+    params(PageQuery),
     responses(
-        (status = 200, description = "全請求書を取得", body = Vec<InvoiceResponse>),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "請求書を一覧取得（カーソルページング）", body = PageResponse<InvoiceResponse>),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "invoices"
 )]
 pub async fn get_all_invoices<T: InvoiceService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
+  OriginalUri(uri): OriginalUri,
+  Query(query): Query<PageQuery>,
 ) -> impl IntoResponse {
-  match state.invoice_service.get_all_invoices().await {
-    Ok(invoices) => {
-      let response: Vec<InvoiceResponse> = invoices.into_iter().map(InvoiceResponse::from).collect();
-      Json(response).into_response()
-    }
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch invoices").into_response(),
+  let page = PageRequest::from(&query);
+  match state.invoice_service.get_all_invoices(tenant, page).await {
+    Ok(invoices) => page_response(&uri, invoices.map(InvoiceResponse::from)),
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     get,
     path = "/api/invoices/{id}",
-    params(("id" = Uuid, Path, description = "Invoice ID")),
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID"),
+        ("If-None-Match" = Option<String>, Header, description = "以前に受け取った ETag。一致すれば 304 を返す")
+    ),
     responses(
-        (status = 200, description = "請求書を取得", body = InvoiceResponse),
-        (status = 404, description = "請求書が見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "請求書を取得", body = InvoiceResponse, headers(("etag" = String, description = "現在の版数"))),
+        (status = 304, description = "If-None-Match の ETag から変更されていない"),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "invoices"
 )]
 pub async fn get_invoice_by_id<T: InvoiceService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
+  headers: HeaderMap,
 ) -> impl IntoResponse {
-  match state.invoice_service.get_invoice_by_id(id).await {
-    Ok(Some(invoice)) => Json(InvoiceResponse::from(invoice)).into_response(),
-    Ok(None) => (StatusCode::NOT_FOUND, "Invoice not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch invoice").into_response(),
+  match state.invoice_service.get_invoice_by_id(tenant, id).await {
+    Ok(Some(invoice)) if if_none_match(&headers, invoice.version) => (StatusCode::NOT_MODIFIED, etag_header(invoice.version)).into_response(),
+    Ok(Some(invoice)) => (etag_header(invoice.version), Json(InvoiceResponse::from(invoice))).into_response(),
+    Ok(None) => AppError::NotFound("Invoice not found".to_string()).into_response(),
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     post,
     path = "/api/invoices",
+    params(("Idempotency-Key" = Option<String>, Header, description = "再送時に同じ値を指定すると、最初のレスポンスを返す")),
     request_body = CreateInvoiceRequest,
     responses(
         (status = 201, description = "請求書を作成", body = InvoiceResponse),
-        (status = 500, description = "サーバーエラー")
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "同じ Idempotency-Key のリクエストが処理中", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）、または Idempotency-Key が別の内容のリクエストに使われている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "invoices"
 )]
 pub async fn create_invoice<T: InvoiceService>(
   State(state): State<AppState<T>>,
-  Json(payload): Json<CreateInvoiceRequest>,
+  tenant: Tenant,
+  audit: AuditContext,
+  ValidatedJson(payload): ValidatedJson<CreateInvoiceRequest>,
 ) -> impl IntoResponse {
-  match state.invoice_service.create_invoice(payload.amount).await {
+  match state.invoice_service.create_invoice(tenant, payload.amount, &audit).await {
     Ok(invoice) => (StatusCode::CREATED, Json(InvoiceResponse::from(invoice))).into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create invoice").into_response(),
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     put,
     path = "/api/invoices/{id}",
-    params(("id" = Uuid, Path, description = "Invoice ID")),
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID"),
+        ("If-Match" = Option<String>, Header, description = "GET で受け取った ETag。一致しなければ 412 を返す")
+    ),
     request_body = UpdateInvoiceRequest,
     responses(
-        (status = 200, description = "請求書を更新", body = InvoiceResponse),
-        (status = 404, description = "請求書が見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "請求書を更新", body = InvoiceResponse, headers(("etag" = String, description = "更新後の版数"))),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "If-Match の ETag と現在の版数が一致しない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "invoices"
 )]
 pub async fn update_invoice<T: InvoiceService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
-  Json(payload): Json<UpdateInvoiceRequest>,
+  audit: AuditContext,
+  headers: HeaderMap,
+  ValidatedJson(payload): ValidatedJson<UpdateInvoiceRequest>,
 ) -> impl IntoResponse {
-  match state.invoice_service.update_invoice(id, payload.amount, payload.paid).await {
-    Ok(invoice) => Json(InvoiceResponse::from(invoice)).into_response(),
-    Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Invoice not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update invoice").into_response(),
+  let Ok(expected_version) = if_match_version(&headers) else {
+    return AppError::PreconditionFailed("If-Match does not match the current version".to_string()).into_response();
+  };
+
+  match state.invoice_service.update_invoice(tenant, id, payload.amount, payload.paid, expected_version, &audit).await {
+    Ok(invoice) => (etag_header(invoice.version), Json(InvoiceResponse::from(invoice))).into_response(),
+    Err(err) => err.into_response(),
   }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/invoices/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Invoice ID"),
+        ("If-Match" = Option<String>, Header, description = "GET で受け取った ETag。一致しなければ 412 を返す")
+    ),
+    request_body(content = PatchInvoiceRequest, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "請求書を部分更新", body = InvoiceResponse, headers(("etag" = String, description = "更新後の版数"))),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "If-Match の ETag と現在の版数が一致しない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "invoices"
+)]
+pub async fn patch_invoice<T: InvoiceService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+  headers: HeaderMap,
+  ValidatedJson(payload): ValidatedJson<PatchInvoiceRequest>,
+) -> impl IntoResponse {
+  let Ok(expected_version) = if_match_version(&headers) else {
+    return AppError::PreconditionFailed("If-Match does not match the current version".to_string()).into_response();
+  };
+  let patch = payload.into_patch();
+
+  match state.invoice_service.patch_invoice(tenant, id, patch, expected_version, &audit).await {
+    Ok(invoice) => (etag_header(invoice.version), Json(InvoiceResponse::from(invoice))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+
 #[utoipa::path(
     delete,
     path = "/api/invoices/{id}",
     params(("id" = Uuid, Path, description = "Invoice ID")),
     responses(
-        (status = 204, description = "請求書を削除"),
-        (status = 404, description = "請求書が見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 204, description = "請求書をゴミ箱に移動"),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "invoices"
 )]
 pub async fn delete_invoice<T: InvoiceService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
+  audit: AuditContext,
 ) -> impl IntoResponse {
-  match state.invoice_service.delete_invoice(id).await {
+  match state.invoice_service.delete_invoice(tenant, id, &audit).await {
     Ok(_) => StatusCode::NO_CONTENT.into_response(),
-    Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Invoice not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete invoice").into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/invoices/{id}/restore",
+    params(("id" = Uuid, Path, description = "Invoice ID")),
+    responses(
+        (status = 200, description = "請求書をゴミ箱から戻す", body = InvoiceResponse),
+        (status = 403, description = "請求書の発行・取り消しは owner・accountant だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "ゴミ箱に請求書が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "invoices"
+)]
+pub async fn restore_invoice<T: InvoiceService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+) -> impl IntoResponse {
+  match state.invoice_service.restore_invoice(tenant, id, &audit).await {
+    Ok(invoice) => Json(InvoiceResponse::from(invoice)).into_response(),
+    Err(err) => err.into_response(),
   }
 }
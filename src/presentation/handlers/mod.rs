@@ -1,2 +1,15 @@
 pub mod todo_handler;
-pub mod invoice_handler;
\ No newline at end of file
+pub mod invoice_handler;
+pub mod api_key_handler;
+pub mod assignment_handler;
+pub mod attachment_handler;
+pub mod audit_handler;
+pub mod auth_handler;
+pub mod comment_handler;
+pub mod oidc_handler;
+pub mod organization_handler;
+pub mod project_handler;
+pub mod saved_filter_handler;
+pub mod share_handler;
+pub mod trash_handler;
+pub mod user_handler;
\ No newline at end of file
@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use http::{header, HeaderMap};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::error::AppError;
+use crate::domain::models::oidc::LOGIN_STATE_TTL;
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::handlers::auth_handler::TokenResponse;
+use crate::presentation::problem::Problem;
+use crate::usecase::auth_usecase::AuthService;
+use crate::usecase::oidc_usecase::OidcService;
+
+
+pub struct AppState<T: OidcService, A: AuthService> {
+  pub oidc_service: T,
+  pub auth_service: Arc<A>,
+}
+
+// 認証ミドルウェアと同じ Arc を共有するため、A: Clone は求めない
+impl<T: OidcService + Clone, A: AuthService> Clone for AppState<T, A> {
+  fn clone(&self) -> Self {
+    Self { oidc_service: self.oidc_service.clone(), auth_service: self.auth_service.clone() }
+  }
+}
+
+// 対応付け以外は認証なしで呼べるエンドポイント。OIDC_ISSUER_URL を設定した場合だけ有効になる
+pub fn create_oidc_router<T, A>(oidc_service: T, auth_service: Arc<A>) -> Router
+where
+  T: OidcService + Clone + Send + Sync + 'static,
+  A: AuthService + Send + Sync + 'static,
+{
+  let state = AppState { oidc_service, auth_service };
+
+  Router::new()
+    .route("/auth/oidc/login", get(begin_oidc_login::<T, A>))
+    .route("/auth/oidc/callback", get(complete_oidc_login::<T, A>))
+    .route("/auth/oidc/link", post(begin_oidc_link::<T, A>))
+    .with_state(state)
+}
+
+// ログインを始めたブラウザに state を覚えさせるクッキー。コールバックだけに送られるようにする。
+// IdP からのリダイレクト（トップレベルの GET）でも送られるよう SameSite=Lax にする
+const STATE_COOKIE: &str = "oidc_state";
+const STATE_COOKIE_PATH: &str = "/api/auth/oidc";
+
+fn state_cookie(value: &str, max_age: i64) -> String {
+  format!("{}={}; Path={}; Max-Age={}; HttpOnly; SameSite=Lax", STATE_COOKIE, value, STATE_COOKIE_PATH, max_age)
+}
+
+fn read_state_cookie(headers: &HeaderMap) -> Option<&str> {
+  headers
+    .get_all(header::COOKIE)
+    .iter()
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(';'))
+    .filter_map(|pair| pair.trim().split_once('='))
+    .find(|(name, _)| *name == STATE_COOKIE)
+    .map(|(_, value)| value)
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct CallbackQuery {
+  /// 認可コード
+  code: Option<String>,
+  /// ログインの開始時に IdP に渡した値
+  state: Option<String>,
+  /// IdP がログインを拒否した場合のエラーコード
+  error: Option<String>,
+  error_description: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/login",
+    responses(
+        (status = 303, description = "IdP の認可エンドポイントにリダイレクト（認可コードフロー + PKCE）。state を oidc_state クッキーに入れる"),
+        (status = 404, description = "OpenID Connect が設定されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn begin_oidc_login<T: OidcService, A: AuthService>(
+  State(state): State<AppState<T, A>>,
+) -> impl IntoResponse {
+  match state.oidc_service.begin_login(None).await {
+    Ok(redirect) => (
+      [(header::SET_COOKIE, state_cookie(&redirect.state, LOGIN_STATE_TTL.num_seconds()))],
+      Redirect::to(&redirect.authorization_url),
+    )
+      .into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OidcLinkResponse {
+  /// ブラウザで開く IdP の認可エンドポイントの URL
+  authorization_url: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/oidc/link",
+    responses(
+        (status = 200, description = "ログイン中のユーザーに IdP のアカウントを対応付ける。返した URL で IdP にログインすると、コールバックで対応付けてトークンを発行する。state を oidc_state クッキーに入れる", body = OidcLinkResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API キーでは呼び出せない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "OpenID Connect が設定されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "auth"
+)]
+pub async fn begin_oidc_link<T: OidcService, A: AuthService>(
+  State(state): State<AppState<T, A>>,
+  CurrentUser(user_id): CurrentUser,
+) -> impl IntoResponse {
+  match state.oidc_service.begin_login(Some(user_id)).await {
+    Ok(redirect) => (
+      [(header::SET_COOKIE, state_cookie(&redirect.state, LOGIN_STATE_TTL.num_seconds()))],
+      Json(OidcLinkResponse { authorization_url: redirect.authorization_url }),
+    )
+      .into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/callback",
+    params(CallbackQuery),
+    responses(
+        (status = 200, description = "IdP で認証したユーザーにアクセストークンとリフレッシュトークンを発行（初回はユーザーを作成。対応付けの場合は対応付けたユーザー）", body = TokenResponse),
+        (status = 400, description = "code または state がない", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "IdP がログインを拒否した、state が無効かログインを始めたブラウザの oidc_state クッキーと一致しない、または認可コード・ID トークンが無効", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "初回のログインで、IdP がメールアドレスを確認していない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "OpenID Connect が設定されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "初回のログインで、同じメールアドレスのユーザーがいる（ログインして対応付ける）。または対応付けで、IdP のアカウントが他のユーザーに対応付けられている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    security(()),
+    tag = "auth"
+)]
+pub async fn complete_oidc_login<T: OidcService, A: AuthService>(
+  State(state): State<AppState<T, A>>,
+  Query(query): Query<CallbackQuery>,
+  headers: HeaderMap,
+) -> impl IntoResponse {
+  // state は 1 度しか使えないので、結果にかかわらずクッキーを消す
+  let clear_cookie = [(header::SET_COOKIE, state_cookie("", 0))];
+  (clear_cookie, callback_response(state, query, read_state_cookie(&headers)).await)
+}
+
+async fn callback_response<T: OidcService, A: AuthService>(
+  state: AppState<T, A>,
+  query: CallbackQuery,
+  browser_state: Option<&str>,
+) -> Response {
+  if let Some(error) = query.error {
+    let description = query.error_description.map(|description| format!(": {}", description)).unwrap_or_default();
+    return AppError::Unauthorized(format!("The identity provider rejected the login ({}{})", error, description)).into_response();
+  }
+  let (Some(code), Some(login_state)) = (query.code, query.state) else {
+    return AppError::BadRequest("code and state are required".to_string()).into_response();
+  };
+
+  let user_id = match state.oidc_service.complete_login(&code, &login_state, browser_state).await {
+    Ok(user_id) => user_id,
+    Err(err) => return err.into_response(),
+  };
+  match state.auth_service.sign_in(user_id).await {
+    Ok(pair) => Json(TokenResponse::from(pair)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,431 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::models::organization::{Invitation, IssuedInvitation, Member, MembershipRole, UserOrganization, NAME_MAX_LENGTH};
+use crate::domain::models::user::EMAIL_MAX_LENGTH;
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::idempotency::ConfidentialResponse;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::usecase::organization_usecase::OrganizationService;
+
+
+pub struct AppState<T: OrganizationService> {
+  pub organization_service: Arc<T>,
+}
+
+// テナント解決のミドルウェアと同じ Arc を共有するため、T: Clone は求めない
+impl<T: OrganizationService> Clone for AppState<T> {
+  fn clone(&self) -> Self {
+    Self { organization_service: self.organization_service.clone() }
+  }
+}
+
+pub fn create_organization_router<T: OrganizationService + Send + Sync + 'static>(organization_service: Arc<T>) -> Router {
+  let state = AppState { organization_service };
+
+  Router::new()
+    .route("/organizations", get(get_organizations::<T>).post(create_organization::<T>))
+    .route("/organizations/{id}/members", get(get_members::<T>))
+    .route("/organizations/{id}/members/{user_id}", put(change_member_role::<T>).delete(remove_member::<T>))
+    .route("/organizations/{id}/invitations", get(get_invitations::<T>).post(create_invitation::<T>))
+    .route("/organizations/{id}/invitations/{invitation_id}", delete(revoke_invitation::<T>))
+    .route("/invitations/accept", post(accept_invitation::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct OrganizationRequest {
+  name: String,
+}
+
+impl Validate for OrganizationRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", self.name.trim(), 1, NAME_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InvitationRequest {
+  email: String,
+  /// admin / accountant / member / viewer（省略時は member）
+  #[schema(value_type = Option<String>, example = "member")]
+  role: Option<MembershipRole>,
+}
+
+impl Validate for InvitationRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.email("email", self.email.trim(), EMAIL_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct MemberRoleRequest {
+  /// owner / admin / accountant / member / viewer
+  #[schema(value_type = String, example = "admin")]
+  role: MembershipRole,
+}
+
+// 役割の値は読み込みの時点で検証される
+impl Validate for MemberRoleRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    Ok(())
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AcceptInvitationRequest {
+  token: String,
+}
+
+impl Validate for AcceptInvitationRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("token", &self.token, 1, 256);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationResponse {
+  id: Uuid,
+  name: String,
+  /// 本人だけが所属する個人用の組織
+  personal: bool,
+  /// リクエストしたユーザーの役割（owner / admin / accountant / member / viewer）
+  #[schema(value_type = String, example = "owner")]
+  role: MembershipRole,
+  created_at: DateTime<Utc>,
+}
+
+impl From<UserOrganization> for OrganizationResponse {
+  fn from(membership: UserOrganization) -> Self {
+    Self {
+      id: membership.organization.id,
+      name: membership.organization.name,
+      personal: membership.organization.personal_user_id.is_some(),
+      role: membership.role,
+      created_at: membership.organization.created_at,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MemberResponse {
+  user_id: Uuid,
+  name: String,
+  email: String,
+  /// owner / admin / accountant / member / viewer
+  #[schema(value_type = String, example = "member")]
+  role: MembershipRole,
+  joined_at: DateTime<Utc>,
+}
+
+impl From<Member> for MemberResponse {
+  fn from(member: Member) -> Self {
+    Self {
+      user_id: member.user_id,
+      name: member.name,
+      email: member.email,
+      role: member.role,
+      joined_at: member.created_at,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvitationResponse {
+  id: Uuid,
+  email: String,
+  /// admin / accountant / member / viewer
+  #[schema(value_type = String, example = "member")]
+  role: MembershipRole,
+  invited_by: Option<Uuid>,
+  expires_at: DateTime<Utc>,
+  created_at: DateTime<Utc>,
+  /// 招待を受け入れる際に指定するトークン。作成時だけ返す
+  #[serde(skip_serializing_if = "Option::is_none")]
+  token: Option<String>,
+}
+
+impl From<Invitation> for InvitationResponse {
+  fn from(invitation: Invitation) -> Self {
+    Self {
+      id: invitation.id,
+      email: invitation.email,
+      role: invitation.role,
+      invited_by: invitation.invited_by,
+      expires_at: invitation.expires_at,
+      created_at: invitation.created_at,
+      token: None,
+    }
+  }
+}
+
+impl From<IssuedInvitation> for InvitationResponse {
+  fn from(issued: IssuedInvitation) -> Self {
+    Self {
+      token: Some(issued.token),
+      ..Self::from(issued.invitation)
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/organizations",
+    responses(
+        (status = 200, description = "所属している組織を取得（個人用の組織が先頭）", body = Vec<OrganizationResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn get_organizations<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+) -> impl IntoResponse {
+  match state.organization_service.get_organizations(user_id).await {
+    Ok(organizations) => {
+      let response: Vec<OrganizationResponse> = organizations.into_iter().map(OrganizationResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/organizations",
+    request_body = OrganizationRequest,
+    responses(
+        (status = 201, description = "組織を作成（作成したユーザーが owner になる）", body = OrganizationResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn create_organization<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  ValidatedJson(payload): ValidatedJson<OrganizationRequest>,
+) -> impl IntoResponse {
+  match state.organization_service.create_organization(user_id, payload.name.trim().to_string()).await {
+    Ok(organization) => (StatusCode::CREATED, Json(OrganizationResponse::from(organization))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{id}/members",
+    params(("id" = Uuid, Path, description = "Organization ID")),
+    responses(
+        (status = 200, description = "組織のメンバーを取得", body = Vec<MemberResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織が見つからない（所属していない場合を含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn get_members<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.organization_service.get_members(user_id, id).await {
+    Ok(members) => {
+      let response: Vec<MemberResponse> = members.into_iter().map(MemberResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/organizations/{id}/members/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "Organization ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = MemberRoleRequest,
+    responses(
+        (status = 200, description = "メンバーの役割を変更", body = MemberResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "owner・admin 以外が変更しようとした、または owner 以外が owner にする・owner を変えようとした", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織またはメンバーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "最後の owner の役割は変えられない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn change_member_role<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path((id, member_id)): Path<(Uuid, Uuid)>,
+  ValidatedJson(payload): ValidatedJson<MemberRoleRequest>,
+) -> impl IntoResponse {
+  match state.organization_service.change_member_role(user_id, id, member_id, payload.role).await {
+    Ok(member) => Json(MemberResponse::from(member)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/organizations/{id}/members/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "Organization ID"),
+        ("user_id" = Uuid, Path, description = "User ID（自分を指定すると組織から抜ける）")
+    ),
+    responses(
+        (status = 204, description = "メンバーを組織から外す"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "owner・admin 以外が他のメンバーを、または owner 以外が owner を外そうとした", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織またはメンバーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "最後の owner は外せない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn remove_member<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path((id, member_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.organization_service.remove_member(user_id, id, member_id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{id}/invitations",
+    params(("id" = Uuid, Path, description = "Organization ID")),
+    responses(
+        (status = 200, description = "受け入れられていない招待を取得（新しい順）", body = Vec<InvitationResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "owner・admin ではない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織が見つからない（所属していない場合を含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn get_invitations<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.organization_service.get_invitations(user_id, id).await {
+    Ok(invitations) => {
+      let response: Vec<InvitationResponse> = invitations.into_iter().map(InvitationResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/organizations/{id}/invitations",
+    params(("id" = Uuid, Path, description = "Organization ID")),
+    request_body = InvitationRequest,
+    responses(
+        (status = 201, description = "招待を作成（トークンはこのレスポンスでだけ返す）", body = InvitationResponse),
+        (status = 400, description = "owner としては招待できない", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "owner・admin ではない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織が見つからない（所属していない場合を含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "個人用の組織には招待できない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn create_invitation<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<InvitationRequest>,
+) -> impl IntoResponse {
+  let email = payload.email.trim().to_string();
+  let role = payload.role.unwrap_or(MembershipRole::Member);
+  match state.organization_service.create_invitation(user_id, id, email, role).await {
+    // 招待トークンを Idempotency-Key の記録に残さない
+    Ok(issued) => (StatusCode::CREATED, Extension(ConfidentialResponse), Json(InvitationResponse::from(issued))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/organizations/{id}/invitations/{invitation_id}",
+    params(
+        ("id" = Uuid, Path, description = "Organization ID"),
+        ("invitation_id" = Uuid, Path, description = "Invitation ID")
+    ),
+    responses(
+        (status = 204, description = "招待を取り消す"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "owner・admin ではない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "組織または受け入れられていない招待が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn revoke_invitation<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path((id, invitation_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.organization_service.revoke_invitation(user_id, id, invitation_id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/invitations/accept",
+    request_body = AcceptInvitationRequest,
+    responses(
+        (status = 200, description = "招待を受け入れて組織に所属する", body = OrganizationResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "招待が見つからない（他のメールアドレス宛ての場合を含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "受け入れ済みの招待", body = Problem, content_type = "application/problem+json"),
+        (status = 410, description = "招待の期限切れ", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "organizations"
+)]
+pub async fn accept_invitation<T: OrganizationService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  ValidatedJson(payload): ValidatedJson<AcceptInvitationRequest>,
+) -> impl IntoResponse {
+  match state.organization_service.accept_invitation(user_id, &payload.token).await {
+    Ok(organization) => Json(OrganizationResponse::from(organization)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,285 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::error::AppError;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::project::{Project, NAME_MAX_LENGTH};
+use crate::domain::models::workflow::{
+  Transition, Workflow, WorkflowStatus, MAX_STATUSES, STATUS_KEY_MAX_LENGTH, STATUS_NAME_MAX_LENGTH,
+};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::usecase::project_usecase::ProjectService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: ProjectService> {
+  pub project_service: Arc<T>,
+}
+
+pub fn create_project_router<T: ProjectService + Send + Sync + 'static + Clone>(project_service: T) -> Router {
+  let state = AppState {
+    project_service: Arc::new(project_service),
+  };
+
+  Router::new()
+    .route("/projects", get(get_all_projects::<T>).post(create_project::<T>))
+    .route("/projects/{id}", get(get_project_by_id::<T>)
+      .put(update_project::<T>)
+      .delete(delete_project::<T>))
+    .with_state(state)
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WorkflowStatusDto {
+  key: String,
+  name: String,
+  /// このステータスの Todo を完了扱いにする
+  #[serde(default)]
+  done: bool,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TransitionDto {
+  from: String,
+  to: String,
+}
+
+/// 先頭のステータスが新規 Todo の初期ステータスになる。transitions が空の場合はすべての遷移を許可する
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct WorkflowDto {
+  statuses: Vec<WorkflowStatusDto>,
+  #[serde(default)]
+  transitions: Vec<TransitionDto>,
+}
+
+impl From<WorkflowStatus> for WorkflowStatusDto {
+  fn from(status: WorkflowStatus) -> Self {
+    Self {
+      key: status.key,
+      name: status.name,
+      done: status.done,
+    }
+  }
+}
+
+impl From<Workflow> for WorkflowDto {
+  fn from(workflow: Workflow) -> Self {
+    Self {
+      statuses: workflow.statuses.into_iter().map(WorkflowStatusDto::from).collect(),
+      transitions: workflow
+        .transitions
+        .into_iter()
+        .map(|t| TransitionDto { from: t.from, to: t.to })
+        .collect(),
+    }
+  }
+}
+
+impl From<WorkflowDto> for Workflow {
+  fn from(dto: WorkflowDto) -> Self {
+    Self {
+      statuses: dto
+        .statuses
+        .into_iter()
+        .map(|s| WorkflowStatus { key: s.key, name: s.name, done: s.done })
+        .collect(),
+      transitions: dto
+        .transitions
+        .into_iter()
+        .map(|t| Transition { from: t.from, to: t.to })
+        .collect(),
+    }
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateProjectRequest {
+  name: String,
+  /// 省略時は Backlog / Todo / In Progress / Review / Done
+  workflow: Option<WorkflowDto>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateProjectRequest {
+  name: String,
+  workflow: WorkflowDto,
+}
+
+// 項目ごとの形式だけを検証する。ステータスの重複や遷移の整合性は Workflow::validate で検証する
+fn validate_workflow(errors: &mut ValidationErrors, workflow: &WorkflowDto) {
+  errors.max_items("workflow.statuses", workflow.statuses.len(), MAX_STATUSES);
+  for (index, status) in workflow.statuses.iter().enumerate() {
+    errors.length(&format!("workflow.statuses[{}].key", index), &status.key, 1, STATUS_KEY_MAX_LENGTH);
+    errors.length(&format!("workflow.statuses[{}].name", index), &status.name, 1, STATUS_NAME_MAX_LENGTH);
+  }
+}
+
+impl Validate for CreateProjectRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", &self.name, 1, NAME_MAX_LENGTH);
+    if let Some(workflow) = &self.workflow {
+      validate_workflow(&mut errors, workflow);
+    }
+    errors.into_result()
+  }
+}
+
+impl Validate for UpdateProjectRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", &self.name, 1, NAME_MAX_LENGTH);
+    validate_workflow(&mut errors, &self.workflow);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct ProjectResponse {
+  id: Uuid,
+  name: String,
+  workflow: WorkflowDto,
+}
+
+impl From<Project> for ProjectResponse {
+  fn from(project: Project) -> Self {
+    Self {
+      id: project.id,
+      name: project.name,
+      workflow: WorkflowDto::from(project.workflow.0),
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    responses(
+        (status = 200, description = "全プロジェクトを取得", body = Vec<ProjectResponse>),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "projects"
+)]
+pub async fn get_all_projects<T: ProjectService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+) -> impl IntoResponse {
+  match state.project_service.get_all_projects(tenant).await {
+    Ok(projects) => {
+      let response: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "プロジェクトを取得", body = ProjectResponse),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "projects"
+)]
+pub async fn get_project_by_id<T: ProjectService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.project_service.get_project_by_id(tenant, id).await {
+    Ok(Some(project)) => Json(ProjectResponse::from(project)).into_response(),
+    Ok(None) => AppError::NotFound("Project not found".to_string()).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    request_body = CreateProjectRequest,
+    responses(
+        (status = 201, description = "プロジェクトを作成", body = ProjectResponse),
+        (status = 400, description = "ワークフローが不正", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "プロジェクトの管理は owner・admin だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "projects"
+)]
+pub async fn create_project<T: ProjectService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  ValidatedJson(payload): ValidatedJson<CreateProjectRequest>,
+) -> impl IntoResponse {
+  match state.project_service.create_project(tenant, payload.name, payload.workflow.map(Workflow::from)).await {
+    Ok(project) => (StatusCode::CREATED, Json(ProjectResponse::from(project))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = UpdateProjectRequest,
+    responses(
+        (status = 200, description = "プロジェクトを更新", body = ProjectResponse),
+        (status = 400, description = "ワークフローが不正", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "プロジェクトの管理は owner・admin だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Todoが使用中のステータスが削除されている", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "projects"
+)]
+pub async fn update_project<T: ProjectService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<UpdateProjectRequest>,
+) -> impl IntoResponse {
+  match state.project_service.update_project(tenant, id, payload.name, Workflow::from(payload.workflow)).await {
+    Ok(project) => Json(ProjectResponse::from(project)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 204, description = "プロジェクトを削除"),
+        (status = 403, description = "プロジェクトの管理は owner・admin だけができる", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "Todoが残っている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "projects"
+)]
+pub async fn delete_project<T: ProjectService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.project_service.delete_project(tenant, id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::error::AppError;
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::domain::models::saved_filter::{SavedFilter, NAME_MAX_LENGTH, QUERY_MAX_LENGTH};
+use crate::domain::query::parser::parse_filter;
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::usecase::saved_filter_usecase::SavedFilterService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: SavedFilterService> {
+  pub saved_filter_service: Arc<T>,
+}
+
+pub fn create_saved_filter_router<T: SavedFilterService + Send + Sync + 'static + Clone>(saved_filter_service: T) -> Router {
+  let state = AppState {
+    saved_filter_service: Arc::new(saved_filter_service),
+  };
+
+  Router::new()
+    .route("/saved-filters", get(get_all_saved_filters::<T>).post(create_saved_filter::<T>))
+    .route("/saved-filters/{id}", get(get_saved_filter_by_id::<T>)
+      .put(update_saved_filter::<T>)
+      .delete(delete_saved_filter::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SavedFilterRequest {
+  name: String,
+  /// `GET /api/todos?filter=` と同じフィルタ式
+  query: String,
+}
+
+impl Validate for SavedFilterRequest {
+  // フィルタ式の構文は位置つきのエラーを返すため、ハンドラで別に検証する
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", &self.name, 1, NAME_MAX_LENGTH);
+    errors.length("query", &self.query, 1, QUERY_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct SavedFilterResponse {
+  id: Uuid,
+  name: String,
+  query: String,
+}
+
+impl From<SavedFilter> for SavedFilterResponse {
+  fn from(saved_filter: SavedFilter) -> Self {
+    Self {
+      id: saved_filter.id,
+      name: saved_filter.name,
+      query: saved_filter.query,
+    }
+  }
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/saved-filters",
+    responses(
+        (status = 200, description = "自分の保存済みフィルタを取得", body = Vec<SavedFilterResponse>),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "saved-filters"
+)]
+pub async fn get_all_saved_filters<T: SavedFilterService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+) -> impl IntoResponse {
+  match state.saved_filter_service.get_all_saved_filters(user_id).await {
+    Ok(saved_filters) => {
+      let response: Vec<SavedFilterResponse> = saved_filters.into_iter().map(SavedFilterResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/saved-filters/{id}",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    responses(
+        (status = 200, description = "保存済みフィルタを取得", body = SavedFilterResponse),
+        (status = 404, description = "保存済みフィルタが見つからない（他のユーザーのフィルタを含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "saved-filters"
+)]
+pub async fn get_saved_filter_by_id<T: SavedFilterService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.saved_filter_service.get_saved_filter_by_id(user_id, id).await {
+    Ok(Some(saved_filter)) => Json(SavedFilterResponse::from(saved_filter)).into_response(),
+    Ok(None) => AppError::NotFound("Saved filter not found".to_string()).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saved-filters",
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 201, description = "保存済みフィルタを作成", body = SavedFilterResponse),
+        (status = 400, description = "フィルタ式が不正", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "saved-filters"
+)]
+pub async fn create_saved_filter<T: SavedFilterService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  ValidatedJson(payload): ValidatedJson<SavedFilterRequest>,
+) -> impl IntoResponse {
+  if let Err(err) = parse_filter(&payload.query) {
+    return AppError::from(err).into_response();
+  }
+
+  match state.saved_filter_service.create_saved_filter(user_id, payload.name, payload.query).await {
+    Ok(saved_filter) => (StatusCode::CREATED, Json(SavedFilterResponse::from(saved_filter))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/saved-filters/{id}",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 200, description = "保存済みフィルタを更新", body = SavedFilterResponse),
+        (status = 400, description = "フィルタ式が不正", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "保存済みフィルタが見つからない（他のユーザーのフィルタを含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "saved-filters"
+)]
+pub async fn update_saved_filter<T: SavedFilterService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<SavedFilterRequest>,
+) -> impl IntoResponse {
+  if let Err(err) = parse_filter(&payload.query) {
+    return AppError::from(err).into_response();
+  }
+
+  match state.saved_filter_service.update_saved_filter(user_id, id, payload.name, payload.query).await {
+    Ok(saved_filter) => Json(SavedFilterResponse::from(saved_filter)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/saved-filters/{id}",
+    params(("id" = Uuid, Path, description = "Saved filter ID")),
+    responses(
+        (status = 204, description = "保存済みフィルタを削除"),
+        (status = 404, description = "保存済みフィルタが見つからない（他のユーザーのフィルタを含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "saved-filters"
+)]
+pub async fn delete_saved_filter<T: SavedFilterService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.saved_filter_service.delete_saved_filter(user_id, id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
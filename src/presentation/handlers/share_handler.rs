@@ -0,0 +1,390 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::share::{Collaborator, IssuedShare, ProjectShare, SharePermission, SharedProject};
+use crate::domain::models::user::EMAIL_MAX_LENGTH;
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::idempotency::ConfidentialResponse;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::usecase::share_usecase::ShareService;
+
+
+pub struct AppState<T: ShareService> {
+  pub share_service: Arc<T>,
+}
+
+// テナント解決のミドルウェアと同じ Arc を共有するため、T: Clone は求めない
+impl<T: ShareService> Clone for AppState<T> {
+  fn clone(&self) -> Self {
+    Self { share_service: self.share_service.clone() }
+  }
+}
+
+// プロジェクトを持つ組織の側で共有を管理するルート。対象の組織を決めた後に置く
+pub fn create_share_router<T: ShareService + Send + Sync + 'static>(share_service: Arc<T>) -> Router {
+  let state = AppState { share_service };
+
+  Router::new()
+    .route("/projects/{id}/shares", get(get_shares::<T>).post(create_share::<T>))
+    .route("/projects/{id}/shares/{share_id}", delete(revoke_share::<T>))
+    .route("/projects/{id}/collaborators", get(get_collaborators::<T>))
+    .route("/projects/{id}/collaborators/{user_id}", delete(remove_collaborator::<T>))
+    .with_state(state)
+}
+
+// 共有されたユーザーの側のルート。組織によらない
+pub fn create_shared_project_router<T: ShareService + Send + Sync + 'static>(share_service: Arc<T>) -> Router {
+  let state = AppState { share_service };
+
+  Router::new()
+    .route("/shared-projects", get(get_shared_projects::<T>))
+    .route("/shared-projects/accept", post(accept_share::<T>))
+    .route("/shared-projects/{project_id}", delete(leave_shared_project::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ShareRequest {
+  /// view / edit（省略時は view）
+  #[schema(value_type = Option<String>, example = "edit")]
+  permission: Option<SharePermission>,
+  /// 招待するユーザーのメールアドレス。省略するとリンクによる共有になる
+  email: Option<String>,
+}
+
+impl Validate for ShareRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if let Some(email) = &self.email {
+      errors.email("email", email.trim(), EMAIL_MAX_LENGTH);
+    }
+    errors.into_result()
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AcceptShareRequest {
+  token: String,
+}
+
+impl Validate for AcceptShareRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("token", &self.token, 1, 256);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ShareResponse {
+  id: Uuid,
+  /// view / edit
+  #[schema(value_type = String, example = "edit")]
+  permission: SharePermission,
+  /// 招待したメールアドレス。リンクによる共有は null
+  email: Option<String>,
+  created_by: Option<Uuid>,
+  /// 招待の有効期限。リンクは取り消すまで有効
+  expires_at: Option<DateTime<Utc>>,
+  created_at: DateTime<Utc>,
+  /// 共有を受け入れる際に指定するトークン。作成時だけ返す
+  #[serde(skip_serializing_if = "Option::is_none")]
+  token: Option<String>,
+}
+
+impl From<ProjectShare> for ShareResponse {
+  fn from(share: ProjectShare) -> Self {
+    Self {
+      id: share.id,
+      permission: share.permission,
+      email: share.email,
+      created_by: share.created_by,
+      expires_at: share.expires_at,
+      created_at: share.created_at,
+      token: None,
+    }
+  }
+}
+
+impl From<IssuedShare> for ShareResponse {
+  fn from(issued: IssuedShare) -> Self {
+    Self {
+      token: Some(issued.token),
+      ..Self::from(issued.share)
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CollaboratorResponse {
+  user_id: Uuid,
+  name: String,
+  email: String,
+  /// view / edit
+  #[schema(value_type = String, example = "view")]
+  permission: SharePermission,
+  joined_at: DateTime<Utc>,
+}
+
+impl From<Collaborator> for CollaboratorResponse {
+  fn from(collaborator: Collaborator) -> Self {
+    Self {
+      user_id: collaborator.user_id,
+      name: collaborator.name,
+      email: collaborator.email,
+      permission: collaborator.permission,
+      joined_at: collaborator.created_at,
+    }
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SharedProjectResponse {
+  /// X-Shared-Project-Id に指定すると、このプロジェクトの Todo を扱える
+  project_id: Uuid,
+  name: String,
+  organization_id: Uuid,
+  organization_name: String,
+  /// view / edit
+  #[schema(value_type = String, example = "edit")]
+  permission: SharePermission,
+  shared_at: DateTime<Utc>,
+}
+
+impl From<SharedProject> for SharedProjectResponse {
+  fn from(shared: SharedProject) -> Self {
+    Self {
+      project_id: shared.project_id,
+      name: shared.name,
+      organization_id: shared.organization_id,
+      organization_name: shared.organization_name,
+      permission: shared.permission,
+      shared_at: shared.shared_at,
+    }
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/shares",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "有効な共有（リンクと、受け入れられていない招待）を取得（新しい順）", body = Vec<ShareResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "共有の管理は owner・admin だけができる（API キーでは呼び出せない）", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn get_shares<T: ShareService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.share_service.get_shares(tenant, id).await {
+    Ok(shares) => {
+      let response: Vec<ShareResponse> = shares.into_iter().map(ShareResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/shares",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = ShareRequest,
+    responses(
+        (status = 201, description = "ユーザーへの招待（7 日間有効）またはリンクを作成（トークンはこのレスポンスでだけ返す）", body = ShareResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "共有の管理は owner・admin だけができる（API キーでは呼び出せない）", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn create_share<T: ShareService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<ShareRequest>,
+) -> impl IntoResponse {
+  let permission = payload.permission.unwrap_or(SharePermission::View);
+  let email = payload.email.map(|email| email.trim().to_string());
+  match state.share_service.create_share(tenant, id, permission, email).await {
+    // 共有トークンを Idempotency-Key の記録に残さない
+    Ok(issued) => (StatusCode::CREATED, Extension(ConfidentialResponse), Json(ShareResponse::from(issued))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/shares/{share_id}",
+    params(
+        ("id" = Uuid, Path, description = "Project ID"),
+        ("share_id" = Uuid, Path, description = "Share ID")
+    ),
+    responses(
+        (status = 204, description = "共有を取り消す（この共有で加わったユーザーも外れる）"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "共有の管理は owner・admin だけができる（API キーでは呼び出せない）", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトまたは共有が見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn revoke_share<T: ShareService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((id, share_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.share_service.revoke_share(tenant, id, share_id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/collaborators",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "共有を受け入れたユーザーを取得", body = Vec<CollaboratorResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "API キーでは呼び出せない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn get_collaborators<T: ShareService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.share_service.get_collaborators(tenant, id).await {
+    Ok(collaborators) => {
+      let response: Vec<CollaboratorResponse> = collaborators.into_iter().map(CollaboratorResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/collaborators/{user_id}",
+    params(
+        ("id" = Uuid, Path, description = "Project ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    responses(
+        (status = 204, description = "ユーザーへの共有をやめる"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "共有の管理は owner・admin だけができる（API キーでは呼び出せない）", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトまたはユーザーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn remove_collaborator<T: ShareService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+  match state.share_service.remove_collaborator(tenant, id, user_id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/shared-projects",
+    responses(
+        (status = 200, description = "自分に共有されているプロジェクトを取得（新しく共有された順）", body = Vec<SharedProjectResponse>),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn get_shared_projects<T: ShareService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+) -> impl IntoResponse {
+  match state.share_service.get_shared_projects(user_id).await {
+    Ok(projects) => {
+      let response: Vec<SharedProjectResponse> = projects.into_iter().map(SharedProjectResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/shared-projects/accept",
+    request_body = AcceptShareRequest,
+    responses(
+        (status = 200, description = "共有を受け入れる（すでに加わっている場合は edit の共有でだけ権限が変わる）", body = SharedProjectResponse),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "共有が見つからない（他のメールアドレス宛ての招待を含む）", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "受け入れ済みの招待", body = Problem, content_type = "application/problem+json"),
+        (status = 410, description = "招待の期限切れ", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn accept_share<T: ShareService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  ValidatedJson(payload): ValidatedJson<AcceptShareRequest>,
+) -> impl IntoResponse {
+  match state.share_service.accept_share(user_id, &payload.token).await {
+    Ok(project) => Json(SharedProjectResponse::from(project)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/shared-projects/{project_id}",
+    params(("project_id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 204, description = "共有されたプロジェクトから抜ける"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "共有されたプロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "shares"
+)]
+pub async fn leave_shared_project<T: ShareService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(project_id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.share_service.leave_shared_project(user_id, project_id).await {
+    Ok(()) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -1,33 +1,65 @@
 use axum::{
-    extract::{Path, State},
+    extract::{OriginalUri, Path, Query, State},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
-use http::StatusCode;
+use http::{HeaderMap, StatusCode};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tracing::error;
 use uuid::Uuid;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::usecase::todo_usecase::TodoService;
-use crate::domain::models::todo::Todo;
+use crate::domain::error::AppError;
+use crate::presentation::problem::Problem;
+use crate::domain::models::organization::Tenant;
+use crate::presentation::etag::{etag_header, if_match_version, if_none_match};
+use crate::presentation::handlers::user_handler::UserSummaryResponse;
+use crate::presentation::merge_patch::{nullable, reject_null};
+use crate::presentation::operation::operation_header;
+use crate::presentation::pagination::{page_response, PageQuery, PageResponse};
+use crate::presentation::validation::ValidatedJson;
+use crate::usecase::todo_usecase::{BulkOperation, MoveAnchor, TodoService};
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::page::PageRequest;
+use crate::domain::models::recurrence::RecurrenceRule;
+use crate::domain::models::search::SearchHit;
+use crate::domain::models::todo::{
+  validate_recurrence, Priority, Todo, TodoDraft, TodoPatch, DESCRIPTION_MAX_LENGTH, MAX_TAGS, TAG_MAX_LENGTH, TITLE_MAX_LENGTH,
+};
+use crate::domain::models::workflow::{BoardColumn, STATUS_KEY_MAX_LENGTH};
+use crate::domain::query::parser::parse_filter;
+use crate::domain::query::{TodoOrder, TodoQuery};
+use crate::domain::validation::{Validate, ValidationErrors};
 
 #[derive(Clone)]
 pub struct AppState<T: TodoService> {
   pub todo_service: Arc<T>,
+  // 一括操作 1 回あたりの操作数の上限
+  pub bulk_max_operations: usize,
 }
 
-pub fn create_todo_router<T: TodoService + Send + Sync + 'static + Clone>(todo_service: T) -> Router {
+pub fn create_todo_router<T: TodoService + Send + Sync + 'static + Clone>(todo_service: T, bulk_max_operations: usize) -> Router {
   let state = AppState {
     todo_service: Arc::new(todo_service),
+    bulk_max_operations,
   };
 
   Router::new()
     .route("/todos", get(get_all_todos::<T>).post(create_todo::<T>))
+    .route("/todos/search", get(search_todos::<T>))
+    .route("/todos/board", get(get_board::<T>))
+    .route("/todos/bulk", post(bulk_update_todos::<T>))
     .route("/todos/{id}", get(get_todo_by_id::<T>)
       .put(update_todo::<T>)
+      .patch(patch_todo::<T>)
       .delete(delete_todo::<T>))
+    .route("/todos/{id}/move", post(move_todo::<T>))
+    .route("/todos/{id}/status", post(change_todo_status::<T>))
+    .route("/todos/{id}/restore", post(restore_todo::<T>))
+    .route("/todos/series/{series_id}", get(get_todo_series::<T>))
     .with_state(state)
 }
 
@@ -35,6 +67,17 @@ pub fn create_todo_router<T: TodoService + Send + Sync + 'static + Clone>(todo_s
 pub struct CreateTodoRequest {
   title: String,
   description: String,
+  project_id: Option<Uuid>,
+  /// ワークフローのステータスキー（省略時はワークフローの初期ステータス）
+  status: Option<String>,
+  /// low / normal / high / urgent（既定 normal）
+  #[schema(value_type = Option<String>, example = "high")]
+  priority: Option<Priority>,
+  #[serde(default)]
+  tags: Vec<String>,
+  due_date: Option<DateTime<Utc>>,
+  /// RFC 5545 RRULE（例: "FREQ=WEEKLY;BYDAY=MO,WE"）
+  recurrence: Option<String>,
 }
 
 
@@ -42,15 +85,140 @@ pub struct CreateTodoRequest {
 pub struct UpdateTodoRequest {
   title: String,
   description: String,
+  project_id: Option<Uuid>,
+  /// ワークフローのステータスキー。省略時は completed の変更を完了（未完了）ステータスへの遷移として扱う
+  status: Option<String>,
   completed: bool,
+  /// low / normal / high / urgent（既定 normal）
+  #[schema(value_type = Option<String>, example = "high")]
+  priority: Option<Priority>,
+  #[serde(default)]
+  tags: Vec<String>,
+  due_date: Option<DateTime<Utc>>,
+  /// RFC 5545 RRULE（例: "FREQ=WEEKLY;BYDAY=MO,WE"）
+  recurrence: Option<String>,
+}
+
+/// JSON Merge Patch（RFC 7396）。含めた項目だけを変更し、null を指定した項目は値を消す
+#[derive(Deserialize, ToSchema)]
+pub struct PatchTodoRequest {
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = String, required = false)]
+  title: Option<Option<String>>,
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = Option<String>)]
+  description: Option<Option<String>>,
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = Option<Uuid>)]
+  project_id: Option<Option<Uuid>>,
+  /// ワークフローのステータスキー。省略時は completed の変更を完了（未完了）ステータスへの遷移として扱う
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = String, required = false)]
+  status: Option<Option<String>>,
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = bool, required = false)]
+  completed: Option<Option<bool>>,
+  /// low / normal / high / urgent
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = String, required = false, example = "high")]
+  priority: Option<Option<Priority>>,
+  /// 配列全体を置き換える
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = Vec<String>, required = false)]
+  tags: Option<Option<Vec<String>>>,
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = Option<DateTime<Utc>>)]
+  due_date: Option<Option<DateTime<Utc>>>,
+  /// RFC 5545 RRULE（例: "FREQ=WEEKLY;BYDAY=MO,WE"）
+  #[serde(default, deserialize_with = "nullable")]
+  #[schema(value_type = Option<String>)]
+  recurrence: Option<Option<String>>,
+}
+
+impl PatchTodoRequest {
+  // null にできない項目の null は validate で弾いている
+  fn into_patch(self) -> TodoPatch {
+    TodoPatch {
+      title: self.title.flatten(),
+      description: self.description,
+      project_id: self.project_id,
+      status: self.status.flatten(),
+      completed: self.completed.flatten(),
+      priority: self.priority.flatten(),
+      tags: self.tags.flatten().map(normalize_tags),
+      due_date: self.due_date,
+      recurrence: self.recurrence,
+      series_id: None,
+    }
+  }
+}
+
+impl Validate for CreateTodoRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    validate_todo_fields(&mut errors, &self.title, &self.description, self.status.as_deref(), &self.tags);
+    validate_recurrence(&mut errors, self.due_date, self.recurrence.as_deref());
+    errors.into_result()
+  }
+}
+
+impl Validate for UpdateTodoRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    validate_todo_fields(&mut errors, &self.title, &self.description, self.status.as_deref(), &self.tags);
+    validate_recurrence(&mut errors, self.due_date, self.recurrence.as_deref());
+    errors.into_result()
+  }
+}
+
+impl Validate for PatchTodoRequest {
+  // 繰り返し設定と期日の組み合わせは、変更後の Todo に対してユースケースで検証する
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    reject_null(&mut errors, &self.title, "title");
+    reject_null(&mut errors, &self.status, "status");
+    reject_null(&mut errors, &self.completed, "completed");
+    reject_null(&mut errors, &self.priority, "priority");
+    reject_null(&mut errors, &self.tags, "tags");
+    if let Some(Some(title)) = &self.title {
+      errors.length("title", title, 1, TITLE_MAX_LENGTH);
+    }
+    if let Some(Some(description)) = &self.description {
+      errors.length("description", description, 0, DESCRIPTION_MAX_LENGTH);
+    }
+    if let Some(Some(status)) = &self.status {
+      errors.length("status", status, 1, STATUS_KEY_MAX_LENGTH);
+    }
+    if let Some(Some(tags)) = &self.tags {
+      validate_request_tags(&mut errors, tags);
+    }
+    if let Some(Some(recurrence)) = &self.recurrence
+      && let Err(err) = recurrence.parse::<RecurrenceRule>()
+    {
+      errors.add("recurrence", "format", err.to_string());
+    }
+    errors.into_result()
+  }
 }
 
 #[derive(Serialize, ToSchema)]
-struct TodoResponse {
+pub struct TodoResponse {
   id: Uuid,
   title: String,
   description: Option<String>,
+  project_id: Option<Uuid>,
+  status: String,
+  /// ステータスが完了扱いかどうか（status から導出）
   completed: bool,
+  #[schema(value_type = String, example = "normal")]
+  priority: Priority,
+  tags: Vec<String>,
+  due_date: Option<DateTime<Utc>>,
+  recurrence: Option<String>,
+  series_id: Option<Uuid>,
+  rank: String,
+  comment_count: i64,
+  assignees: Vec<UserSummaryResponse>,
 }
 
 
@@ -60,96 +228,648 @@ impl From<Todo> for TodoResponse {
       id: todo.id,
       title: todo.title,
       description: todo.description,
+      project_id: todo.project_id,
+      status: todo.status,
       completed: todo.completed,
+      priority: todo.priority,
+      tags: todo.tags,
+      due_date: todo.due_date,
+      recurrence: todo.recurrence,
+      series_id: todo.series_id,
+      rank: todo.rank,
+      comment_count: todo.comment_count,
+      assignees: todo.assignees.0.into_iter().map(UserSummaryResponse::from).collect(),
+    }
+  }
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct TodoListQuery {
+  /// フィルタ式（例: `completed:false AND (tag:work OR priority>=high) AND due<2026-11-01`）
+  filter: Option<String>,
+  /// 並び順（既定 created）
+  #[param(inline)]
+  order: Option<TodoOrderParam>,
+  /// 担当者で絞り込む。`me` は認証したユーザー
+  assignee: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+enum TodoOrderParam {
+  /// 作成順
+  Created,
+  /// 手動で並べ替えた順
+  Manual,
+}
+
+impl From<TodoOrderParam> for TodoOrder {
+  fn from(order: TodoOrderParam) -> Self {
+    match order {
+      TodoOrderParam::Created => TodoOrder::Created,
+      TodoOrderParam::Manual => TodoOrder::Manual,
+    }
+  }
+}
+
+/// `before` と `after` のどちらか一方を指定する
+#[derive(Deserialize, ToSchema)]
+pub struct MoveTodoRequest {
+  /// この Todo の直前に移動する
+  before: Option<Uuid>,
+  /// この Todo の直後に移動する
+  after: Option<Uuid>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ChangeStatusRequest {
+  status: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum BulkOperationRequest {
+  /// ワークフロー上で到達できる完了ステータスにする
+  Complete { id: Uuid },
+  /// ワークフロー上で到達できる未完了ステータスにする
+  Reopen { id: Uuid },
+  /// ゴミ箱に移動する
+  Delete { id: Uuid },
+  /// project_id を null にするとプロジェクトから外す
+  MoveToProject { id: Uuid, project_id: Option<Uuid> },
+  AddTag { id: Uuid, tag: String },
+}
+
+impl From<BulkOperationRequest> for BulkOperation {
+  fn from(request: BulkOperationRequest) -> Self {
+    match request {
+      BulkOperationRequest::Complete { id } => BulkOperation::Complete(id),
+      BulkOperationRequest::Reopen { id } => BulkOperation::Reopen(id),
+      BulkOperationRequest::Delete { id } => BulkOperation::Delete(id),
+      BulkOperationRequest::MoveToProject { id, project_id } => BulkOperation::MoveToProject { id, project_id },
+      BulkOperationRequest::AddTag { id, tag } => BulkOperation::AddTag { id, tag: tag.trim().to_string() },
+    }
+  }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct BulkTodoRequest {
+  /// 先頭から順に実行する。同じ Todo への操作は前の操作の結果に対して行う
+  operations: Vec<BulkOperationRequest>,
+}
+
+impl Validate for BulkTodoRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    for (index, operation) in self.operations.iter().enumerate() {
+      if let BulkOperationRequest::AddTag { tag, .. } = operation {
+        errors.length(&format!("operations[{}].tag", index), tag, 1, TAG_MAX_LENGTH);
+      }
+    }
+    errors.into_result()
+  }
+}
+
+impl MoveTodoRequest {
+  fn anchor(&self) -> Option<MoveAnchor> {
+    match (self.before, self.after) {
+      (Some(before), None) => Some(MoveAnchor::Before(before)),
+      (None, Some(after)) => Some(MoveAnchor::After(after)),
+      _ => None,
+    }
+  }
+}
+
+impl Validate for MoveTodoRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    if self.anchor().is_none() {
+      errors.add("before", "required", "Specify exactly one of before or after");
     }
+    errors.into_result()
   }
 }
 
+impl Validate for ChangeStatusRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("status", &self.status, 1, STATUS_KEY_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+struct BulkItemResponse {
+  id: Uuid,
+  /// 単独の API で実行した場合の HTTP ステータス
+  status: u16,
+  /// 操作後の Todo（削除または失敗した場合は null）
+  todo: Option<TodoResponse>,
+  /// 失敗した場合のエラー
+  error: Option<Problem>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BulkTodoResponse {
+  /// operations と同じ順の結果
+  results: Vec<BulkItemResponse>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct BoardQuery {
+  /// 省略時はプロジェクトに属さない Todo のボード
+  project_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct BoardColumnResponse {
+  status: String,
+  name: String,
+  done: bool,
+  todos: Vec<TodoResponse>,
+}
+
+impl From<BoardColumn> for BoardColumnResponse {
+  fn from(column: BoardColumn) -> Self {
+    Self {
+      status: column.status.key,
+      name: column.status.name,
+      done: column.status.done,
+      todos: column.todos.into_iter().map(TodoResponse::from).collect(),
+    }
+  }
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct SearchTodosQuery {
+  /// 検索語（空白区切りで AND 検索）
+  q: String,
+  /// 最大件数（既定 20、最大 100）
+  limit: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TodoSearchResponse {
+  todo: TodoResponse,
+  rank: f32,
+  /// 一致箇所を <mark> で囲んだ抜粋（HTML エスケープ済み）
+  snippet: Option<String>,
+}
+
+impl From<SearchHit<Todo>> for TodoSearchResponse {
+  fn from(hit: SearchHit<Todo>) -> Self {
+    Self {
+      todo: TodoResponse::from(hit.item),
+      rank: hit.rank,
+      snippet: hit.snippet,
+    }
+  }
+}
+
+// 作成・更新で共通の項目の検証
+fn validate_todo_fields(errors: &mut ValidationErrors, title: &str, description: &str, status: Option<&str>, tags: &[String]) {
+  errors.length("title", title, 1, TITLE_MAX_LENGTH);
+  errors.length("description", description, 0, DESCRIPTION_MAX_LENGTH);
+  if let Some(status) = status {
+    errors.length("status", status, 1, STATUS_KEY_MAX_LENGTH);
+  }
+  validate_request_tags(errors, tags);
+}
+
+// 空のタグは normalize_tags で取り除くため、長さの上限だけを検証する
+fn validate_request_tags(errors: &mut ValidationErrors, tags: &[String]) {
+  errors.max_items("tags", tags.len(), MAX_TAGS);
+  for (index, tag) in tags.iter().enumerate() {
+    errors.length(&format!("tags[{}]", index), tag.trim(), 0, TAG_MAX_LENGTH);
+  }
+}
+
+// タグの前後の空白を除き、空のものと重複を取り除く
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+  let mut normalized: Vec<String> = Vec::new();
+  for tag in tags {
+    let tag = tag.trim().to_string();
+    if !tag.is_empty() && !normalized.contains(&tag) {
+      normalized.push(tag);
+    }
+  }
+  normalized
+}
+
+fn into_draft(
+  title: String,
+  description: String,
+  project_id: Option<Uuid>,
+  priority: Option<Priority>,
+  tags: Vec<String>,
+  due_date: Option<DateTime<Utc>>,
+  recurrence: Option<String>,
+) -> TodoDraft {
+  TodoDraft {
+    title,
+    description,
+    project_id,
+    priority: priority.unwrap_or_default(),
+    tags: normalize_tags(tags),
+    due_date,
+    recurrence,
+  }
+}
 
 #[utoipa::path(
     get,
     path = "/api/todos",
+    params(PageQuery, TodoListQuery),
     responses(
-        (status = 200, description = "全Todoを取得", body = Vec<TodoResponse>),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "Todoを一覧取得（カーソルページング）", body = PageResponse<TodoResponse>),
+        (status = 400, description = "フィルタ式、担当者の指定、または手動の並び順のカーソルが不正", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "認証されていない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "todos"
 )]
 pub async fn get_all_todos<T: TodoService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
+  OriginalUri(uri): OriginalUri,
+  Query(query): Query<PageQuery>,
+  Query(list): Query<TodoListQuery>,
 ) -> impl IntoResponse {
-  match state.todo_service.get_all_todos().await {
-    Ok(todos) => {
-      let response: Vec<TodoResponse> = todos.into_iter().map(TodoResponse::from).collect();
-      Json(response).into_response()
-    }
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch todos").into_response(),
+  let page = PageRequest::from(&query);
+  let filter = match list.filter.as_deref().map(str::trim).filter(|f| !f.is_empty()).map(parse_filter) {
+    Some(Ok(filter)) => Some(filter),
+    Some(Err(err)) => return AppError::from(err).into_response(),
+    None => None,
+  };
+
+  let assignee = match list.assignee.as_deref().map(str::trim) {
+    None | Some("") => None,
+    Some("me") => Some(tenant.user_id),
+    Some(assignee) => match Uuid::parse_str(assignee) {
+      Ok(assignee_id) => Some(assignee_id),
+      Err(_) => return AppError::BadRequest("assignee must be 'me' or a user id".to_string()).into_response(),
+    },
+  };
+
+  let query = TodoQuery {
+    filter,
+    order: list.order.map(TodoOrder::from).unwrap_or_default(),
+    assignee,
+  };
+
+  match state.todo_service.get_all_todos(tenant, &query, page).await {
+    Ok(todos) => page_response(&uri, todos.map(TodoResponse::from)),
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     get,
     path = "/api/todos/{id}",
-    params(("id" = Uuid, Path, description = "Todo ID")),
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-None-Match" = Option<String>, Header, description = "以前に受け取った ETag。一致すれば 304 を返す")
+    ),
     responses(
-        (status = 200, description = "Todoを取得", body = TodoResponse),
-        (status = 404, description = "Todoが見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "Todoを取得", body = TodoResponse, headers(("etag" = String, description = "現在の版数"))),
+        (status = 304, description = "If-None-Match の ETag から変更されていない"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "todos"
 )]
 pub async fn get_todo_by_id<T: TodoService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
+  headers: HeaderMap,
+) -> impl IntoResponse {
+  match state.todo_service.get_todo_by_id(tenant, id).await {
+    Ok(Some(todo)) if if_none_match(&headers, todo.version) => (StatusCode::NOT_MODIFIED, etag_header(todo.version)).into_response(),
+    Ok(Some(todo)) => (etag_header(todo.version), Json(TodoResponse::from(todo))).into_response(),
+    Ok(None) => AppError::NotFound("Todo not found".to_string()).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/search",
+    params(SearchTodosQuery),
+    responses(
+        (status = 200, description = "Todoを全文検索", body = Vec<TodoSearchResponse>),
+        (status = 400, description = "検索語が空", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn search_todos<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Query(params): Query<SearchTodosQuery>,
+) -> impl IntoResponse {
+  if params.q.trim().is_empty() {
+    return AppError::BadRequest("q must not be empty".to_string()).into_response();
+  }
+  let limit = params.limit.unwrap_or(20).clamp(1, 100);
+
+  match state.todo_service.search_todos(tenant, &params.q, limit).await {
+    Ok(hits) => {
+      let response: Vec<TodoSearchResponse> = hits.into_iter().map(TodoSearchResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/board",
+    params(BoardQuery),
+    responses(
+        (status = 200, description = "ステータスごとにまとめたTodoのボードを取得", body = Vec<BoardColumnResponse>),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn get_board<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Query(params): Query<BoardQuery>,
 ) -> impl IntoResponse {
-  match state.todo_service.get_todo_by_id(id).await {
-    Ok(Some(todo)) => Json(TodoResponse::from(todo)).into_response(),
-    Ok(None) => (StatusCode::NOT_FOUND, "Todo not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch todo").into_response(),
+  match state.todo_service.get_board(tenant, params.project_id).await {
+    Ok(columns) => {
+      let response: Vec<BoardColumnResponse> = columns.into_iter().map(BoardColumnResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/todos/series/{series_id}",
+    params(("series_id" = Uuid, Path, description = "Series ID")),
+    responses(
+        (status = 200, description = "繰り返しシリーズのTodo履歴を取得", body = Vec<TodoResponse>),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn get_todo_series<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(series_id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.todo_service.get_todo_series(tenant, series_id).await {
+    Ok(todos) => {
+      let response: Vec<TodoResponse> = todos.into_iter().map(TodoResponse::from).collect();
+      Json(response).into_response()
+    }
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     post,
     path = "/api/todos",
+    params(("Idempotency-Key" = Option<String>, Header, description = "再送時に同じ値を指定すると、最初のレスポンスを返す")),
     request_body = CreateTodoRequest,
     responses(
-        (status = 201, description = "Todoを作成", body = TodoResponse),
-        (status = 500, description = "サーバーエラー")
+        (status = 201, description = "Todoを作成", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 400, description = "ワークフローに存在しないステータス", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "プロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "同じ Idempotency-Key のリクエストが処理中", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）、または Idempotency-Key が別の内容のリクエストに使われている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "todos"
 )]
 pub async fn create_todo<T: TodoService>(
   State(state): State<AppState<T>>,
-  Json(payload): Json<CreateTodoRequest>,
+  tenant: Tenant,
+  audit: AuditContext,
+  ValidatedJson(payload): ValidatedJson<CreateTodoRequest>,
 ) -> impl IntoResponse {
-  match state.todo_service.create_todo(payload.title, payload.description).await {
-    Ok(todo) => (StatusCode::CREATED, Json(TodoResponse::from(todo))).into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create todo").into_response(),
+  let draft = into_draft(
+    payload.title,
+    payload.description,
+    payload.project_id,
+    payload.priority,
+    payload.tags,
+    payload.due_date,
+    payload.recurrence,
+  );
+
+  match state.todo_service.create_todo(tenant, draft, payload.status, &audit).await {
+    Ok(todo) => (StatusCode::CREATED, operation_header(&audit), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
   }
 }
 
 #[utoipa::path(
     put,
     path = "/api/todos/{id}",
-    params(("id" = Uuid, Path, description = "Todo ID")),
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-Match" = Option<String>, Header, description = "GET で受け取った ETag。一致しなければ 412 を返す")
+    ),
     request_body = UpdateTodoRequest,
     responses(
-        (status = 200, description = "Todoを更新", body = TodoResponse),
-        (status = 404, description = "Todoが見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 200, description = "Todoを更新", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"), ("etag" = String, description = "更新後の版数"))),
+        (status = 400, description = "ワークフローに存在しないステータス", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたはプロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "ワークフローで許可されていない遷移", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "If-Match の ETag と現在の版数が一致しない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "todos"
 )]
 pub async fn update_todo<T: TodoService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+  headers: HeaderMap,
+  ValidatedJson(payload): ValidatedJson<UpdateTodoRequest>,
+) -> impl IntoResponse {
+  let Ok(expected_version) = if_match_version(&headers) else {
+    return AppError::PreconditionFailed("If-Match does not match the current version".to_string()).into_response();
+  };
+  let draft = into_draft(
+    payload.title,
+    payload.description,
+    payload.project_id,
+    payload.priority,
+    payload.tags,
+    payload.due_date,
+    payload.recurrence,
+  );
+
+  match state.todo_service.update_todo(tenant, id, draft, payload.status, payload.completed, expected_version, &audit).await {
+    Ok(todo) => (operation_header(&audit), etag_header(todo.version), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/todos/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Todo ID"),
+        ("If-Match" = Option<String>, Header, description = "GET で受け取った ETag。一致しなければ 412 を返す")
+    ),
+    request_body(content = PatchTodoRequest, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Todoを部分更新", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"), ("etag" = String, description = "更新後の版数"))),
+        (status = 400, description = "ワークフローに存在しないステータス", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたはプロジェクトが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "ワークフローで許可されていない遷移", body = Problem, content_type = "application/problem+json"),
+        (status = 412, description = "If-Match の ETag と現在の版数が一致しない", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn patch_todo<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+  headers: HeaderMap,
+  ValidatedJson(payload): ValidatedJson<PatchTodoRequest>,
+) -> impl IntoResponse {
+  let Ok(expected_version) = if_match_version(&headers) else {
+    return AppError::PreconditionFailed("If-Match does not match the current version".to_string()).into_response();
+  };
+  let patch = payload.into_patch();
+
+  match state.todo_service.patch_todo(tenant, id, patch, expected_version, &audit).await {
+    Ok(todo) => (operation_header(&audit), etag_header(todo.version), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/status",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = ChangeStatusRequest,
+    responses(
+        (status = 200, description = "Todoのステータスを変更", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 400, description = "ワークフローに存在しないステータス", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "ワークフローで許可されていない遷移", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn change_todo_status<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
-  Json(payload): Json<UpdateTodoRequest>,
+  audit: AuditContext,
+  ValidatedJson(payload): ValidatedJson<ChangeStatusRequest>,
+) -> impl IntoResponse {
+  match state.todo_service.change_status(tenant, id, payload.status, &audit).await {
+    Ok(todo) => (operation_header(&audit), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/bulk",
+    request_body = BulkTodoRequest,
+    responses(
+        (status = 200, description = "操作をまとめて実行し、操作ごとの結果を返す（失敗した操作だけが取り消される）", body = BulkTodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 400, description = "操作が空", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 413, description = "操作数が上限を超えている", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn bulk_update_todos<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  audit: AuditContext,
+  ValidatedJson(payload): ValidatedJson<BulkTodoRequest>,
 ) -> impl IntoResponse {
-  match state.todo_service.update_todo(id, payload.title, payload.description, payload.completed).await {
-    Ok(todo) => Json(TodoResponse::from(todo)).into_response(),
-    Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Todo not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to update todo").into_response(),
+  if payload.operations.is_empty() {
+    return AppError::BadRequest("No operations given".to_string()).into_response();
+  }
+  if payload.operations.len() > state.bulk_max_operations {
+    let message = format!("At most {} operations can be performed at once", state.bulk_max_operations);
+    return AppError::PayloadTooLarge(message).into_response();
+  }
+
+  let operations: Vec<BulkOperation> = payload.operations.into_iter().map(BulkOperation::from).collect();
+  let ids: Vec<Uuid> = operations.iter().map(BulkOperation::id).collect();
+
+  match state.todo_service.bulk_update(tenant, operations, &audit).await {
+    Ok(results) => {
+      let results = ids
+        .into_iter()
+        .zip(results)
+        .map(|(id, result)| match result {
+          Ok(Some(todo)) => BulkItemResponse { id, status: StatusCode::OK.as_u16(), todo: Some(TodoResponse::from(todo)), error: None },
+          Ok(None) => BulkItemResponse { id, status: StatusCode::NO_CONTENT.as_u16(), todo: None, error: None },
+          Err(err) => {
+            if let AppError::Internal(message) = &err {
+              error!("bulk operation on todo {} failed: {}", id, message);
+            }
+            BulkItemResponse { id, status: err.status().as_u16(), todo: None, error: Some(err.to_problem()) }
+          }
+        })
+        .collect();
+      (operation_header(&audit), Json(BulkTodoResponse { results })).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/move",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    request_body = MoveTodoRequest,
+    responses(
+        (status = 200, description = "Todoの並び順を変更", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 400, description = "自身を基準に指定した", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoまたは基準のTodoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "共有されたプロジェクトとしてのアクセスで、組織全体の並び順の振り直しが必要な位置に移動しようとした", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn move_todo<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+  ValidatedJson(payload): ValidatedJson<MoveTodoRequest>,
+) -> impl IntoResponse {
+  let Some(anchor) = payload.anchor() else {
+    return AppError::Validation("Specify exactly one of before or after".to_string()).into_response();
+  };
+  if matches!(anchor, MoveAnchor::Before(anchor_id) | MoveAnchor::After(anchor_id) if anchor_id == id) {
+    return AppError::BadRequest("A todo cannot be moved relative to itself".to_string()).into_response();
+  }
+
+  match state.todo_service.move_todo(tenant, id, anchor, &audit).await {
+    Ok(todo) => (operation_header(&audit), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
   }
 }
 
@@ -158,19 +878,45 @@ pub async fn update_todo<T: TodoService>(
     path = "/api/todos/{id}",
     params(("id" = Uuid, Path, description = "Todo ID")),
     responses(
-        (status = 204, description = "Todoを削除"),
-        (status = 404, description = "Todoが見つからない"),
-        (status = 500, description = "サーバーエラー")
+        (status = 204, description = "Todoをゴミ箱に移動", headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Todoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
     ),
     tag = "todos"
 )]
 pub async fn delete_todo<T: TodoService>(
   State(state): State<AppState<T>>,
+  tenant: Tenant,
+  Path(id): Path<Uuid>,
+  audit: AuditContext,
+) -> impl IntoResponse {
+  match state.todo_service.delete_todo(tenant, id, &audit).await {
+    Ok(_) => (StatusCode::NO_CONTENT, operation_header(&audit)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/todos/{id}/restore",
+    params(("id" = Uuid, Path, description = "Todo ID")),
+    responses(
+        (status = 200, description = "Todoをゴミ箱から戻す", body = TodoResponse, headers(("x-operation-id" = Uuid, description = "取り消し用の操作 ID"))),
+        (status = 403, description = "viewer は Todo を変更できない", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "ゴミ箱にTodoが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "todos"
+)]
+pub async fn restore_todo<T: TodoService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
   Path(id): Path<Uuid>,
+  audit: AuditContext,
 ) -> impl IntoResponse {
-  match state.todo_service.delete_todo(id).await {
-    Ok(_) => StatusCode::NO_CONTENT.into_response(),
-    Err(sqlx::Error::RowNotFound) => (StatusCode::NOT_FOUND, "Todo not found").into_response(),
-    Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete todo").into_response(),
+  match state.todo_service.restore_todo(tenant, id, &audit).await {
+    Ok(todo) => (operation_header(&audit), Json(TodoResponse::from(todo))).into_response(),
+    Err(err) => err.into_response(),
   }
 }
@@ -0,0 +1,95 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::domain::models::organization::Tenant;
+use crate::presentation::problem::Problem;
+use crate::domain::models::trash::Trash;
+use crate::presentation::handlers::invoice_handler::InvoiceResponse;
+use crate::presentation::handlers::todo_handler::TodoResponse;
+use crate::usecase::trash_usecase::TrashService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: TrashService> {
+  pub trash_service: Arc<T>,
+}
+
+pub fn create_trash_router<T: TrashService + Send + Sync + 'static + Clone>(trash_service: T) -> Router {
+  let state = AppState {
+    trash_service: Arc::new(trash_service),
+  };
+
+  Router::new()
+    .route("/trash", get(get_trash::<T>))
+    .with_state(state)
+}
+
+#[derive(Serialize, ToSchema)]
+struct TrashedResponse<T> {
+  item: T,
+  deleted_at: DateTime<Utc>,
+  /// この日時以降のパージで物理削除される
+  purge_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TrashResponse {
+  todos: Vec<TrashedResponse<TodoResponse>>,
+  invoices: Vec<TrashedResponse<InvoiceResponse>>,
+}
+
+impl TrashResponse {
+  fn new(trash: Trash, retention: Duration) -> Self {
+    let trashed = |deleted_at: Option<DateTime<Utc>>| {
+      let deleted_at = deleted_at.unwrap_or_default();
+      (deleted_at, deleted_at + retention)
+    };
+
+    Self {
+      todos: trash
+        .todos
+        .into_iter()
+        .map(|todo| {
+          let (deleted_at, purge_at) = trashed(todo.deleted_at);
+          TrashedResponse { item: TodoResponse::from(todo), deleted_at, purge_at }
+        })
+        .collect(),
+      invoices: trash
+        .invoices
+        .into_iter()
+        .map(|invoice| {
+          let (deleted_at, purge_at) = trashed(invoice.deleted_at);
+          TrashedResponse { item: InvoiceResponse::from(invoice), deleted_at, purge_at }
+        })
+        .collect(),
+    }
+  }
+}
+
+
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    responses(
+        (status = 200, description = "ゴミ箱のTodoと請求書を新しく削除した順に取得", body = TrashResponse),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "trash"
+)]
+pub async fn get_trash<T: TrashService>(
+  State(state): State<AppState<T>>,
+  tenant: Tenant,
+) -> impl IntoResponse {
+  match state.trash_service.get_trash(tenant).await {
+    Ok(trash) => Json(TrashResponse::new(trash, state.trash_service.retention())).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,211 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use utoipa::ToSchema;
+
+use crate::domain::error::AppError;
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::problem::Problem;
+use crate::presentation::validation::ValidatedJson;
+use crate::domain::models::user::{User, UserSummary, EMAIL_MAX_LENGTH, NAME_MAX_LENGTH};
+use crate::domain::validation::{Validate, ValidationErrors};
+use crate::usecase::user_usecase::UserService;
+
+
+#[derive(Clone)]
+pub struct AppState<T: UserService> {
+  pub user_service: Arc<T>,
+}
+
+pub fn create_user_router<T: UserService + Send + Sync + 'static + Clone>(user_service: T) -> Router {
+  let state = AppState {
+    user_service: Arc::new(user_service),
+  };
+
+  Router::new()
+    .route("/users/{id}", get(get_user_by_id::<T>)
+      .put(update_user::<T>)
+      .delete(delete_user::<T>))
+    .route("/users/{id}/email/verify", post(verify_email::<T>))
+    .with_state(state)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UserRequest {
+  name: String,
+  email: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EmailVerificationRequest {
+  /// 新しいメールアドレスに送られたトークン
+  token: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UserResponse {
+  id: Uuid,
+  name: String,
+  email: String,
+  /// 確認待ちの新しいメールアドレス。更新でメールアドレスを変えた場合だけ返す
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pending_email: Option<String>,
+}
+
+impl From<User> for UserResponse {
+  fn from(user: User) -> Self {
+    Self {
+      id: user.id,
+      name: user.name,
+      email: user.email,
+      pending_email: None,
+    }
+  }
+}
+
+// Todo の担当者などに埋め込むユーザー情報
+#[derive(Serialize, ToSchema)]
+pub struct UserSummaryResponse {
+  id: Uuid,
+  name: String,
+}
+
+impl From<UserSummary> for UserSummaryResponse {
+  fn from(user: UserSummary) -> Self {
+    Self {
+      id: user.id,
+      name: user.name,
+    }
+  }
+}
+
+impl Validate for EmailVerificationRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("token", self.token.trim(), 1, 256);
+    errors.into_result()
+  }
+}
+
+impl Validate for UserRequest {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+    errors.length("name", self.name.trim(), 1, NAME_MAX_LENGTH);
+    errors.email("email", self.email.trim(), EMAIL_MAX_LENGTH);
+    errors.into_result()
+  }
+}
+
+
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "ユーザーを取得", body = UserResponse),
+        (status = 403, description = "自分以外のユーザー", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "ユーザーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "users"
+)]
+pub async fn get_user_by_id<T: UserService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.user_service.get_user_by_id(user_id, id).await {
+    Ok(Some(user)) => Json(UserResponse::from(user)).into_response(),
+    Ok(None) => AppError::NotFound("User not found".to_string()).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = UserRequest,
+    responses(
+        (status = 200, description = "ユーザーを更新（メールアドレスは新しいアドレスで確認されるまで変わらず、pending_email に返す）", body = UserResponse),
+        (status = 403, description = "自分以外のユーザー", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "ユーザーが見つからない", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "メールアドレスが使用済み", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "users"
+)]
+pub async fn update_user<T: UserService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<UserRequest>,
+) -> impl IntoResponse {
+  let email = payload.email.trim().to_string();
+  match state.user_service.update_user(user_id, id, payload.name.trim().to_string(), email.clone()).await {
+    Ok(user) => {
+      let pending_email = (!user.email.eq_ignore_ascii_case(&email)).then_some(email);
+      Json(UserResponse { pending_email, ..UserResponse::from(user) }).into_response()
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/email/verify",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = EmailVerificationRequest,
+    responses(
+        (status = 200, description = "確認待ちのメールアドレスに変更", body = UserResponse),
+        (status = 403, description = "自分以外のユーザー", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "トークンが無効または期限切れ", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "メールアドレスが使用済み", body = Problem, content_type = "application/problem+json"),
+        (status = 422, description = "入力の検証エラー（項目ごとのエラーを errors に返す）", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "users"
+)]
+pub async fn verify_email<T: UserService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+  ValidatedJson(payload): ValidatedJson<EmailVerificationRequest>,
+) -> impl IntoResponse {
+  match state.user_service.confirm_email(user_id, id, payload.token.trim()).await {
+    Ok(user) => Json(UserResponse::from(user)).into_response(),
+    Err(err) => err.into_response(),
+  }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 204, description = "ユーザーを削除（担当・ウォッチも解除される）"),
+        (status = 403, description = "自分以外のユーザー", body = Problem, content_type = "application/problem+json"),
+        (status = 409, description = "ユーザーが所有する Todo・請求書が残っている", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "サーバーエラー", body = Problem, content_type = "application/problem+json")
+    ),
+    tag = "users"
+)]
+pub async fn delete_user<T: UserService>(
+  State(state): State<AppState<T>>,
+  CurrentUser(user_id): CurrentUser,
+  Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+  match state.user_service.delete_user(user_id, id).await {
+    Ok(_) => StatusCode::NO_CONTENT.into_response(),
+    Err(err) => err.into_response(),
+  }
+}
@@ -0,0 +1,244 @@
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::request::Parts;
+use http::{HeaderName, HeaderValue, Method, StatusCode};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::domain::error::AppError;
+use crate::domain::models::idempotency::StoredResponse;
+use crate::presentation::current_user::CurrentUser;
+use crate::presentation::tenant::{ORGANIZATION_ID_HEADER, SHARED_PROJECT_ID_HEADER};
+use crate::usecase::idempotency_usecase::{IdempotencyOutcome, IdempotencyService};
+
+// 同じ値で再送された POST リクエストは処理せず、最初のレスポンスを返す。
+// キーはユーザーごとに別々に扱い、認証されていないリクエストには適用しない
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+// 記録したレスポンスを返した場合に付けるヘッダ
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+const MAX_KEY_LENGTH: usize = 255;
+
+// 秘密の値（発行した API キーなど）を含むレスポンスの印。ハンドラがレスポンスの extensions に入れると、
+// そのレスポンスは記録せずにキーを解放する（再送された場合は処理し直す）
+#[derive(Debug, Clone, Copy)]
+pub struct ConfidentialResponse;
+
+#[derive(Clone)]
+pub struct IdempotencyState<T: IdempotencyService> {
+  pub idempotency_service: Arc<T>,
+  // 比較のために読み込むリクエスト本体の上限
+  pub max_body_bytes: usize,
+}
+
+// メソッド・パス・ユーザー・対象の組織（X-Organization-Id）・共有されたプロジェクト（X-Shared-Project-Id）・本体が同じなら同じリクエストとみなす
+fn request_hash(parts: &Parts, body: &[u8]) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(parts.method.as_str());
+  hasher.update([0]);
+  hasher.update(parts.uri.path_and_query().map(|value| value.as_str()).unwrap_or_default());
+  hasher.update([0]);
+  if let Some(CurrentUser(user_id)) = parts.extensions.get::<CurrentUser>() {
+    hasher.update(user_id.as_bytes());
+  }
+  hasher.update([0]);
+  if let Some(organization_id) = parts.headers.get(ORGANIZATION_ID_HEADER) {
+    hasher.update(organization_id.as_bytes());
+  }
+  hasher.update([0]);
+  if let Some(project_id) = parts.headers.get(SHARED_PROJECT_ID_HEADER) {
+    hasher.update(project_id.as_bytes());
+  }
+  hasher.update([0]);
+  hasher.update(body);
+  hex::encode(hasher.finalize())
+}
+
+// 処理中として登録したキー。記録も解放もしないまま破棄された場合（クライアントの切断でハンドラが中断された場合など）は、
+// 破棄の時点で解放して再送を受け付ける
+struct PendingKey<T: IdempotencyService + Send + Sync + 'static> {
+  service: Arc<T>,
+  user_id: Uuid,
+  key: String,
+  settled: bool,
+}
+
+impl<T: IdempotencyService + Send + Sync + 'static> PendingKey<T> {
+  // レスポンスを記録する。失敗した場合はキーを解放する
+  async fn complete(mut self, response: &StoredResponse) {
+    match self.service.complete(self.user_id, &self.key, response).await {
+      Ok(()) => self.settled = true,
+      Err(err) => {
+        warn!("failed to record response for idempotency key {}: {}", self.key, err);
+        self.release().await;
+      }
+    }
+  }
+
+  async fn release(mut self) {
+    self.settled = true;
+    if let Err(err) = self.service.release(self.user_id, &self.key).await {
+      warn!("failed to release idempotency key {}: {}", self.key, err);
+    }
+  }
+}
+
+impl<T: IdempotencyService + Send + Sync + 'static> Drop for PendingKey<T> {
+  fn drop(&mut self) {
+    if self.settled {
+      return;
+    }
+    let (service, user_id, key) = (self.service.clone(), self.user_id, std::mem::take(&mut self.key));
+    tokio::spawn(async move {
+      if let Err(err) = service.release(user_id, &key).await {
+        warn!("failed to release idempotency key {}: {}", key, err);
+      }
+    });
+  }
+}
+
+fn replay(stored: StoredResponse) -> Response {
+  let mut response = Response::new(Body::from(stored.body));
+  *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+  let headers = response.headers_mut();
+  for (name, value) in stored.headers {
+    if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+      headers.append(name, value);
+    }
+  }
+  headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+  response
+}
+
+pub async fn idempotency<T: IdempotencyService + Send + Sync + 'static>(
+  State(state): State<IdempotencyState<T>>,
+  request: Request,
+  next: Next,
+) -> Response {
+  if request.method() != Method::POST {
+    return next.run(request).await;
+  }
+  let Some(value) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+    return next.run(request).await;
+  };
+  let Some(CurrentUser(user_id)) = request.extensions().get::<CurrentUser>().copied() else {
+    return next.run(request).await;
+  };
+  let key = match value.to_str().map(str::trim) {
+    Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+    _ => return AppError::BadRequest("Invalid Idempotency-Key header".to_string()).into_response(),
+  };
+
+  let (parts, body) = request.into_parts();
+  let Ok(body) = to_bytes(body, state.max_body_bytes).await else {
+    return AppError::PayloadTooLarge("Request body is too large".to_string()).into_response();
+  };
+
+  match state.idempotency_service.begin(user_id, &key, &request_hash(&parts, &body)).await {
+    Ok(IdempotencyOutcome::Proceed) => {}
+    Ok(IdempotencyOutcome::Replay(stored)) => return replay(stored),
+    Err(err) => return err.into_response(),
+  }
+  let pending = PendingKey { service: state.idempotency_service.clone(), user_id, key, settled: false };
+
+  let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+  // サーバーエラーと秘密の値を含むレスポンスは記録せず、再送で処理し直せるようにする
+  if response.status().is_server_error() || response.extensions().get::<ConfidentialResponse>().is_some() {
+    pending.release().await;
+    return response;
+  }
+
+  let (parts, body) = response.into_parts();
+  let body = match to_bytes(body, usize::MAX).await {
+    Ok(body) => body,
+    Err(read_err) => {
+      let message = format!("failed to read response for idempotency key {}: {}", pending.key, read_err);
+      pending.release().await;
+      return AppError::Internal(message).into_response();
+    }
+  };
+  let stored = StoredResponse {
+    status: parts.status.as_u16(),
+    headers: parts
+      .headers
+      .iter()
+      .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+      .collect(),
+    body: body.to_vec(),
+  };
+  // 記録に失敗してもレスポンス自体は返す（キーは解放する）
+  pending.complete(&stored).await;
+  Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use async_trait::async_trait;
+  use std::sync::Mutex;
+
+  // 解放されたキーを記録する
+  #[derive(Default)]
+  struct RecordingService {
+    released: Mutex<Vec<String>>,
+  }
+
+  #[async_trait]
+  impl IdempotencyService for RecordingService {
+    async fn begin(&self, _user_id: Uuid, _key: &str, _request_hash: &str) -> Result<IdempotencyOutcome, AppError> {
+      Ok(IdempotencyOutcome::Proceed)
+    }
+
+    async fn complete(&self, _user_id: Uuid, key: &str, _response: &StoredResponse) -> Result<(), AppError> {
+      if key == "unrecordable" {
+        return Err(AppError::Internal("database is unavailable".to_string()));
+      }
+      Ok(())
+    }
+
+    async fn release(&self, _user_id: Uuid, key: &str) -> Result<(), AppError> {
+      self.released.lock().unwrap().push(key.to_string());
+      Ok(())
+    }
+
+    async fn purge_expired(&self) -> Result<u64, AppError> {
+      Ok(0)
+    }
+  }
+
+  fn pending(service: &Arc<RecordingService>, key: &str) -> PendingKey<RecordingService> {
+    PendingKey { service: service.clone(), user_id: Uuid::now_v7(), key: key.to_string(), settled: false }
+  }
+
+  fn response() -> StoredResponse {
+    StoredResponse { status: 201, headers: Vec::new(), body: Vec::new() }
+  }
+
+  #[tokio::test]
+  async fn dropped_key_is_released() {
+    let service = Arc::new(RecordingService::default());
+    drop(pending(&service, "cancelled"));
+    tokio::task::yield_now().await;
+    assert_eq!(*service.released.lock().unwrap(), vec!["cancelled"]);
+  }
+
+  #[tokio::test]
+  async fn recorded_key_is_kept() {
+    let service = Arc::new(RecordingService::default());
+    pending(&service, "recorded").complete(&response()).await;
+    tokio::task::yield_now().await;
+    assert!(service.released.lock().unwrap().is_empty());
+  }
+
+  #[tokio::test]
+  async fn key_is_released_when_recording_fails() {
+    let service = Arc::new(RecordingService::default());
+    pending(&service, "unrecordable").complete(&response()).await;
+    tokio::task::yield_now().await;
+    assert_eq!(*service.released.lock().unwrap(), vec!["unrecordable"]);
+  }
+}
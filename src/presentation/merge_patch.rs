@@ -0,0 +1,20 @@
+use serde::{Deserialize, Deserializer};
+
+use crate::domain::validation::ValidationErrors;
+
+// JSON Merge Patch（RFC 7396）の項目用。#[serde(default)] と組み合わせて、
+// 項目なし → None、null → Some(None)、値あり → Some(Some(値)) に分けて受け取る
+pub fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  Option::<T>::deserialize(deserializer).map(Some)
+}
+
+// null にできない項目で null が指定された場合のエラー
+pub fn reject_null<T>(errors: &mut ValidationErrors, value: &Option<Option<T>>, field: &str) {
+  if let Some(None) = value {
+    errors.add(field, "required", format!("{} cannot be null", field));
+  }
+}
@@ -1 +1,11 @@
+pub mod auth;
+pub mod current_user;
+pub mod etag;
 pub mod handlers;
+pub mod idempotency;
+pub mod merge_patch;
+pub mod operation;
+pub mod pagination;
+pub mod problem;
+pub mod tenant;
+pub mod validation;
@@ -0,0 +1,8 @@
+use crate::domain::models::audit::AuditContext;
+
+// 変更を伴うリクエストの操作 ID を返すヘッダ。POST /api/undo/{operation_id} で取り消せる
+pub const OPERATION_ID_HEADER: &str = "x-operation-id";
+
+pub fn operation_header(audit: &AuditContext) -> [(&'static str, String); 1] {
+  [(OPERATION_ID_HEADER, audit.operation_id.to_string())]
+}
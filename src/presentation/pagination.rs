@@ -0,0 +1,79 @@
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use http::{header, HeaderValue, Uri};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::domain::models::page::{Page, PageRequest};
+
+#[derive(Deserialize, IntoParams)]
+pub struct PageQuery {
+  /// 1ページの件数（既定 50、最大 200）
+  pub limit: Option<i64>,
+  /// 前のページの `next_cursor`
+  pub cursor: Option<Uuid>,
+}
+
+impl From<&PageQuery> for PageRequest {
+  fn from(query: &PageQuery) -> Self {
+    PageRequest::new(query.limit, query.cursor)
+  }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PageResponse<T> {
+  pub items: Vec<T>,
+  pub next_cursor: Option<Uuid>,
+}
+
+// ページ本体に加えて、次ページがあれば RFC 8288 の Link ヘッダを付ける。
+// 次ページのリンクは元のクエリ（limit やフィルタなど）をそのまま使い、cursor だけを置き換える
+pub fn page_response<T: Serialize>(uri: &Uri, page: Page<T>) -> Response {
+  let link = page.next_cursor.map(|cursor| format!("<{}>; rel=\"next\"", next_link(uri, cursor)));
+
+  let mut response = Json(PageResponse {
+    items: page.items,
+    next_cursor: page.next_cursor,
+  })
+  .into_response();
+
+  if let Some(value) = link.and_then(|l| HeaderValue::from_str(&l).ok()) {
+    response.headers_mut().insert(header::LINK, value);
+  }
+  response
+}
+
+fn next_link(uri: &Uri, cursor: Uuid) -> String {
+  let mut params: Vec<String> = uri
+    .query()
+    .unwrap_or_default()
+    .split('&')
+    .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some("cursor"))
+    .map(str::to_string)
+    .collect();
+  params.push(format!("cursor={}", cursor));
+  format!("{}?{}", uri.path(), params.join("&"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn next_link_keeps_the_original_query_and_replaces_the_cursor() {
+    let cursor = Uuid::nil();
+    let uri: Uri = "/api/todos?filter=priority%3Dhigh&cursor=0192a000-0000-7000-8000-000000000000&order=manual&limit=500".parse().unwrap();
+    assert_eq!(
+      next_link(&uri, cursor),
+      format!("/api/todos?filter=priority%3Dhigh&order=manual&limit=500&cursor={}", cursor)
+    );
+  }
+
+  #[test]
+  fn next_link_without_query() {
+    let cursor = Uuid::nil();
+    let uri: Uri = "/api/invoices".parse().unwrap();
+    assert_eq!(next_link(&uri, cursor), format!("/api/invoices?cursor={}", cursor));
+  }
+}
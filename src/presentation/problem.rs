@@ -0,0 +1,110 @@
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use http::{header, HeaderValue, StatusCode};
+use serde::Serialize;
+use tracing::error;
+use utoipa::ToSchema;
+
+use crate::domain::error::AppError;
+use crate::domain::validation::FieldError;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// RFC 9457 の Problem Details。エラー時はすべてこの形式で返す
+#[derive(Serialize, ToSchema)]
+pub struct Problem {
+  /// 問題の種類。個別の種類を定義していないため常に about:blank
+  #[serde(rename = "type")]
+  #[schema(example = "about:blank")]
+  pub problem_type: &'static str,
+  /// ステータスコードの説明
+  #[schema(example = "Not Found")]
+  pub title: String,
+  pub status: u16,
+  /// 今回の問題の説明
+  #[schema(example = "Todo not found")]
+  pub detail: String,
+  /// フィルタ式の構文エラーの位置（0 始まりの文字数）
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub position: Option<usize>,
+  /// フィルタ式の構文エラーのトークン
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub token: Option<String>,
+  /// 入力の検証エラー（422）の場合の項目ごとのエラー
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub errors: Option<Vec<FieldProblem>>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FieldProblem {
+  /// エラーのある項目（配列の要素は tags[2] のように表す）
+  #[schema(example = "title")]
+  pub field: String,
+  /// required / length / range / format / count
+  #[schema(example = "length")]
+  pub code: &'static str,
+  #[schema(example = "title must not be longer than 200 characters")]
+  pub message: String,
+}
+
+impl From<&FieldError> for FieldProblem {
+  fn from(err: &FieldError) -> Self {
+    Self {
+      field: err.field.clone(),
+      code: err.code,
+      message: err.message.clone(),
+    }
+  }
+}
+
+impl AppError {
+  pub fn status(&self) -> StatusCode {
+    match self {
+      AppError::BadRequest(_) | AppError::InvalidFilter(_) => StatusCode::BAD_REQUEST,
+      AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+      AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+      AppError::NotFound(_) => StatusCode::NOT_FOUND,
+      AppError::Conflict(_) => StatusCode::CONFLICT,
+      AppError::Gone(_) => StatusCode::GONE,
+      AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+      AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+      AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+      AppError::Validation(_) | AppError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+      AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+
+  pub fn to_problem(&self) -> Problem {
+    let status = self.status();
+    let (detail, position, token) = match self {
+      AppError::InvalidFilter(err) => (err.message.clone(), Some(err.position), Some(err.token.clone())),
+      AppError::InvalidFields(_) => ("The request contains invalid fields".to_string(), None, None),
+      AppError::Internal(_) => ("An unexpected error occurred".to_string(), None, None),
+      err => (err.to_string(), None, None),
+    };
+    let errors = match self {
+      AppError::InvalidFields(errors) => Some(errors.errors().iter().map(FieldProblem::from).collect()),
+      _ => None,
+    };
+    Problem {
+      problem_type: "about:blank",
+      title: status.canonical_reason().unwrap_or_default().to_string(),
+      status: status.as_u16(),
+      detail,
+      position,
+      token,
+      errors,
+    }
+  }
+}
+
+impl IntoResponse for AppError {
+  fn into_response(self) -> Response {
+    if let AppError::Internal(message) = &self {
+      error!("{}", message);
+    }
+    let mut response = (self.status(), Json(self.to_problem())).into_response();
+    response.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    response
+  }
+}
@@ -0,0 +1,108 @@
+use axum::extract::{FromRequestParts, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::request::Parts;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::presentation::current_user::CurrentUser;
+use crate::usecase::organization_usecase::OrganizationService;
+use crate::usecase::share_usecase::ShareService;
+
+// 対象の組織を指定するヘッダ。省略した場合は個人用の組織
+pub const ORGANIZATION_ID_HEADER: &str = "x-organization-id";
+
+// 組織の外から、共有されたプロジェクトを対象にする場合に指定するヘッダ
+pub const SHARED_PROJECT_ID_HEADER: &str = "x-shared-project-id";
+
+fn uuid_header(request: &Request, name: &str, label: &str) -> Result<Option<Uuid>, AppError> {
+  let Some(value) = request.headers().get(name) else {
+    return Ok(None);
+  };
+  value
+    .to_str()
+    .ok()
+    .and_then(|value| Uuid::parse_str(value.trim()).ok())
+    .map(Some)
+    .ok_or_else(|| AppError::BadRequest(format!("{} must be a UUID", label)))
+}
+
+pub struct TenantState<T: OrganizationService, S: ShareService> {
+  pub organization_service: Arc<T>,
+  pub share_service: Arc<S>,
+  // 共有されたプロジェクトとしてのアクセスを受け付けるか。請求書など、プロジェクトに属さないデータのルートでは false
+  pub allow_shared: bool,
+}
+
+// ハンドラと同じ Arc を共有するため、T: Clone, S: Clone は求めない
+impl<T: OrganizationService, S: ShareService> Clone for TenantState<T, S> {
+  fn clone(&self) -> Self {
+    Self {
+      organization_service: self.organization_service.clone(),
+      share_service: self.share_service.clone(),
+      allow_shared: self.allow_shared,
+    }
+  }
+}
+
+async fn tenant_for<T: OrganizationService, S: ShareService>(
+  state: &TenantState<T, S>,
+  user_id: Uuid,
+  organization_id: Option<Uuid>,
+  shared_project_id: Option<Uuid>,
+) -> Result<Tenant, AppError> {
+  let Some(project_id) = shared_project_id else {
+    return state.organization_service.resolve_tenant(user_id, organization_id).await;
+  };
+  if !state.allow_shared {
+    return Err(AppError::Forbidden("This endpoint cannot be called for a shared project".to_string()));
+  }
+  if organization_id.is_some() {
+    return Err(AppError::BadRequest("X-Organization-Id and X-Shared-Project-Id cannot be combined".to_string()));
+  }
+  state.share_service.resolve_shared_tenant(user_id, project_id).await
+}
+
+// 認証したユーザーと X-Organization-Id（または X-Shared-Project-Id）から対象を決め、Tenant として extensions に入れる。
+// require_authentication の後に置く
+pub async fn resolve_tenant<T, S>(
+  State(state): State<TenantState<T, S>>,
+  mut request: Request,
+  next: Next,
+) -> Response
+where
+  T: OrganizationService + Send + Sync + 'static,
+  S: ShareService + Send + Sync + 'static,
+{
+  let Some(CurrentUser(user_id)) = request.extensions().get::<CurrentUser>().copied() else {
+    return AppError::Unauthorized("Authentication required".to_string()).into_response();
+  };
+  let targets = uuid_header(&request, ORGANIZATION_ID_HEADER, "X-Organization-Id")
+    .and_then(|organization_id| Ok((organization_id, uuid_header(&request, SHARED_PROJECT_ID_HEADER, "X-Shared-Project-Id")?)));
+  let tenant = match targets {
+    Ok((organization_id, shared_project_id)) => tenant_for(&state, user_id, organization_id, shared_project_id).await,
+    Err(err) => Err(err),
+  };
+  match tenant {
+    Ok(tenant) => {
+      request.extensions_mut().insert(tenant);
+      next.run(request).await
+    }
+    Err(err) => err.into_response(),
+  }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Tenant {
+  type Rejection = AppError;
+
+  async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    // resolve_tenant を通らないルートで使われた場合
+    parts
+      .extensions
+      .get::<Tenant>()
+      .copied()
+      .ok_or_else(|| AppError::Internal("tenant is not resolved for this route".to_string()))
+  }
+}
@@ -0,0 +1,37 @@
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::Json;
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+
+use crate::domain::error::AppError;
+use crate::domain::validation::Validate;
+
+// JSON の本体を読み込んで検証する。読み込みと検証のエラーは problem+json で返す
+pub struct ValidatedJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidatedJson<T>
+where
+  S: Send + Sync,
+  T: DeserializeOwned + Validate,
+{
+  type Rejection = AppError;
+
+  async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let Json(value) = Json::<T>::from_request(request, state).await.map_err(json_rejection)?;
+    value.validate()?;
+    Ok(Self(value))
+  }
+}
+
+fn json_rejection(rejection: JsonRejection) -> AppError {
+  match rejection {
+    // 必須項目がない・型が違うなど、構文は正しいが受け付けられない
+    JsonRejection::JsonDataError(err) => AppError::Validation(err.body_text()),
+    JsonRejection::MissingJsonContentType(err) => AppError::UnsupportedMediaType(err.body_text()),
+    JsonRejection::BytesRejection(err) if err.status() == StatusCode::PAYLOAD_TOO_LARGE => {
+      AppError::PayloadTooLarge(err.body_text())
+    }
+    err => AppError::BadRequest(err.body_text()),
+  }
+}
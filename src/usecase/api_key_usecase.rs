@@ -0,0 +1,88 @@
+use crate::domain::error::AppError;
+use crate::domain::models::api_key::{
+  ApiKey, ApiScope, ApiScopes, IssuedApiKey, DISPLAY_PREFIX_LENGTH, LAST_USED_RESOLUTION, TOKEN_PREFIX,
+};
+use crate::domain::repositories::api_key_repository::ApiKeyRepository;
+use crate::usecase::secret_token;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct ApiKeyUsecase<A: ApiKeyRepository + Clone> {
+  repository: A,
+}
+
+impl<A: ApiKeyRepository + Clone> ApiKeyUsecase<A> {
+  pub fn new(repository: A) -> Self {
+    Self { repository }
+  }
+}
+
+fn invalid_api_key() -> AppError {
+  AppError::Unauthorized("Invalid or expired API key".to_string())
+}
+
+#[async_trait]
+pub trait ApiKeyService {
+  // キーは結果にだけ含まれ、保存されない
+  async fn create_api_key(&self, user_id: Uuid, name: String, scopes: Vec<ApiScope>, ttl: Duration) -> Result<IssuedApiKey, AppError>;
+  // 期限切れのキーも含めて返す
+  async fn get_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, AppError>;
+  async fn revoke_api_key(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError>;
+  // キーを検証して最後に使った日時を記録する
+  async fn authenticate(&self, token: &str) -> Result<ApiKey, AppError>;
+}
+
+#[async_trait]
+impl<A: ApiKeyRepository + Send + Sync + Clone> ApiKeyService for ApiKeyUsecase<A> {
+  async fn create_api_key(&self, user_id: Uuid, name: String, scopes: Vec<ApiScope>, ttl: Duration) -> Result<IssuedApiKey, AppError> {
+    let mut unique_scopes = Vec::with_capacity(scopes.len());
+    for scope in scopes {
+      if !unique_scopes.contains(&scope) {
+        unique_scopes.push(scope);
+      }
+    }
+
+    let token = format!("{}{}", TOKEN_PREFIX, secret_token::generate());
+    let now = Utc::now();
+    let api_key = ApiKey {
+      id: Uuid::now_v7(),
+      user_id,
+      name,
+      prefix: token[..DISPLAY_PREFIX_LENGTH].to_string(),
+      token_hash: secret_token::hash(&token),
+      scopes: ApiScopes(unique_scopes),
+      expires_at: now + ttl,
+      last_used_at: None,
+      created_at: now,
+    };
+    let api_key = self.repository.create(api_key).await?;
+    Ok(IssuedApiKey { api_key, token })
+  }
+
+  async fn get_api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, AppError> {
+    Ok(self.repository.find_by_user(user_id).await?)
+  }
+
+  async fn revoke_api_key(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    self.repository
+      .delete(user_id, id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "API key not found"))
+  }
+
+  async fn authenticate(&self, token: &str) -> Result<ApiKey, AppError> {
+    let api_key = self.repository
+      .find_by_hash(&secret_token::hash(token))
+      .await?
+      .ok_or_else(invalid_api_key)?;
+    let now = Utc::now();
+    if api_key.expires_at <= now {
+      return Err(invalid_api_key());
+    }
+    self.repository.touch(api_key.id, now, now - LAST_USED_RESOLUTION).await?;
+    Ok(api_key)
+  }
+}
@@ -0,0 +1,114 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::assignment::{AssignmentEvent, MyWork};
+use crate::domain::models::todo::Todo;
+use crate::domain::models::user::UserSummary;
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::assignment_repository::AssignmentRepository;
+use crate::domain::repositories::todo_repository::TodoRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct AssignmentUsecase<A: AssignmentRepository + Clone, T: TodoRepository + Clone, U: UserRepository + Clone> {
+  repository: A,
+  todo_repository: T,
+  user_repository: U,
+}
+
+impl<A: AssignmentRepository + Clone, T: TodoRepository + Clone, U: UserRepository + Clone> AssignmentUsecase<A, T, U> {
+  pub fn new(repository: A, todo_repository: T, user_repository: U) -> Self {
+    Self { repository, todo_repository, user_repository }
+  }
+}
+
+fn todo_not_found() -> AppError {
+  AppError::NotFound("Todo not found".to_string())
+}
+
+impl<A, T, U> AssignmentUsecase<A, T, U>
+where
+  A: AssignmentRepository + Send + Sync + Clone,
+  T: TodoRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn find_todo(&self, tenant: Tenant, todo_id: Uuid) -> Result<Todo, AppError> {
+    self.todo_repository.find_by_id(tenant, todo_id).await?.ok_or_else(todo_not_found)
+  }
+
+  // 組織に所属していないユーザーは存在しないものとして扱う
+  async fn ensure_members(&self, tenant: Tenant, user_ids: &[Uuid]) -> Result<(), AppError> {
+    let found = self.user_repository.find_members_by_ids(tenant.organization_id, user_ids).await?;
+    let unknown: Vec<Uuid> = user_ids
+      .iter()
+      .filter(|id| !found.iter().any(|user| user.id == **id))
+      .copied()
+      .collect();
+    if unknown.is_empty() {
+      return Ok(());
+    }
+    let ids: Vec<String> = unknown.iter().map(Uuid::to_string).collect();
+    Err(AppError::BadRequest(format!("unknown users: {}", ids.join(", "))))
+  }
+}
+
+#[async_trait]
+pub trait AssignmentService {
+  // 担当者を置き換える。actor_id は履歴に記録する変更者
+  async fn set_assignees(&self, tenant: Tenant, todo_id: Uuid, user_ids: Vec<Uuid>, actor_id: Option<Uuid>) -> Result<Todo, AppError>;
+  async fn get_assignment_history(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<AssignmentEvent>, AppError>;
+  async fn get_watchers(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<UserSummary>, AppError>;
+  async fn watch_todo(&self, tenant: Tenant, todo_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+  async fn unwatch_todo(&self, tenant: Tenant, todo_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+  // 組織内の Todo のうち、自分が担当・ウォッチしているもの
+  async fn get_my_work(&self, tenant: Tenant) -> Result<MyWork, AppError>;
+}
+
+#[async_trait]
+impl<A, T, U> AssignmentService for AssignmentUsecase<A, T, U>
+where
+  A: AssignmentRepository + Send + Sync + Clone,
+  T: TodoRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn set_assignees(&self, tenant: Tenant, todo_id: Uuid, mut user_ids: Vec<Uuid>, actor_id: Option<Uuid>) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    self.find_todo(tenant, todo_id).await?;
+    user_ids.sort();
+    user_ids.dedup();
+    self.ensure_members(tenant, &user_ids).await?;
+
+    self.repository.replace_assignees(todo_id, &user_ids, actor_id).await?;
+    self.find_todo(tenant, todo_id).await
+  }
+
+  async fn get_assignment_history(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<AssignmentEvent>, AppError> {
+    self.find_todo(tenant, todo_id).await?;
+    Ok(self.repository.find_history(todo_id).await?)
+  }
+
+  async fn get_watchers(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<UserSummary>, AppError> {
+    self.find_todo(tenant, todo_id).await?;
+    Ok(self.repository.find_watchers(todo_id).await?)
+  }
+
+  async fn watch_todo(&self, tenant: Tenant, todo_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    self.find_todo(tenant, todo_id).await?;
+    self.ensure_members(tenant, &[user_id]).await?;
+    Ok(self.repository.add_watcher(todo_id, user_id).await?)
+  }
+
+  async fn unwatch_todo(&self, tenant: Tenant, todo_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    self.find_todo(tenant, todo_id).await?;
+    Ok(self.repository.remove_watcher(todo_id, user_id).await?)
+  }
+
+  async fn get_my_work(&self, tenant: Tenant) -> Result<MyWork, AppError> {
+    Ok(MyWork {
+      assigned: self.todo_repository.find_open_assigned(tenant, tenant.user_id).await?,
+      watching: self.todo_repository.find_open_watched(tenant, tenant.user_id).await?,
+    })
+  }
+}
@@ -0,0 +1,150 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::attachment::{sniff_content_type, Attachment, AttachmentOwner};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::attachment_repository::AttachmentRepository;
+use crate::domain::repositories::blob_store::{BlobError, BlobStore, BlobStream, ByteRange};
+use crate::domain::repositories::invoice_repository::InvoiceRepository;
+use crate::domain::repositories::todo_repository::TodoRepository;
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+
+// 保存先は設定で切り替えるため、BlobStore はトレイトオブジェクトで保持する
+#[derive(Clone)]
+pub struct AttachmentUsecase<A: AttachmentRepository + Clone, T: TodoRepository + Clone, I: InvoiceRepository + Clone> {
+  repository: A,
+  todo_repository: T,
+  invoice_repository: I,
+  blob_store: Arc<dyn BlobStore>,
+}
+
+impl<A: AttachmentRepository + Clone, T: TodoRepository + Clone, I: InvoiceRepository + Clone> AttachmentUsecase<A, T, I> {
+  pub fn new(repository: A, todo_repository: T, invoice_repository: I, blob_store: Arc<dyn BlobStore>) -> Self {
+    Self { repository, todo_repository, invoice_repository, blob_store }
+  }
+}
+
+fn owner_not_found(owner: AttachmentOwner) -> AppError {
+  match owner {
+    AttachmentOwner::Todo(_) => AppError::NotFound("Todo not found".to_string()),
+    AttachmentOwner::Invoice(_) => AppError::NotFound("Invoice not found".to_string()),
+  }
+}
+
+// 添付ファイルの追加・削除は添付先の変更として扱う
+fn edit_permission(owner: AttachmentOwner) -> Permission {
+  match owner {
+    AttachmentOwner::Todo(_) => Permission::EditTodos,
+    AttachmentOwner::Invoice(_) => Permission::ManageInvoices,
+  }
+}
+
+// 添付ファイルが存在しないか、指定した添付先のものではない
+fn attachment_not_found() -> AppError {
+  AppError::NotFound("Attachment not found".to_string())
+}
+
+fn blob_error(err: BlobError) -> AppError {
+  match err {
+    // DB には行があるのに本体がない
+    BlobError::NotFound => attachment_not_found(),
+    err => err.into(),
+  }
+}
+
+impl<A, T, I> AttachmentUsecase<A, T, I>
+where
+  A: AttachmentRepository + Send + Sync + Clone,
+  T: TodoRepository + Send + Sync + Clone,
+  I: InvoiceRepository + Send + Sync + Clone,
+{
+  async fn ensure_owner(&self, tenant: Tenant, owner: AttachmentOwner) -> Result<(), AppError> {
+    let exists = match owner {
+      AttachmentOwner::Todo(id) => self.todo_repository.find_by_id(tenant, id).await?.is_some(),
+      // 共有されたプロジェクトとしてのアクセスでは請求書は見えない
+      AttachmentOwner::Invoice(_) if tenant.project_id.is_some() => false,
+      AttachmentOwner::Invoice(id) => self.invoice_repository.find_by_id(tenant, id).await?.is_some(),
+    };
+    if exists { Ok(()) } else { Err(owner_not_found(owner)) }
+  }
+
+  async fn find_attachment(&self, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Result<Attachment, AppError> {
+    self.ensure_owner(tenant, owner).await?;
+    self.repository
+      .find_by_id(id)
+      .await?
+      .filter(|attachment| attachment.belongs_to(owner))
+      .ok_or_else(attachment_not_found)
+  }
+}
+
+#[async_trait]
+pub trait AttachmentService {
+  async fn get_attachments(&self, tenant: Tenant, owner: AttachmentOwner) -> Result<Vec<Attachment>, AppError>;
+  async fn get_attachment(&self, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Result<Attachment, AppError>;
+  // Content-Type はクライアントの申告を使わず内容から判定する
+  async fn upload_attachment(&self, tenant: Tenant, owner: AttachmentOwner, file_name: String, data: Bytes) -> Result<Attachment, AppError>;
+  // get_attachment で取得した添付ファイルの本体を読む。range はファイルサイズに収まるよう呼び出し側で調整しておく
+  async fn download_attachment(&self, attachment: &Attachment, range: Option<ByteRange>) -> Result<BlobStream, AppError>;
+  async fn delete_attachment(&self, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<A, T, I> AttachmentService for AttachmentUsecase<A, T, I>
+where
+  A: AttachmentRepository + Send + Sync + Clone,
+  T: TodoRepository + Send + Sync + Clone,
+  I: InvoiceRepository + Send + Sync + Clone,
+{
+  async fn get_attachments(&self, tenant: Tenant, owner: AttachmentOwner) -> Result<Vec<Attachment>, AppError> {
+    self.ensure_owner(tenant, owner).await?;
+    Ok(self.repository.find_by_owner(owner).await?)
+  }
+
+  async fn get_attachment(&self, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Result<Attachment, AppError> {
+    self.find_attachment(tenant, owner, id).await
+  }
+
+  async fn upload_attachment(&self, tenant: Tenant, owner: AttachmentOwner, file_name: String, data: Bytes) -> Result<Attachment, AppError> {
+    authorize(tenant.role, edit_permission(owner))?;
+    self.ensure_owner(tenant, owner).await?;
+
+    let content_type = sniff_content_type(&data);
+    let attachment = Attachment::new(owner, file_name, content_type, data.len() as i64);
+    self.blob_store.put(&attachment.storage_key, data, &attachment.content_type).await.map_err(blob_error)?;
+
+    // 行を保存できなかった場合は、参照されなくなる本体を消しておく
+    match self.repository.create(attachment.clone()).await {
+      Ok(created) => Ok(created),
+      Err(err) => {
+        if let Err(cleanup) = self.blob_store.delete(&attachment.storage_key).await {
+          warn!("failed to remove orphaned blob {}: {}", attachment.storage_key, cleanup);
+        }
+        Err(match err {
+          // 保存中に添付先が削除された
+          sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => owner_not_found(owner),
+          err => err.into(),
+        })
+      }
+    }
+  }
+
+  async fn download_attachment(&self, attachment: &Attachment, range: Option<ByteRange>) -> Result<BlobStream, AppError> {
+    self.blob_store.get(&attachment.storage_key, range).await.map_err(blob_error)
+  }
+
+  async fn delete_attachment(&self, tenant: Tenant, owner: AttachmentOwner, id: Uuid) -> Result<(), AppError> {
+    authorize(tenant.role, edit_permission(owner))?;
+    let attachment = self.find_attachment(tenant, owner, id).await?;
+    self.repository.delete(attachment.id).await.map_err(|err| AppError::from_sqlx(err, "Attachment not found"))?;
+    // 行は削除済みなので、本体の削除に失敗しても参照されることはない
+    if let Err(err) = self.blob_store.delete(&attachment.storage_key).await {
+      warn!("failed to remove blob {}: {}", attachment.storage_key, err);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,69 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::{AuditAction, AuditContext, AuditEntity, AuditEvent, RevertOutcome};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::audit_repository::AuditRepository;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct AuditUsecase<T: AuditRepository + Clone> {
+  repository: T,
+  // 操作を取り消せる期間
+  undo_window: Duration,
+}
+
+impl<T: AuditRepository + Clone> AuditUsecase<T> {
+  pub fn new(repository: T, undo_window: Duration) -> Self {
+    Self { repository, undo_window }
+  }
+}
+
+fn operation_not_found() -> AppError {
+  AppError::NotFound("Operation not found".to_string())
+}
+
+#[async_trait]
+pub trait AuditService {
+  // ゴミ箱に移動・物理削除したものの履歴も返す。一度も記録がない場合は NotFound
+  async fn get_history(&self, tenant: Tenant, entity: AuditEntity, entity_id: Uuid) -> Result<Vec<AuditEvent>, AppError>;
+  // 操作で記録された変更を打ち消す。取り消し自体も audit の操作として記録される
+  async fn undo(&self, tenant: Tenant, operation_id: Uuid, audit: &AuditContext) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: AuditRepository + Send + Sync + Clone> AuditService for AuditUsecase<T> {
+  async fn get_history(&self, tenant: Tenant, entity: AuditEntity, entity_id: Uuid) -> Result<Vec<AuditEvent>, AppError> {
+    let events = self.repository.find_by_entity(tenant, entity, entity_id).await?;
+    if events.is_empty() {
+      return Err(match entity {
+        AuditEntity::Todo => AppError::NotFound("Todo not found".to_string()),
+        AuditEntity::Invoice => AppError::NotFound("Invoice not found".to_string()),
+      });
+    }
+    Ok(events)
+  }
+
+  async fn undo(&self, tenant: Tenant, operation_id: Uuid, audit: &AuditContext) -> Result<(), AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let events = self.repository.find_by_operation(tenant, operation_id).await?;
+    let Some(first) = events.first() else {
+      return Err(operation_not_found());
+    };
+    if events.iter().any(|event| event.entity_type != AuditEntity::Todo || event.action == AuditAction::Purge) {
+      // Todo の作成・更新・削除・復元以外の操作
+      return Err(AppError::BadRequest("operation cannot be undone".to_string()));
+    }
+    if first.created_at < Utc::now() - self.undo_window {
+      return Err(AppError::Gone("operation can no longer be undone".to_string()));
+    }
+
+    match self.repository.revert(&events, audit).await? {
+      RevertOutcome::Reverted => Ok(()),
+      // 対象がその後に変更されている
+      RevertOutcome::Conflict => Err(AppError::Conflict("the affected todos have been modified since the operation".to_string())),
+    }
+  }
+}
@@ -0,0 +1,209 @@
+use crate::domain::error::AppError;
+use crate::domain::models::auth::{AccessClaims, RefreshToken, TokenPair};
+use crate::domain::models::user::User;
+use crate::domain::repositories::refresh_token_repository::RefreshTokenRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::usecase::secret_token;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use std::sync::OnceLock;
+use tracing::warn;
+use uuid::Uuid;
+
+
+// アクセストークン（HS256 の JWT）の署名鍵
+#[derive(Clone)]
+pub struct JwtKeys {
+  encoding: EncodingKey,
+  decoding: DecodingKey,
+}
+
+impl JwtKeys {
+  pub fn new(secret: &[u8]) -> Self {
+    Self {
+      encoding: EncodingKey::from_secret(secret),
+      decoding: DecodingKey::from_secret(secret),
+    }
+  }
+}
+
+#[derive(Clone)]
+pub struct AuthUsecase<U: UserRepository + Clone, R: RefreshTokenRepository + Clone> {
+  users: U,
+  refresh_tokens: R,
+  keys: JwtKeys,
+  access_token_ttl: Duration,
+  refresh_token_ttl: Duration,
+}
+
+impl<U: UserRepository + Clone, R: RefreshTokenRepository + Clone> AuthUsecase<U, R> {
+  pub fn new(users: U, refresh_tokens: R, keys: JwtKeys, access_token_ttl: Duration, refresh_token_ttl: Duration) -> Self {
+    Self { users, refresh_tokens, keys, access_token_ttl, refresh_token_ttl }
+  }
+
+  // アクセストークンと、family_id の系列に属する新しいリフレッシュトークンを発行する（リフレッシュトークンは未保存）
+  fn issue_tokens(&self, user_id: Uuid, family_id: Uuid) -> Result<(TokenPair, RefreshToken), AppError> {
+    let now = Utc::now();
+    let access_token_expires_at = now + self.access_token_ttl;
+    let claims = AccessClaims {
+      sub: user_id,
+      iat: now.timestamp(),
+      exp: access_token_expires_at.timestamp(),
+    };
+    let access_token = encode(&Header::new(Algorithm::HS256), &claims, &self.keys.encoding)
+      .map_err(|err| AppError::Internal(format!("failed to sign access token: {}", err)))?;
+
+    let refresh_token = secret_token::generate();
+    let record = RefreshToken {
+      id: Uuid::now_v7(),
+      user_id,
+      family_id,
+      token_hash: secret_token::hash(&refresh_token),
+      expires_at: now + self.refresh_token_ttl,
+      revoked_at: None,
+    };
+    let pair = TokenPair {
+      access_token,
+      access_token_expires_at,
+      refresh_token,
+      refresh_token_expires_at: record.expires_at,
+    };
+    Ok((pair, record))
+  }
+}
+
+fn invalid_credentials() -> AppError {
+  AppError::Unauthorized("Invalid email or password".to_string())
+}
+
+fn invalid_refresh_token() -> AppError {
+  AppError::Unauthorized("Invalid refresh token".to_string())
+}
+
+// Argon2 の計算は重いため、非同期のワーカーをふさがないよう別スレッドで行う
+async fn hash_password(password: String) -> Result<String, AppError> {
+  tokio::task::spawn_blocking(move || {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+      .hash_password(password.as_bytes(), &salt)
+      .map(|hash| hash.to_string())
+      .map_err(|err| AppError::Internal(format!("failed to hash password: {}", err)))
+  })
+  .await
+  .map_err(|err| AppError::Internal(format!("password hashing task failed: {}", err)))?
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool, AppError> {
+  tokio::task::spawn_blocking(move || {
+    let parsed = PasswordHash::new(&password_hash)
+      .map_err(|err| AppError::Internal(format!("invalid stored password hash: {}", err)))?;
+    Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+  })
+  .await
+  .map_err(|err| AppError::Internal(format!("password verification task failed: {}", err)))?
+}
+
+// 存在しないユーザーでも同じだけ照合に時間をかけ、登録の有無を応答時間から推測されないようにする
+async fn dummy_password_hash() -> Result<String, AppError> {
+  static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+  if let Some(hash) = DUMMY_HASH.get() {
+    return Ok(hash.clone());
+  }
+  let hash = hash_password(secret_token::generate()).await?;
+  Ok(DUMMY_HASH.get_or_init(|| hash).clone())
+}
+
+#[async_trait]
+pub trait AuthService {
+  async fn register(&self, name: String, email: String, password: String) -> Result<User, AppError>;
+  async fn login(&self, email: &str, password: String) -> Result<TokenPair, AppError>;
+  // パスワード以外の方法（OpenID Connect など）で本人確認をしたユーザーにトークンを発行する
+  async fn sign_in(&self, user_id: Uuid) -> Result<TokenPair, AppError>;
+  // 使ったリフレッシュトークンは無効になる。無効になったトークンが再び使われた場合は同じ系列をすべて無効にする
+  async fn refresh(&self, refresh_token: &str) -> Result<TokenPair, AppError>;
+  async fn logout(&self, refresh_token: &str) -> Result<(), AppError>;
+  // アクセストークンを検証して、ユーザー ID を返す
+  fn authenticate(&self, access_token: &str) -> Result<Uuid, AppError>;
+  // 期限切れのリフレッシュトークンを削除する。定期ジョブから呼ばれる
+  async fn purge_expired(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl<U: UserRepository + Send + Sync + Clone, R: RefreshTokenRepository + Send + Sync + Clone> AuthService for AuthUsecase<U, R> {
+  async fn register(&self, name: String, email: String, password: String) -> Result<User, AppError> {
+    let password_hash = hash_password(password).await?;
+    match self.users.create_with_password(User::new(name, email), &password_hash).await {
+      Ok(user) => Ok(user),
+      Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+        Err(AppError::Conflict("Email is already in use".to_string()))
+      }
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  async fn login(&self, email: &str, password: String) -> Result<TokenPair, AppError> {
+    let credentials = self.users.find_credentials_by_email(email).await?;
+    let (user_id, password_hash) = match credentials {
+      Some((user_id, Some(password_hash))) => (Some(user_id), password_hash),
+      _ => (None, dummy_password_hash().await?),
+    };
+    let verified = verify_password(password, password_hash).await?;
+    let Some(user_id) = user_id.filter(|_| verified) else {
+      return Err(invalid_credentials());
+    };
+    self.sign_in(user_id).await
+  }
+
+  async fn sign_in(&self, user_id: Uuid) -> Result<TokenPair, AppError> {
+    let (pair, record) = self.issue_tokens(user_id, Uuid::now_v7())?;
+    self.refresh_tokens.create(record).await?;
+    Ok(pair)
+  }
+
+  async fn refresh(&self, refresh_token: &str) -> Result<TokenPair, AppError> {
+    let token = self
+      .refresh_tokens
+      .find_by_hash(&secret_token::hash(refresh_token))
+      .await?
+      .ok_or_else(invalid_refresh_token)?;
+    if token.revoked_at.is_some() {
+      warn!("revoked refresh token reused; revoking token family {}", token.family_id);
+      self.refresh_tokens.revoke_family(token.family_id).await?;
+      return Err(invalid_refresh_token());
+    }
+    if token.expires_at <= Utc::now() {
+      return Err(invalid_refresh_token());
+    }
+
+    let (pair, next) = self.issue_tokens(token.user_id, token.family_id)?;
+    if !self.refresh_tokens.rotate(token.id, next).await? {
+      // 検証した後に他のリクエストが同じトークンを使った
+      warn!("refresh token used concurrently; revoking token family {}", token.family_id);
+      self.refresh_tokens.revoke_family(token.family_id).await?;
+      return Err(invalid_refresh_token());
+    }
+    Ok(pair)
+  }
+
+  async fn logout(&self, refresh_token: &str) -> Result<(), AppError> {
+    // 無効なトークンでもログアウトは成功扱いにする
+    if let Some(token) = self.refresh_tokens.find_by_hash(&secret_token::hash(refresh_token)).await? {
+      self.refresh_tokens.revoke_family(token.family_id).await?;
+    }
+    Ok(())
+  }
+
+  fn authenticate(&self, access_token: &str) -> Result<Uuid, AppError> {
+    decode::<AccessClaims>(access_token, &self.keys.decoding, &Validation::new(Algorithm::HS256))
+      .map(|data| data.claims.sub)
+      .map_err(|_| AppError::Unauthorized("Invalid or expired access token".to_string()))
+  }
+
+  async fn purge_expired(&self) -> Result<u64, AppError> {
+    Ok(self.refresh_tokens.delete_expired(Utc::now()).await?)
+  }
+}
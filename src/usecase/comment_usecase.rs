@@ -0,0 +1,136 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::comment::{Comment, CommentRevision};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::comment_repository::CommentRepository;
+use crate::domain::repositories::todo_repository::TodoRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct CommentUsecase<T: CommentRepository + Clone, R: TodoRepository + Clone, U: UserRepository + Clone> {
+  repository: T,
+  todo_repository: R,
+  user_repository: U,
+}
+
+impl<T: CommentRepository + Clone, R: TodoRepository + Clone, U: UserRepository + Clone> CommentUsecase<T, R, U> {
+  pub fn new(repository: T, todo_repository: R, user_repository: U) -> Self {
+    Self { repository, todo_repository, user_repository }
+  }
+}
+
+fn todo_not_found() -> AppError {
+  AppError::NotFound("Todo not found".to_string())
+}
+
+// コメントが存在しないか、指定した Todo のコメントではない
+fn comment_not_found() -> AppError {
+  AppError::NotFound("Comment not found".to_string())
+}
+
+fn write_error(err: sqlx::Error) -> AppError {
+  match err {
+    sqlx::Error::RowNotFound => comment_not_found(),
+    // コメントの作成中に Todo が削除された
+    sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => todo_not_found(),
+    err => err.into(),
+  }
+}
+
+// 自分のコメントは EditTodos で足りる。他のユーザーのコメントは owner・admin だけが編集・削除できる
+fn ensure_can_modify(tenant: Tenant, user_id: Uuid, comment: &Comment) -> Result<(), AppError> {
+  if comment.author_id == Some(user_id) {
+    return Ok(());
+  }
+  authorize(tenant.role, Permission::ModerateComments)
+}
+
+impl<T, R, U> CommentUsecase<T, R, U>
+where
+  T: CommentRepository + Send + Sync + Clone,
+  R: TodoRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn ensure_todo(&self, tenant: Tenant, todo_id: Uuid) -> Result<(), AppError> {
+    match self.todo_repository.find_by_id(tenant, todo_id).await? {
+      Some(_) => Ok(()),
+      None => Err(todo_not_found()),
+    }
+  }
+
+  async fn find_comment(&self, tenant: Tenant, todo_id: Uuid, id: Uuid) -> Result<Comment, AppError> {
+    self.ensure_todo(tenant, todo_id).await?;
+    self.repository
+      .find_by_id(id)
+      .await?
+      .filter(|comment| comment.todo_id == todo_id)
+      .ok_or_else(comment_not_found)
+  }
+}
+
+#[async_trait]
+pub trait CommentService {
+  async fn get_comments(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<Comment>, AppError>;
+  async fn get_comment(&self, tenant: Tenant, todo_id: Uuid, id: Uuid) -> Result<Comment, AppError>;
+  // 古い順。最新の本文はコメント自体の body
+  async fn get_comment_revisions(&self, tenant: Tenant, todo_id: Uuid, id: Uuid) -> Result<Vec<CommentRevision>, AppError>;
+  // user_id はログイン中のユーザー。投稿者として記録する
+  async fn create_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, body: String) -> Result<Comment, AppError>;
+  async fn update_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, id: Uuid, body: String) -> Result<Comment, AppError>;
+  async fn delete_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T, R, U> CommentService for CommentUsecase<T, R, U>
+where
+  T: CommentRepository + Send + Sync + Clone,
+  R: TodoRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn get_comments(&self, tenant: Tenant, todo_id: Uuid) -> Result<Vec<Comment>, AppError> {
+    self.ensure_todo(tenant, todo_id).await?;
+    Ok(self.repository.find_by_todo(todo_id).await?)
+  }
+
+  async fn get_comment(&self, tenant: Tenant, todo_id: Uuid, id: Uuid) -> Result<Comment, AppError> {
+    self.find_comment(tenant, todo_id, id).await
+  }
+
+  async fn get_comment_revisions(&self, tenant: Tenant, todo_id: Uuid, id: Uuid) -> Result<Vec<CommentRevision>, AppError> {
+    let comment = self.find_comment(tenant, todo_id, id).await?;
+    Ok(self.repository.find_revisions(comment.id).await?)
+  }
+
+  async fn create_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, body: String) -> Result<Comment, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    self.ensure_todo(tenant, todo_id).await?;
+    let author = self.user_repository
+      .find_by_id(user_id)
+      .await?
+      .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    let new_comment = Comment::new(todo_id, author.id, author.name, body);
+    self.repository.create(new_comment).await.map_err(write_error)
+  }
+
+  async fn update_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, id: Uuid, body: String) -> Result<Comment, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let mut comment = self.find_comment(tenant, todo_id, id).await?;
+    ensure_can_modify(tenant, user_id, &comment)?;
+    // 本文が変わらない編集は履歴に残さない
+    if comment.body == body {
+      return Ok(comment);
+    }
+    comment.body = body;
+    self.repository.update(comment).await.map_err(write_error)
+  }
+
+  async fn delete_comment(&self, tenant: Tenant, user_id: Uuid, todo_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let comment = self.find_comment(tenant, todo_id, id).await?;
+    ensure_can_modify(tenant, user_id, &comment)?;
+    self.repository.delete(comment.id).await.map_err(write_error)
+  }
+}
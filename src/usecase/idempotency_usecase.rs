@@ -0,0 +1,71 @@
+use crate::domain::error::AppError;
+use crate::domain::models::idempotency::StoredResponse;
+use crate::domain::repositories::idempotency_repository::IdempotencyRepository;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+
+// Idempotency-Key 付きのリクエストを処理してよいかの判定結果
+#[derive(Debug, Clone)]
+pub enum IdempotencyOutcome {
+  // 初めてのキー。処理してレスポンスを記録する
+  Proceed,
+  // 同じリクエストの再送。記録したレスポンスを返す
+  Replay(StoredResponse),
+}
+
+#[derive(Clone)]
+pub struct IdempotencyUsecase<T: IdempotencyRepository + Clone> {
+  repository: T,
+  // キーを記録しておく期間
+  ttl: Duration,
+  // 処理中のキーを他のリクエストに渡さない期間。過ぎたものは中断されたとみなし、再送で処理し直す
+  lease: Duration,
+}
+
+impl<T: IdempotencyRepository + Clone> IdempotencyUsecase<T> {
+  pub fn new(repository: T, ttl: Duration, lease: Duration) -> Self {
+    Self { repository, ttl, lease }
+  }
+}
+
+#[async_trait]
+pub trait IdempotencyService {
+  // キーはユーザー（user_id）ごとに別々に扱う。
+  // 同じキーのリクエストが処理中なら Conflict、別の内容のリクエストに使われていれば Validation
+  async fn begin(&self, user_id: Uuid, key: &str, request_hash: &str) -> Result<IdempotencyOutcome, AppError>;
+  async fn complete(&self, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<(), AppError>;
+  async fn release(&self, user_id: Uuid, key: &str) -> Result<(), AppError>;
+  // 期限切れのキーを削除する。定期ジョブから呼ばれる
+  async fn purge_expired(&self) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl<T: IdempotencyRepository + Send + Sync + Clone> IdempotencyService for IdempotencyUsecase<T> {
+  async fn begin(&self, user_id: Uuid, key: &str, request_hash: &str) -> Result<IdempotencyOutcome, AppError> {
+    let now = Utc::now();
+    let Some(record) = self.repository.claim(user_id, key, request_hash, now - self.ttl, now + self.lease).await? else {
+      return Ok(IdempotencyOutcome::Proceed);
+    };
+    if record.request_hash != request_hash {
+      return Err(AppError::Validation("Idempotency-Key has already been used for a different request".to_string()));
+    }
+    match record.response() {
+      Some(response) => Ok(IdempotencyOutcome::Replay(response)),
+      None => Err(AppError::Conflict("A request with this Idempotency-Key is still in progress".to_string())),
+    }
+  }
+
+  async fn complete(&self, user_id: Uuid, key: &str, response: &StoredResponse) -> Result<(), AppError> {
+    Ok(self.repository.complete(user_id, key, response).await?)
+  }
+
+  async fn release(&self, user_id: Uuid, key: &str) -> Result<(), AppError> {
+    Ok(self.repository.release(user_id, key).await?)
+  }
+
+  async fn purge_expired(&self) -> Result<u64, AppError> {
+    Ok(self.repository.delete_expired(Utc::now() - self.ttl).await?)
+  }
+}
@@ -1,5 +1,11 @@
-use crate::domain::models::invoice::Invoice;
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::invoice::{validate_amount, Invoice, InvoicePatch};
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::policy::{authorize, Permission};
 use crate::domain::repositories::invoice_repository::InvoiceRepository;
+use crate::domain::validation::ValidationErrors;
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -15,41 +21,99 @@ impl<T: InvoiceRepository + Clone> InvoiceUsecase<T> {
   }
 }
 
+fn invoice_not_found() -> AppError {
+  AppError::NotFound("Invoice not found".to_string())
+}
+
+// If-Match で指定された版数と一致しない、または読み込んだ後に他で更新された
+fn version_mismatch() -> AppError {
+  AppError::PreconditionFailed("Invoice has been modified".to_string())
+}
+
+impl<T: InvoiceRepository + Send + Sync + Clone> InvoiceUsecase<T> {
+  // 版数つきの更新が対象なしで終わった場合に、削除されたのか他で更新されたのかを見分ける
+  async fn update_failure(&self, tenant: Tenant, id: Uuid, err: sqlx::Error) -> AppError {
+    match err {
+      sqlx::Error::RowNotFound => match self.repository.find_by_id(tenant, id).await {
+        Ok(Some(_)) => version_mismatch(),
+        Ok(None) => invoice_not_found(),
+        Err(err) => err.into(),
+      },
+      err => err.into(),
+    }
+  }
+}
+
 #[async_trait]
 pub trait InvoiceService {
-  async fn get_all_invoices(&self) -> Result<Vec<Invoice>, sqlx::Error>;
-  async fn get_invoice_by_id(&self, id: Uuid) -> Result<Option<Invoice>, sqlx::Error>;
-  async fn create_invoice(&self, amount: i32) -> Result<Invoice, sqlx::Error>;
-  async fn update_invoice(&self, id: Uuid, amount: i32, paid: bool) -> Result<Invoice, sqlx::Error>;
-  async fn delete_invoice(&self, id: Uuid) -> Result<(), sqlx::Error>;
+  async fn get_all_invoices(&self, tenant: Tenant, page: PageRequest) -> Result<Page<Invoice>, AppError>;
+  async fn get_invoice_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Invoice>, AppError>;
+  async fn create_invoice(&self, tenant: Tenant, amount: i32, audit: &AuditContext) -> Result<Invoice, AppError>;
+  // expected_version を指定した場合、現在の版数と異なれば PreconditionFailed
+  async fn update_invoice(&self, tenant: Tenant, id: Uuid, amount: i32, paid: bool, expected_version: Option<i64>, audit: &AuditContext) -> Result<Invoice, AppError>;
+  // patch に含まれる項目だけを変更する
+  async fn patch_invoice(&self, tenant: Tenant, id: Uuid, patch: InvoicePatch, expected_version: Option<i64>, audit: &AuditContext) -> Result<Invoice, AppError>;
+  // ゴミ箱に移動する
+  async fn delete_invoice(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), AppError>;
+  async fn restore_invoice(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Invoice, AppError>;
 }
 
 #[async_trait]
 impl<T: InvoiceRepository + Send + Sync + Clone> InvoiceService for InvoiceUsecase<T> {
-  async fn get_all_invoices(&self) -> Result<Vec<Invoice>, sqlx::Error> {
-    self.repository.find_all().await
+  async fn get_all_invoices(&self, tenant: Tenant, page: PageRequest) -> Result<Page<Invoice>, AppError> {
+    Ok(self.repository.find_all(tenant, page).await?)
   }
 
-  async fn get_invoice_by_id(&self, id: Uuid) -> Result<Option<Invoice>, sqlx::Error> {
-    self.repository.find_by_id(id).await
+  async fn get_invoice_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Invoice>, AppError> {
+    Ok(self.repository.find_by_id(tenant, id).await?)
   }
 
-  async fn create_invoice(&self, amount: i32) -> Result<Invoice, sqlx::Error> {
-    let new_invoice = Invoice::new(amount);
-    self.repository.create(new_invoice).await
+  async fn create_invoice(&self, tenant: Tenant, amount: i32, audit: &AuditContext) -> Result<Invoice, AppError> {
+    authorize(tenant.role, Permission::ManageInvoices)?;
+    let new_invoice = Invoice::new(amount)?;
+    Ok(self.repository.create(tenant, new_invoice, audit).await?)
   }
 
-  async fn update_invoice(&self, id: Uuid, amount: i32, paid: bool) -> Result<Invoice, sqlx::Error> {
-    let existing_invoice = self.repository.find_by_id(id).await?;
-    if let Some(mut invoice) = existing_invoice {
-      invoice.amount = amount;
-      invoice.paid = paid;
-      return self.repository.update(invoice).await;
+  async fn update_invoice(&self, tenant: Tenant, id: Uuid, amount: i32, paid: bool, expected_version: Option<i64>, audit: &AuditContext) -> Result<Invoice, AppError> {
+    authorize(tenant.role, Permission::ManageInvoices)?;
+    let mut invoice = self.repository.find_by_id(tenant, id).await?.ok_or_else(invoice_not_found)?;
+    if expected_version.is_some_and(|version| version != invoice.version) {
+      return Err(version_mismatch());
     }
-    Err(sqlx::Error::RowNotFound)
+    let mut errors = ValidationErrors::new();
+    validate_amount(&mut errors, amount);
+    errors.into_result()?;
+    invoice.amount = amount;
+    invoice.paid = paid;
+    match self.repository.update(tenant, invoice, audit).await {
+      Ok(invoice) => Ok(invoice),
+      Err(err) => Err(self.update_failure(tenant, id, err).await),
+    }
+  }
+
+  async fn patch_invoice(&self, tenant: Tenant, id: Uuid, patch: InvoicePatch, expected_version: Option<i64>, audit: &AuditContext) -> Result<Invoice, AppError> {
+    authorize(tenant.role, Permission::ManageInvoices)?;
+    patch.validate()?;
+    let invoice = self.repository.find_by_id(tenant, id).await?.ok_or_else(invoice_not_found)?;
+    if expected_version.is_some_and(|version| version != invoice.version) {
+      return Err(version_mismatch());
+    }
+    if patch.is_empty() {
+      return Ok(invoice);
+    }
+    match self.repository.patch(tenant, id, invoice.version, &patch, audit).await {
+      Ok(invoice) => Ok(invoice),
+      Err(err) => Err(self.update_failure(tenant, id, err).await),
+    }
+  }
+
+  async fn delete_invoice(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), AppError> {
+    authorize(tenant.role, Permission::ManageInvoices)?;
+    self.repository.delete(tenant, id, audit).await.map_err(|err| AppError::from_sqlx(err, "Invoice not found"))
   }
 
-  async fn delete_invoice(&self, id: Uuid) -> Result<(), sqlx::Error> {
-    self.repository.delete(id).await
+  async fn restore_invoice(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Invoice, AppError> {
+    authorize(tenant.role, Permission::ManageInvoices)?;
+    self.repository.restore(tenant, id, audit).await.map_err(|err| AppError::from_sqlx(err, "Invoice not found in trash"))
   }
 }
@@ -1,2 +1,17 @@
 pub mod todo_usecase;
-pub mod invoice_usecase;
\ No newline at end of file
+pub mod invoice_usecase;
+pub mod api_key_usecase;
+pub mod assignment_usecase;
+pub mod attachment_usecase;
+pub mod audit_usecase;
+pub mod auth_usecase;
+pub mod comment_usecase;
+pub mod idempotency_usecase;
+pub mod oidc_usecase;
+pub mod organization_usecase;
+pub mod project_usecase;
+pub mod saved_filter_usecase;
+pub mod secret_token;
+pub mod share_usecase;
+pub mod trash_usecase;
+pub mod user_usecase;
\ No newline at end of file
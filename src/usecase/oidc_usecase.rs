@@ -0,0 +1,638 @@
+use crate::domain::error::AppError;
+use crate::domain::models::oidc::{GroupRoleMapping, IdentityClaims, LoginRedirect, LoginState, LOGIN_STATE_TTL};
+use crate::domain::models::user::{User, NAME_MAX_LENGTH};
+use crate::domain::repositories::identity_provider::IdentityProvider;
+use crate::domain::repositories::oidc_repository::OidcRepository;
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::usecase::secret_token;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+
+// IdP は設定で切り替えられるよう（テストではモックにできるよう）、トレイトオブジェクトで保持する
+#[derive(Clone)]
+pub struct OidcUsecase<R: OidcRepository + Clone, U: UserRepository + Clone, O: OrganizationRepository + Clone> {
+  repository: R,
+  user_repository: U,
+  organization_repository: O,
+  provider: Arc<dyn IdentityProvider>,
+  group_role_mappings: Vec<GroupRoleMapping>,
+}
+
+impl<R: OidcRepository + Clone, U: UserRepository + Clone, O: OrganizationRepository + Clone> OidcUsecase<R, U, O> {
+  pub fn new(
+    repository: R,
+    user_repository: U,
+    organization_repository: O,
+    provider: Arc<dyn IdentityProvider>,
+    group_role_mappings: Vec<GroupRoleMapping>,
+  ) -> Self {
+    Self { repository, user_repository, organization_repository, provider, group_role_mappings }
+  }
+}
+
+fn invalid_login_state() -> AppError {
+  AppError::Unauthorized("Invalid or expired login state".to_string())
+}
+
+// PKCE の S256: code_verifier の SHA-256 を base64url（パディングなし）にした値
+fn code_challenge(code_verifier: &str) -> String {
+  URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+impl<R, U, O> OidcUsecase<R, U, O>
+where
+  R: OidcRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+  O: OrganizationRepository + Send + Sync + Clone,
+{
+  // IdP のアカウントに対応するユーザーを探し、なければ新しく作る（IdP がメールアドレスを確認済みの場合だけ）。
+  // パスワードで登録したユーザーのメールアドレスは確認していないので、同じメールアドレスでも自動では対応付けない。
+  // 先に他人のアドレスで登録しておけば、その人の IdP のアカウントと組織の役割を乗っ取れてしまう
+  async fn provision_user(&self, claims: &IdentityClaims) -> Result<Uuid, AppError> {
+    let issuer = self.provider.issuer();
+    if let Some(user_id) = self.repository.find_user_id_by_identity(issuer, &claims.subject).await? {
+      return Ok(user_id);
+    }
+    let email = claims
+      .email
+      .as_deref()
+      .map(str::trim)
+      .filter(|email| !email.is_empty())
+      .ok_or_else(|| AppError::Unauthorized("The identity provider did not return an email address".to_string()))?;
+    // 確認されていないメールアドレスでは、他人宛ての招待を受け入れられるユーザーを作れてしまう
+    if !claims.email_verified {
+      return Err(AppError::Forbidden("The email address is not verified by the identity provider".to_string()));
+    }
+
+    if self.user_repository.find_credentials_by_email(email).await?.is_some() {
+      return Err(AppError::Conflict(
+        "A user with this email already exists. Sign in and link the identity provider account to it with POST /api/auth/oidc/link".to_string()
+      ));
+    }
+
+    let name = claims
+      .name
+      .as_deref()
+      .map(str::trim)
+      .filter(|name| !name.is_empty())
+      .unwrap_or_else(|| email.split('@').next().unwrap_or(email));
+    let name: String = name.chars().take(NAME_MAX_LENGTH).collect();
+    let user = self.repository.create_user_with_identity(User::new(name, email.to_string()), issuer, &claims.subject).await?;
+    Ok(user.id)
+  }
+
+  // ログイン中のユーザーが始めた対応付け。IdP のアカウントが他のユーザーに対応付けられている場合は拒否する
+  async fn link_user(&self, user_id: Uuid, claims: &IdentityClaims) -> Result<Uuid, AppError> {
+    let issuer = self.provider.issuer();
+    match self.repository.find_user_id_by_identity(issuer, &claims.subject).await? {
+      Some(linked_user_id) if linked_user_id == user_id => {}
+      Some(_) => {
+        return Err(AppError::Conflict("The identity provider account is already linked to another user".to_string()));
+      }
+      None => self.repository.link_identity(user_id, issuer, &claims.subject).await?,
+    }
+    Ok(user_id)
+  }
+}
+
+#[async_trait]
+pub trait OidcService {
+  // state・nonce・code_verifier を保存し、ユーザーをリダイレクトする IdP の URL と state を返す。
+  // link_user_id を指定した場合は、ログインの代わりにそのユーザーに IdP のアカウントを対応付ける
+  async fn begin_login(&self, link_user_id: Option<Uuid>) -> Result<LoginRedirect, AppError>;
+  // IdP から戻った認可コードを検証してユーザーを決め（初回は作成し）、グループに合わせて組織の役割を与える・外す。
+  // browser_state はログインを始めたブラウザのクッキーに入れた state で、state と一致しなければ拒否する
+  async fn complete_login(&self, code: &str, state: &str, browser_state: Option<&str>) -> Result<Uuid, AppError>;
+}
+
+#[async_trait]
+impl<R, U, O> OidcService for OidcUsecase<R, U, O>
+where
+  R: OidcRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+  O: OrganizationRepository + Send + Sync + Clone,
+{
+  async fn begin_login(&self, link_user_id: Option<Uuid>) -> Result<LoginRedirect, AppError> {
+    let now = Utc::now();
+    // 戻ってこなかったログインの state を片付ける
+    self.repository.delete_expired_login_states(now).await?;
+
+    let state = secret_token::generate();
+    let login_state = LoginState {
+      state_hash: secret_token::hash(&state),
+      code_verifier: secret_token::generate(),
+      nonce: secret_token::generate(),
+      link_user_id,
+      expires_at: now + LOGIN_STATE_TTL,
+    };
+    let authorization_url = self.provider.authorization_url(&state, &login_state.nonce, &code_challenge(&login_state.code_verifier));
+    self.repository.create_login_state(login_state).await?;
+    Ok(LoginRedirect { authorization_url, state })
+  }
+
+  async fn complete_login(&self, code: &str, state: &str, browser_state: Option<&str>) -> Result<Uuid, AppError> {
+    // 他人が始めたログインのコールバックを踏ませて、その人のアカウントでログインさせる攻撃（ログイン CSRF）を防ぐ。
+    // ハッシュどうしを比べ、一致するまでの時間から state を推測されないようにする
+    if browser_state.map(secret_token::hash) != Some(secret_token::hash(state)) {
+      return Err(invalid_login_state());
+    }
+    let login_state = self.repository
+      .take_login_state(&secret_token::hash(state))
+      .await?
+      .ok_or_else(invalid_login_state)?;
+    if login_state.expires_at <= Utc::now() {
+      return Err(invalid_login_state());
+    }
+
+    let claims = self.provider.exchange_code(code, &login_state.code_verifier, &login_state.nonce).await?;
+    let user_id = match login_state.link_user_id {
+      Some(user_id) => self.link_user(user_id, &claims).await?,
+      None => self.provision_user(&claims).await?,
+    };
+
+    // グループから外れたユーザーは、次のログインで役割を下げる（どのグループにも属さなければ組織から外す）
+    for (organization_id, role) in GroupRoleMapping::resolve(&self.group_role_mappings, &claims.groups) {
+      // 設定の誤りでログイン自体を失敗させない
+      if let Err(err) = self.organization_repository.sync_role(organization_id, user_id, role).await {
+        let role = role.map_or("no", |role| role.as_str());
+        warn!("failed to sync {} role in organization {} for user {}: {}", role, organization_id, user_id, err);
+      }
+    }
+    Ok(user_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::models::organization::{Invitation, Member, MembershipRole, Organization, UserOrganization};
+  use crate::domain::models::user::EmailVerification;
+  use crate::domain::repositories::identity_provider::IdentityProviderError;
+  use chrono::{DateTime, Duration};
+  use std::sync::Mutex;
+  use url::Url;
+
+  const ISSUER: &str = "https://idp.example.com";
+  const VALID_CODE: &str = "valid-code";
+
+  #[derive(Default)]
+  struct Store {
+    login_states: Vec<LoginState>,
+    users: Vec<User>,
+    identities: Vec<(String, String, Uuid)>,
+    organizations: Vec<Organization>,
+    memberships: Vec<(Uuid, Uuid, MembershipRole)>,
+    invitations: Vec<Invitation>,
+  }
+
+  impl Store {
+    fn membership_index(&self, organization_id: Uuid, user_id: Uuid) -> Option<usize> {
+      self.memberships.iter().position(|(o, u, _)| *o == organization_id && *u == user_id)
+    }
+
+    fn is_last_owner(&self, organization_id: Uuid, user_id: Uuid) -> bool {
+      let owners: Vec<Uuid> = self.memberships
+        .iter()
+        .filter(|(o, _, role)| *o == organization_id && *role == MembershipRole::Owner)
+        .map(|(_, u, _)| *u)
+        .collect();
+      owners == [user_id]
+    }
+
+    // 組織が保存されていない所属（GroupRoleMapping だけで指定した組織）は含めない
+    fn user_organizations(&self, user_id: Uuid) -> Vec<UserOrganization> {
+      let mut organizations: Vec<UserOrganization> = self.memberships
+        .iter()
+        .filter(|(_, u, _)| *u == user_id)
+        .filter_map(|(o, _, role)| {
+          let organization = self.organizations.iter().find(|organization| organization.id == *o)?;
+          Some(UserOrganization { organization: organization.clone(), role: *role })
+        })
+        .collect();
+      organizations.sort_by_key(|membership| membership.organization.personal_user_id.is_none());
+      organizations
+    }
+
+    fn member(&self, organization_id: Uuid, user_id: Uuid) -> Option<Member> {
+      let (_, _, role) = self.memberships[self.membership_index(organization_id, user_id)?];
+      let user = self.users.iter().find(|user| user.id == user_id)?;
+      Some(Member { user_id, name: user.name.clone(), email: user.email.clone(), role, created_at: user.created_at })
+    }
+  }
+
+  // 3 つのリポジトリをまとめてメモリ上で扱う
+  #[derive(Clone, Default)]
+  struct MemoryRepository(Arc<Mutex<Store>>);
+
+  #[async_trait]
+  impl OidcRepository for MemoryRepository {
+    async fn create_login_state(&self, state: LoginState) -> Result<(), sqlx::Error> {
+      self.0.lock().unwrap().login_states.push(state);
+      Ok(())
+    }
+
+    async fn take_login_state(&self, state_hash: &str) -> Result<Option<LoginState>, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let index = store.login_states.iter().position(|state| state.state_hash == state_hash);
+      Ok(index.map(|index| store.login_states.remove(index)))
+    }
+
+    async fn delete_expired_login_states(&self, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let before = store.login_states.len();
+      store.login_states.retain(|state| state.expires_at > now);
+      Ok((before - store.login_states.len()) as u64)
+    }
+
+    async fn find_user_id_by_identity(&self, issuer: &str, subject: &str) -> Result<Option<Uuid>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      Ok(store.identities.iter().find(|(i, s, _)| i == issuer && s == subject).map(|(_, _, user_id)| *user_id))
+    }
+
+    async fn create_user_with_identity(&self, user: User, issuer: &str, subject: &str) -> Result<User, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      store.identities.push((issuer.to_string(), subject.to_string(), user.id));
+      store.users.push(user.clone());
+      Ok(user)
+    }
+
+    async fn link_identity(&self, user_id: Uuid, issuer: &str, subject: &str) -> Result<(), sqlx::Error> {
+      self.0.lock().unwrap().identities.push((issuer.to_string(), subject.to_string(), user_id));
+      Ok(())
+    }
+  }
+
+  #[async_trait]
+  impl UserRepository for MemoryRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, sqlx::Error> {
+      Ok(self.0.lock().unwrap().users.iter().find(|user| user.id == id).cloned())
+    }
+
+    async fn find_members_by_ids(&self, organization_id: Uuid, ids: &[Uuid]) -> Result<Vec<User>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      Ok(store.users
+        .iter()
+        .filter(|user| ids.contains(&user.id) && store.membership_index(organization_id, user.id).is_some())
+        .cloned()
+        .collect())
+    }
+
+    async fn create_with_password(&self, user: User, _password_hash: &str) -> Result<User, sqlx::Error> {
+      self.0.lock().unwrap().users.push(user.clone());
+      Ok(user)
+    }
+
+    async fn find_credentials_by_email(&self, email: &str) -> Result<Option<(Uuid, Option<String>)>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      Ok(store.users.iter().find(|user| user.email.eq_ignore_ascii_case(email)).map(|user| (user.id, None)))
+    }
+
+    async fn update(&self, user: User) -> Result<User, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let stored = store.users.iter_mut().find(|stored| stored.id == user.id).ok_or(sqlx::Error::RowNotFound)?;
+      *stored = user.clone();
+      Ok(user)
+    }
+
+    // メールアドレスの変更はこのテストでは扱わない。確認待ちの変更は保存せず、確認は常に失敗する
+    async fn save_email_verification(&self, _verification: &EmailVerification) -> Result<(), sqlx::Error> {
+      Ok(())
+    }
+
+    async fn confirm_email(&self, _user_id: Uuid, _token_hash: &str, _now: DateTime<Utc>) -> Result<User, sqlx::Error> {
+      Err(sqlx::Error::RowNotFound)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      store.users.retain(|user| user.id != id);
+      store.identities.retain(|(_, _, user_id)| *user_id != id);
+      store.memberships.retain(|(_, user_id, _)| *user_id != id);
+      Ok(())
+    }
+  }
+
+  #[async_trait]
+  impl OrganizationRepository for MemoryRepository {
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, sqlx::Error> {
+      Ok(self.0.lock().unwrap().user_organizations(user_id))
+    }
+
+    async fn find_membership(&self, organization_id: Uuid, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error> {
+      let organizations = self.0.lock().unwrap().user_organizations(user_id);
+      Ok(organizations.into_iter().find(|membership| membership.organization.id == organization_id))
+    }
+
+    async fn find_personal(&self, user_id: Uuid) -> Result<Option<UserOrganization>, sqlx::Error> {
+      let organizations = self.0.lock().unwrap().user_organizations(user_id);
+      Ok(organizations.into_iter().find(|membership| membership.organization.personal_user_id == Some(user_id)))
+    }
+
+    async fn create(&self, organization: Organization, owner_id: Uuid) -> Result<Organization, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      store.organizations.push(organization.clone());
+      store.memberships.push((organization.id, owner_id, MembershipRole::Owner));
+      Ok(organization)
+    }
+
+    async fn find_members(&self, organization_id: Uuid) -> Result<Vec<Member>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      Ok(store.memberships
+        .iter()
+        .filter(|(o, _, _)| *o == organization_id)
+        .filter_map(|(_, user_id, _)| store.member(organization_id, *user_id))
+        .collect())
+    }
+
+    async fn delete_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let index = store.membership_index(organization_id, user_id).ok_or(sqlx::Error::RowNotFound)?;
+      if store.is_last_owner(organization_id, user_id) {
+        return Ok(false);
+      }
+      store.memberships.remove(index);
+      Ok(true)
+    }
+
+    async fn update_member_role(&self, organization_id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<Option<Member>, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let index = store.membership_index(organization_id, user_id).ok_or(sqlx::Error::RowNotFound)?;
+      if role != MembershipRole::Owner && store.is_last_owner(organization_id, user_id) {
+        return Ok(None);
+      }
+      store.memberships[index].2 = role;
+      Ok(store.member(organization_id, user_id))
+    }
+
+    async fn create_invitation(&self, invitation: Invitation) -> Result<Invitation, sqlx::Error> {
+      self.0.lock().unwrap().invitations.push(invitation.clone());
+      Ok(invitation)
+    }
+
+    async fn find_pending_invitations(&self, organization_id: Uuid) -> Result<Vec<Invitation>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      let mut invitations: Vec<Invitation> = store.invitations
+        .iter()
+        .filter(|invitation| invitation.organization_id == organization_id && invitation.accepted_at.is_none())
+        .cloned()
+        .collect();
+      invitations.sort_by_key(|invitation| std::cmp::Reverse(invitation.created_at));
+      Ok(invitations)
+    }
+
+    async fn find_invitation_by_hash(&self, token_hash: &str) -> Result<Option<Invitation>, sqlx::Error> {
+      let store = self.0.lock().unwrap();
+      Ok(store.invitations.iter().find(|invitation| invitation.token_hash == token_hash).cloned())
+    }
+
+    async fn delete_invitation(&self, organization_id: Uuid, id: Uuid) -> Result<(), sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let index = store.invitations
+        .iter()
+        .position(|invitation| invitation.id == id && invitation.organization_id == organization_id && invitation.accepted_at.is_none())
+        .ok_or(sqlx::Error::RowNotFound)?;
+      store.invitations.remove(index);
+      Ok(())
+    }
+
+    async fn accept_invitation(&self, id: Uuid, user_id: Uuid, role: MembershipRole) -> Result<bool, sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let Some(invitation) = store.invitations.iter_mut().find(|invitation| invitation.id == id && invitation.accepted_at.is_none()) else {
+        return Ok(false);
+      };
+      invitation.accepted_at = Some(Utc::now());
+      let organization_id = invitation.organization_id;
+      if store.membership_index(organization_id, user_id).is_none() {
+        store.memberships.push((organization_id, user_id, role));
+      }
+      Ok(true)
+    }
+
+    // OrganizationRepositoryImpl と同じく owner は変えず、外さない
+    async fn sync_role(&self, organization_id: Uuid, user_id: Uuid, role: Option<MembershipRole>) -> Result<(), sqlx::Error> {
+      let mut store = self.0.lock().unwrap();
+      let index = store.membership_index(organization_id, user_id);
+      match (index, role) {
+        (Some(index), _) if store.memberships[index].2 == MembershipRole::Owner => {}
+        (Some(index), Some(role)) => store.memberships[index].2 = role,
+        (Some(index), None) => {
+          store.memberships.remove(index);
+        }
+        (None, Some(role)) => store.memberships.push((organization_id, user_id, role)),
+        (None, None) => {}
+      }
+      Ok(())
+    }
+  }
+
+  impl MemoryRepository {
+    fn role(&self, organization_id: Uuid, user_id: Uuid) -> Option<MembershipRole> {
+      let store = self.0.lock().unwrap();
+      store.membership_index(organization_id, user_id).map(|index| store.memberships[index].2)
+    }
+  }
+
+  // 認可 URL に渡された nonce と code_challenge を覚え、認可コードの交換で IdP と同じように照合する
+  struct MockProvider {
+    claims: Mutex<IdentityClaims>,
+    requests: Mutex<Vec<(String, String)>>,
+  }
+
+  #[async_trait]
+  impl IdentityProvider for MockProvider {
+    fn issuer(&self) -> &str {
+      ISSUER
+    }
+
+    fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> String {
+      self.requests.lock().unwrap().push((nonce.to_string(), code_challenge.to_string()));
+      format!("{}/authorize?state={}", ISSUER, state)
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str, nonce: &str) -> Result<IdentityClaims, IdentityProviderError> {
+      if code != VALID_CODE {
+        return Err(IdentityProviderError::InvalidGrant("unknown code".to_string()));
+      }
+      let request = (nonce.to_string(), code_challenge(code_verifier));
+      if !self.requests.lock().unwrap().contains(&request) {
+        return Err(IdentityProviderError::InvalidGrant("PKCE or nonce mismatch".to_string()));
+      }
+      Ok(self.claims.lock().unwrap().clone())
+    }
+  }
+
+  struct Fixture {
+    usecase: OidcUsecase<MemoryRepository, MemoryRepository, MemoryRepository>,
+    repository: MemoryRepository,
+    provider: Arc<MockProvider>,
+    organization_id: Uuid,
+  }
+
+  fn claims(email: &str, email_verified: bool, groups: &[&str]) -> IdentityClaims {
+    IdentityClaims {
+      subject: format!("sub-{}", email),
+      email: Some(email.to_string()),
+      email_verified,
+      name: Some("Staff".to_string()),
+      groups: groups.iter().map(|group| group.to_string()).collect(),
+    }
+  }
+
+  fn fixture(claims: IdentityClaims) -> Fixture {
+    let repository = MemoryRepository::default();
+    let provider = Arc::new(MockProvider { claims: Mutex::new(claims), requests: Mutex::new(Vec::new()) });
+    let organization_id = Uuid::now_v7();
+    let mappings = GroupRoleMapping::parse_list(&format!(
+      "finance={0}:accountant,everyone={0}:member",
+      organization_id
+    ))
+    .unwrap();
+    let usecase = OidcUsecase::new(repository.clone(), repository.clone(), repository.clone(), provider.clone(), mappings);
+    Fixture { usecase, repository, provider, organization_id }
+  }
+
+  fn state_of(redirect: &LoginRedirect) -> String {
+    let url = Url::parse(&redirect.authorization_url).unwrap();
+    url.query_pairs().find(|(name, _)| name == "state").unwrap().1.into_owned()
+  }
+
+  async fn log_in(fixture: &Fixture) -> Result<Uuid, AppError> {
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+    fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await
+  }
+
+  #[tokio::test]
+  async fn begin_login_passes_the_returned_state_to_the_provider() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+    assert_eq!(state_of(&redirect), redirect.state);
+  }
+
+  #[tokio::test]
+  async fn complete_login_creates_the_user_once() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let user_id = log_in(&fixture).await.unwrap();
+    assert_eq!(log_in(&fixture).await.unwrap(), user_id);
+
+    let user = fixture.repository.find_by_id(user_id).await.unwrap().unwrap();
+    assert_eq!(user.email, "staff@example.com");
+    assert_eq!(user.name, "Staff");
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_a_state_from_another_browser() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+    let other = fixture.usecase.begin_login(None).await.unwrap();
+
+    let result = fixture.usecase.complete_login(VALID_CODE, &redirect.state, None).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    let result = fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&other.state)).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    // 拒否したコールバックでは state を消費しない
+    assert!(fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await.is_ok());
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_a_used_state() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+    assert!(fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await.is_ok());
+
+    let result = fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_an_expired_state() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+    fixture.repository.0.lock().unwrap().login_states[0].expires_at = Utc::now() - Duration::seconds(1);
+
+    let result = fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_an_invalid_code() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let redirect = fixture.usecase.begin_login(None).await.unwrap();
+
+    let result = fixture.usecase.complete_login("forged-code", &redirect.state, Some(&redirect.state)).await;
+    assert!(matches!(result, Err(AppError::Unauthorized(_))));
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_an_unverified_email() {
+    let fixture = fixture(claims("staff@example.com", false, &["everyone"]));
+    assert!(matches!(log_in(&fixture).await, Err(AppError::Forbidden(_))));
+    assert!(fixture.repository.0.lock().unwrap().users.is_empty());
+  }
+
+  #[tokio::test]
+  async fn complete_login_does_not_link_an_existing_user_by_email() {
+    let fixture = fixture(claims("Alice@Example.com", true, &["everyone"]));
+    fixture.repository.create_with_password(User::new("alice".to_string(), "alice@example.com".to_string()), "hash").await.unwrap();
+
+    assert!(matches!(log_in(&fixture).await, Err(AppError::Conflict(_))));
+    let store = fixture.repository.0.lock().unwrap();
+    assert!(store.identities.is_empty());
+    assert!(store.memberships.is_empty());
+  }
+
+  #[tokio::test]
+  async fn complete_login_links_the_user_who_started_the_link() {
+    let fixture = fixture(claims("alice@corp.example.com", false, &[]));
+    let alice = fixture.repository.create_with_password(User::new("alice".to_string(), "alice@example.com".to_string()), "hash").await.unwrap();
+
+    let redirect = fixture.usecase.begin_login(Some(alice.id)).await.unwrap();
+    assert_eq!(fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await.unwrap(), alice.id);
+    // 対応付けた後は IdP のアカウントでログインできる
+    assert_eq!(log_in(&fixture).await.unwrap(), alice.id);
+  }
+
+  #[tokio::test]
+  async fn complete_login_rejects_linking_an_identity_of_another_user() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let staff_id = log_in(&fixture).await.unwrap();
+    let alice = fixture.repository.create_with_password(User::new("alice".to_string(), "alice@example.com".to_string()), "hash").await.unwrap();
+
+    let redirect = fixture.usecase.begin_login(Some(alice.id)).await.unwrap();
+    let result = fixture.usecase.complete_login(VALID_CODE, &redirect.state, Some(&redirect.state)).await;
+    assert!(matches!(result, Err(AppError::Conflict(_))));
+    assert_eq!(log_in(&fixture).await.unwrap(), staff_id);
+  }
+
+  #[tokio::test]
+  async fn complete_login_syncs_roles_with_groups() {
+    let fixture = fixture(claims("staff@example.com", true, &["everyone", "finance"]));
+    let user_id = log_in(&fixture).await.unwrap();
+    assert_eq!(fixture.repository.role(fixture.organization_id, user_id), Some(MembershipRole::Accountant));
+
+    fixture.provider.claims.lock().unwrap().groups = vec!["everyone".to_string()];
+    log_in(&fixture).await.unwrap();
+    assert_eq!(fixture.repository.role(fixture.organization_id, user_id), Some(MembershipRole::Member));
+
+    fixture.provider.claims.lock().unwrap().groups = Vec::new();
+    log_in(&fixture).await.unwrap();
+    assert_eq!(fixture.repository.role(fixture.organization_id, user_id), None);
+  }
+
+  #[tokio::test]
+  async fn complete_login_keeps_owners_outside_of_groups() {
+    let fixture = fixture(claims("staff@example.com", true, &[]));
+    let user_id = log_in(&fixture).await.unwrap();
+    fixture.repository.0.lock().unwrap().memberships.push((fixture.organization_id, user_id, MembershipRole::Owner));
+
+    log_in(&fixture).await.unwrap();
+    assert_eq!(fixture.repository.role(fixture.organization_id, user_id), Some(MembershipRole::Owner));
+  }
+}
@@ -0,0 +1,213 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::{
+  Invitation, IssuedInvitation, Member, MembershipRole, Organization, Tenant, UserOrganization, INVITATION_TTL,
+};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::organization_repository::OrganizationRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::usecase::secret_token;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct OrganizationUsecase<O: OrganizationRepository + Clone, U: UserRepository + Clone> {
+  repository: O,
+  user_repository: U,
+}
+
+impl<O: OrganizationRepository + Clone, U: UserRepository + Clone> OrganizationUsecase<O, U> {
+  pub fn new(repository: O, user_repository: U) -> Self {
+    Self { repository, user_repository }
+  }
+}
+
+// 所属していない組織は存在しないものとして扱う
+fn organization_not_found() -> AppError {
+  AppError::NotFound("Organization not found".to_string())
+}
+
+fn member_not_found() -> AppError {
+  AppError::NotFound("Member not found".to_string())
+}
+
+// owner の役割を持つメンバー（になるメンバー）を扱えるのは owner だけ
+fn ensure_can_manage_owner(role: MembershipRole, member_role: MembershipRole) -> Result<(), AppError> {
+  if member_role == MembershipRole::Owner && role != MembershipRole::Owner {
+    return Err(AppError::Forbidden("Only an owner can change or remove another owner".to_string()));
+  }
+  Ok(())
+}
+
+fn invalid_invitation() -> AppError {
+  AppError::NotFound("Invitation not found".to_string())
+}
+
+impl<O, U> OrganizationUsecase<O, U>
+where
+  O: OrganizationRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn find_membership(&self, user_id: Uuid, organization_id: Uuid) -> Result<UserOrganization, AppError> {
+    self.repository.find_membership(organization_id, user_id).await?.ok_or_else(organization_not_found)
+  }
+
+  async fn ensure_manager(&self, user_id: Uuid, organization_id: Uuid) -> Result<UserOrganization, AppError> {
+    let membership = self.find_membership(user_id, organization_id).await?;
+    authorize(membership.role, Permission::ManageMembers)?;
+    Ok(membership)
+  }
+
+  async fn member_role(&self, organization_id: Uuid, member_id: Uuid) -> Result<MembershipRole, AppError> {
+    let membership = self.repository.find_membership(organization_id, member_id).await?.ok_or_else(member_not_found)?;
+    Ok(membership.role)
+  }
+}
+
+#[async_trait]
+pub trait OrganizationService {
+  // リクエストの対象となる組織を決める。organization_id を省略した場合は個人用の組織
+  async fn resolve_tenant(&self, user_id: Uuid, organization_id: Option<Uuid>) -> Result<Tenant, AppError>;
+  async fn get_organizations(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, AppError>;
+  // 作成したユーザーが owner になる
+  async fn create_organization(&self, user_id: Uuid, name: String) -> Result<UserOrganization, AppError>;
+  async fn get_members(&self, user_id: Uuid, organization_id: Uuid) -> Result<Vec<Member>, AppError>;
+  // ManageMembers が許可された役割は他のメンバーを、それ以外は自分だけを外せる。
+  // owner を外せるのは owner だけで、最後の owner は外せない
+  async fn remove_member(&self, user_id: Uuid, organization_id: Uuid, member_id: Uuid) -> Result<(), AppError>;
+  // ManageMembers が許可された役割だけが変えられる。owner にする・owner を変えるのは owner だけで、最後の owner は変えられない
+  async fn change_member_role(&self, user_id: Uuid, organization_id: Uuid, member_id: Uuid, role: MembershipRole) -> Result<Member, AppError>;
+  // 招待トークンは結果にだけ含まれ、保存されない
+  async fn create_invitation(&self, user_id: Uuid, organization_id: Uuid, email: String, role: MembershipRole) -> Result<IssuedInvitation, AppError>;
+  async fn get_invitations(&self, user_id: Uuid, organization_id: Uuid) -> Result<Vec<Invitation>, AppError>;
+  async fn revoke_invitation(&self, user_id: Uuid, organization_id: Uuid, invitation_id: Uuid) -> Result<(), AppError>;
+  // 招待されたメールアドレスのユーザーだけが受け入れられる
+  async fn accept_invitation(&self, user_id: Uuid, token: &str) -> Result<UserOrganization, AppError>;
+}
+
+#[async_trait]
+impl<O, U> OrganizationService for OrganizationUsecase<O, U>
+where
+  O: OrganizationRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn resolve_tenant(&self, user_id: Uuid, organization_id: Option<Uuid>) -> Result<Tenant, AppError> {
+    let membership = match organization_id {
+      Some(organization_id) => self.find_membership(user_id, organization_id).await?,
+      None => self.repository.find_personal(user_id).await?.ok_or_else(organization_not_found)?,
+    };
+    Ok(Tenant {
+      organization_id: membership.organization.id,
+      user_id,
+      role: membership.role,
+      project_id: None,
+    })
+  }
+
+  async fn get_organizations(&self, user_id: Uuid) -> Result<Vec<UserOrganization>, AppError> {
+    Ok(self.repository.find_by_user(user_id).await?)
+  }
+
+  async fn create_organization(&self, user_id: Uuid, name: String) -> Result<UserOrganization, AppError> {
+    let organization = self.repository.create(Organization::new(name), user_id).await?;
+    Ok(UserOrganization { organization, role: MembershipRole::Owner })
+  }
+
+  async fn get_members(&self, user_id: Uuid, organization_id: Uuid) -> Result<Vec<Member>, AppError> {
+    self.find_membership(user_id, organization_id).await?;
+    Ok(self.repository.find_members(organization_id).await?)
+  }
+
+  async fn remove_member(&self, user_id: Uuid, organization_id: Uuid, member_id: Uuid) -> Result<(), AppError> {
+    if member_id == user_id {
+      self.find_membership(user_id, organization_id).await?;
+    } else {
+      let membership = self.ensure_manager(user_id, organization_id).await?;
+      let member_role = self.member_role(organization_id, member_id).await?;
+      ensure_can_manage_owner(membership.role, member_role)?;
+    }
+    let removed = self.repository
+      .delete_member(organization_id, member_id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Member not found"))?;
+    if !removed {
+      return Err(AppError::Conflict("The last owner cannot leave the organization".to_string()));
+    }
+    Ok(())
+  }
+
+  async fn change_member_role(&self, user_id: Uuid, organization_id: Uuid, member_id: Uuid, role: MembershipRole) -> Result<Member, AppError> {
+    let membership = self.ensure_manager(user_id, organization_id).await?;
+    let member_role = self.member_role(organization_id, member_id).await?;
+    ensure_can_manage_owner(membership.role, member_role)?;
+    ensure_can_manage_owner(membership.role, role)?;
+    self.repository
+      .update_member_role(organization_id, member_id, role)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Member not found"))?
+      .ok_or_else(|| AppError::Conflict("The last owner cannot be given another role".to_string()))
+  }
+
+  async fn create_invitation(&self, user_id: Uuid, organization_id: Uuid, email: String, role: MembershipRole) -> Result<IssuedInvitation, AppError> {
+    let membership = self.ensure_manager(user_id, organization_id).await?;
+    if membership.organization.personal_user_id.is_some() {
+      return Err(AppError::Conflict("Personal organizations cannot have other members".to_string()));
+    }
+    if role == MembershipRole::Owner {
+      return Err(AppError::BadRequest("Invitations cannot grant the owner role".to_string()));
+    }
+
+    let token = secret_token::generate();
+    let now = Utc::now();
+    let invitation = Invitation {
+      id: Uuid::now_v7(),
+      organization_id,
+      email,
+      role,
+      token_hash: secret_token::hash(&token),
+      invited_by: Some(user_id),
+      expires_at: now + INVITATION_TTL,
+      accepted_at: None,
+      created_at: now,
+    };
+    let invitation = self.repository.create_invitation(invitation).await?;
+    Ok(IssuedInvitation { invitation, token })
+  }
+
+  async fn get_invitations(&self, user_id: Uuid, organization_id: Uuid) -> Result<Vec<Invitation>, AppError> {
+    self.ensure_manager(user_id, organization_id).await?;
+    Ok(self.repository.find_pending_invitations(organization_id).await?)
+  }
+
+  async fn revoke_invitation(&self, user_id: Uuid, organization_id: Uuid, invitation_id: Uuid) -> Result<(), AppError> {
+    self.ensure_manager(user_id, organization_id).await?;
+    self.repository
+      .delete_invitation(organization_id, invitation_id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Invitation not found"))
+  }
+
+  async fn accept_invitation(&self, user_id: Uuid, token: &str) -> Result<UserOrganization, AppError> {
+    let invitation = self.repository
+      .find_invitation_by_hash(&secret_token::hash(token))
+      .await?
+      .ok_or_else(invalid_invitation)?;
+    let user = self.user_repository.find_by_id(user_id).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    // 他のユーザー宛ての招待は、存在しない招待と区別しない
+    if !invitation.email.eq_ignore_ascii_case(&user.email) {
+      return Err(invalid_invitation());
+    }
+    if invitation.accepted_at.is_some() {
+      return Err(AppError::Conflict("Invitation has already been accepted".to_string()));
+    }
+    if invitation.expires_at <= Utc::now() {
+      return Err(AppError::Gone("Invitation has expired".to_string()));
+    }
+
+    if !self.repository.accept_invitation(invitation.id, user_id, invitation.role).await? {
+      return Err(AppError::Conflict("Invitation has already been accepted".to_string()));
+    }
+    self.find_membership(user_id, invitation.organization_id).await
+  }
+}
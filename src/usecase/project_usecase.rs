@@ -0,0 +1,91 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::project::Project;
+use crate::domain::models::workflow::Workflow;
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::project_repository::ProjectRepository;
+use async_trait::async_trait;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct ProjectUsecase<T: ProjectRepository + Clone> {
+  repository: T,
+}
+
+impl<T: ProjectRepository + Clone> ProjectUsecase<T> {
+  pub fn new(repository: T) -> Self {
+    Self { repository }
+  }
+}
+
+fn project_not_found() -> AppError {
+  AppError::NotFound("Project not found".to_string())
+}
+
+fn invalid_workflow(message: String) -> AppError {
+  AppError::BadRequest(format!("invalid workflow: {}", message))
+}
+
+#[async_trait]
+pub trait ProjectService {
+  async fn get_all_projects(&self, tenant: Tenant) -> Result<Vec<Project>, AppError>;
+  async fn get_project_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Project>, AppError>;
+  // workflow を省略した場合は既定のワークフロー（Backlog / Todo / In Progress / Review / Done）を使う
+  async fn create_project(&self, tenant: Tenant, name: String, workflow: Option<Workflow>) -> Result<Project, AppError>;
+  async fn update_project(&self, tenant: Tenant, id: Uuid, name: String, workflow: Workflow) -> Result<Project, AppError>;
+  async fn delete_project(&self, tenant: Tenant, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: ProjectRepository + Send + Sync + Clone> ProjectService for ProjectUsecase<T> {
+  async fn get_all_projects(&self, tenant: Tenant) -> Result<Vec<Project>, AppError> {
+    Ok(self.repository.find_all(tenant).await?)
+  }
+
+  async fn get_project_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Project>, AppError> {
+    Ok(self.repository.find_by_id(tenant, id).await?)
+  }
+
+  async fn create_project(&self, tenant: Tenant, name: String, workflow: Option<Workflow>) -> Result<Project, AppError> {
+    authorize(tenant.role, Permission::ManageProjects)?;
+    let workflow = workflow.unwrap_or_default();
+    workflow.validate().map_err(invalid_workflow)?;
+
+    let new_project = Project::new(name, workflow);
+    Ok(self.repository.create(tenant, new_project).await?)
+  }
+
+  async fn update_project(&self, tenant: Tenant, id: Uuid, name: String, workflow: Workflow) -> Result<Project, AppError> {
+    authorize(tenant.role, Permission::ManageProjects)?;
+    workflow.validate().map_err(invalid_workflow)?;
+
+    let mut project = self.repository.find_by_id(tenant, id).await?.ok_or_else(project_not_found)?;
+
+    let mut orphaned: Vec<String> = self.repository
+      .find_statuses_in_use(id)
+      .await?
+      .into_iter()
+      .filter(|status| workflow.status(status).is_none())
+      .collect();
+    if !orphaned.is_empty() {
+      orphaned.sort();
+      // 新しいワークフローに、Todo が使用中のステータスが含まれていない
+      return Err(AppError::Conflict(format!("statuses still used by todos: {}", orphaned.join(", "))));
+    }
+
+    project.name = name;
+    project.workflow = Json(workflow);
+    self.repository.update(tenant, project).await.map_err(|err| AppError::from_sqlx(err, "Project not found"))
+  }
+
+  async fn delete_project(&self, tenant: Tenant, id: Uuid) -> Result<(), AppError> {
+    authorize(tenant.role, Permission::ManageProjects)?;
+    self.repository.delete(tenant, id).await.map_err(|err| match err {
+      // Todo が残っているプロジェクトは削除できない
+      sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => AppError::Conflict("Project still has todos".to_string()),
+      err => AppError::from_sqlx(err, "Project not found"),
+    })
+  }
+}
@@ -0,0 +1,57 @@
+use crate::domain::error::AppError;
+use crate::domain::models::saved_filter::SavedFilter;
+use crate::domain::repositories::saved_filter_repository::SavedFilterRepository;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct SavedFilterUsecase<T: SavedFilterRepository + Clone> {
+  repository: T,
+}
+
+impl<T: SavedFilterRepository + Clone> SavedFilterUsecase<T> {
+  pub fn new(repository: T) -> Self {
+    Self { repository }
+  }
+}
+
+// user_id はリクエストしたユーザー。自分のフィルタだけを扱える
+#[async_trait]
+pub trait SavedFilterService {
+  async fn get_all_saved_filters(&self, user_id: Uuid) -> Result<Vec<SavedFilter>, AppError>;
+  async fn get_saved_filter_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<SavedFilter>, AppError>;
+  async fn create_saved_filter(&self, user_id: Uuid, name: String, query: String) -> Result<SavedFilter, AppError>;
+  async fn update_saved_filter(&self, user_id: Uuid, id: Uuid, name: String, query: String) -> Result<SavedFilter, AppError>;
+  async fn delete_saved_filter(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: SavedFilterRepository + Send + Sync + Clone> SavedFilterService for SavedFilterUsecase<T> {
+  async fn get_all_saved_filters(&self, user_id: Uuid) -> Result<Vec<SavedFilter>, AppError> {
+    Ok(self.repository.find_by_user(user_id).await?)
+  }
+
+  async fn get_saved_filter_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<SavedFilter>, AppError> {
+    Ok(self.repository.find_by_id(user_id, id).await?)
+  }
+
+  async fn create_saved_filter(&self, user_id: Uuid, name: String, query: String) -> Result<SavedFilter, AppError> {
+    let new_saved_filter = SavedFilter::new(user_id, name, query);
+    Ok(self.repository.create(new_saved_filter).await?)
+  }
+
+  async fn update_saved_filter(&self, user_id: Uuid, id: Uuid, name: String, query: String) -> Result<SavedFilter, AppError> {
+    let existing = self.repository.find_by_id(user_id, id).await?;
+    if let Some(mut saved_filter) = existing {
+      saved_filter.name = name;
+      saved_filter.query = query;
+      return self.repository.update(saved_filter).await.map_err(|err| AppError::from_sqlx(err, "Saved filter not found"));
+    }
+    Err(AppError::NotFound("Saved filter not found".to_string()))
+  }
+
+  async fn delete_saved_filter(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    self.repository.delete(user_id, id).await.map_err(|err| AppError::from_sqlx(err, "Saved filter not found"))
+  }
+}
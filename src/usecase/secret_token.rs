@@ -0,0 +1,14 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+// リフレッシュトークン・招待トークンなど、推測できない一度きりの値（256 ビット）
+pub fn generate() -> String {
+  let mut bytes = [0u8; 32];
+  OsRng.fill_bytes(&mut bytes);
+  hex::encode(bytes)
+}
+
+// 保存・照合に使うハッシュ。トークン自体は十分に長い乱数なのでソルトは不要
+pub fn hash(token: &str) -> String {
+  hex::encode(Sha256::digest(token.as_bytes()))
+}
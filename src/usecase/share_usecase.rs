@@ -0,0 +1,168 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::project::Project;
+use crate::domain::models::share::{Collaborator, IssuedShare, ProjectShare, SharePermission, SharedProject, SHARE_INVITATION_TTL};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::share_repository::ShareRepository;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::usecase::secret_token;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct ShareUsecase<S: ShareRepository + Clone, P: ProjectRepository + Clone, U: UserRepository + Clone> {
+  repository: S,
+  project_repository: P,
+  user_repository: U,
+}
+
+impl<S: ShareRepository + Clone, P: ProjectRepository + Clone, U: UserRepository + Clone> ShareUsecase<S, P, U> {
+  pub fn new(repository: S, project_repository: P, user_repository: U) -> Self {
+    Self { repository, project_repository, user_repository }
+  }
+}
+
+// 共有されていないプロジェクトは存在しないものとして扱う
+fn project_not_found() -> AppError {
+  AppError::NotFound("Project not found".to_string())
+}
+
+fn invalid_share() -> AppError {
+  AppError::NotFound("Share not found".to_string())
+}
+
+impl<S, P, U> ShareUsecase<S, P, U>
+where
+  S: ShareRepository + Send + Sync + Clone,
+  P: ProjectRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn find_project(&self, tenant: Tenant, project_id: Uuid) -> Result<Project, AppError> {
+    self.project_repository.find_by_id(tenant, project_id).await?.ok_or_else(project_not_found)
+  }
+
+  // 共有の作成・取り消しはプロジェクトの管理として扱う
+  async fn ensure_manager(&self, tenant: Tenant, project_id: Uuid) -> Result<Project, AppError> {
+    authorize(tenant.role, Permission::ManageProjects)?;
+    self.find_project(tenant, project_id).await
+  }
+}
+
+#[async_trait]
+pub trait ShareService {
+  // 共有されたプロジェクトとしてアクセスする際の対象。役割は共有の権限から決まる
+  async fn resolve_shared_tenant(&self, user_id: Uuid, project_id: Uuid) -> Result<Tenant, AppError>;
+  // email を省略した場合はリンクによる共有。トークンは結果にだけ含まれ、保存されない
+  async fn create_share(&self, tenant: Tenant, project_id: Uuid, permission: SharePermission, email: Option<String>) -> Result<IssuedShare, AppError>;
+  async fn get_shares(&self, tenant: Tenant, project_id: Uuid) -> Result<Vec<ProjectShare>, AppError>;
+  async fn revoke_share(&self, tenant: Tenant, project_id: Uuid, share_id: Uuid) -> Result<(), AppError>;
+  async fn get_collaborators(&self, tenant: Tenant, project_id: Uuid) -> Result<Vec<Collaborator>, AppError>;
+  async fn remove_collaborator(&self, tenant: Tenant, project_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+  // 招待は招待されたメールアドレスのユーザーだけが、リンクはトークンを知っているユーザーなら誰でも受け入れられる
+  async fn accept_share(&self, user_id: Uuid, token: &str) -> Result<SharedProject, AppError>;
+  async fn get_shared_projects(&self, user_id: Uuid) -> Result<Vec<SharedProject>, AppError>;
+  async fn leave_shared_project(&self, user_id: Uuid, project_id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<S, P, U> ShareService for ShareUsecase<S, P, U>
+where
+  S: ShareRepository + Send + Sync + Clone,
+  P: ProjectRepository + Send + Sync + Clone,
+  U: UserRepository + Send + Sync + Clone,
+{
+  async fn resolve_shared_tenant(&self, user_id: Uuid, project_id: Uuid) -> Result<Tenant, AppError> {
+    let shared = self.repository.find_shared_project(user_id, project_id).await?.ok_or_else(project_not_found)?;
+    Ok(Tenant {
+      organization_id: shared.organization_id,
+      user_id,
+      role: shared.permission.role(),
+      project_id: Some(shared.project_id),
+    })
+  }
+
+  async fn create_share(&self, tenant: Tenant, project_id: Uuid, permission: SharePermission, email: Option<String>) -> Result<IssuedShare, AppError> {
+    self.ensure_manager(tenant, project_id).await?;
+
+    let token = secret_token::generate();
+    let now = Utc::now();
+    let share = ProjectShare {
+      id: Uuid::now_v7(),
+      project_id,
+      permission,
+      expires_at: email.as_ref().map(|_| now + SHARE_INVITATION_TTL),
+      email,
+      token_hash: secret_token::hash(&token),
+      created_by: Some(tenant.user_id),
+      accepted_at: None,
+      created_at: now,
+    };
+    let share = self.repository.create(share).await?;
+    Ok(IssuedShare { share, token })
+  }
+
+  async fn get_shares(&self, tenant: Tenant, project_id: Uuid) -> Result<Vec<ProjectShare>, AppError> {
+    self.ensure_manager(tenant, project_id).await?;
+    Ok(self.repository.find_pending(project_id).await?)
+  }
+
+  async fn revoke_share(&self, tenant: Tenant, project_id: Uuid, share_id: Uuid) -> Result<(), AppError> {
+    self.ensure_manager(tenant, project_id).await?;
+    self.repository
+      .delete(project_id, share_id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Share not found"))
+  }
+
+  async fn get_collaborators(&self, tenant: Tenant, project_id: Uuid) -> Result<Vec<Collaborator>, AppError> {
+    self.find_project(tenant, project_id).await?;
+    Ok(self.repository.find_collaborators(project_id).await?)
+  }
+
+  async fn remove_collaborator(&self, tenant: Tenant, project_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+    self.ensure_manager(tenant, project_id).await?;
+    self.repository
+      .delete_collaborator(project_id, user_id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Collaborator not found"))
+  }
+
+  async fn accept_share(&self, user_id: Uuid, token: &str) -> Result<SharedProject, AppError> {
+    let share = self.repository
+      .find_by_hash(&secret_token::hash(token))
+      .await?
+      .ok_or_else(invalid_share)?;
+    if let Some(email) = &share.email {
+      let user = self.user_repository.find_by_id(user_id).await?.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+      // 他のユーザー宛ての招待は、存在しない共有と区別しない
+      if !email.eq_ignore_ascii_case(&user.email) {
+        return Err(invalid_share());
+      }
+    }
+    if share.accepted_at.is_some() {
+      return Err(AppError::Conflict("Share invitation has already been accepted".to_string()));
+    }
+    if share.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+      return Err(AppError::Gone("Share invitation has expired".to_string()));
+    }
+
+    if !self.repository.accept(&share, user_id).await? {
+      return Err(AppError::Conflict("Share invitation has already been accepted".to_string()));
+    }
+    self.repository.find_shared_project(user_id, share.project_id).await?.ok_or_else(project_not_found)
+  }
+
+  async fn get_shared_projects(&self, user_id: Uuid) -> Result<Vec<SharedProject>, AppError> {
+    Ok(self.repository.find_shared_with(user_id).await?)
+  }
+
+  async fn leave_shared_project(&self, user_id: Uuid, project_id: Uuid) -> Result<(), AppError> {
+    self.repository
+      .delete_collaborator(project_id, user_id)
+      .await
+      .map_err(|err| AppError::from_sqlx(err, "Project not found"))
+  }
+}
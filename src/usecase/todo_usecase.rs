@@ -1,56 +1,560 @@
-use crate::domain::models::todo::Todo;
-use crate::domain::repositories::todo_repository::TodoRepository;
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::audit::AuditContext;
+use crate::domain::models::page::{Page, PageRequest};
+use crate::domain::models::rank::{rank_between, MAX_RANK_LENGTH};
+use crate::domain::models::recurrence::RecurrenceRule;
+use crate::domain::models::search::{highlight, search_terms, SearchHit};
+use crate::domain::models::todo::{validate_recurrence, validate_tags, Todo, TodoDraft, TodoPatch};
+use crate::domain::models::workflow::{BoardColumn, Workflow};
+use crate::domain::policy::{authorize, Permission};
+use crate::domain::query::TodoQuery;
+use crate::domain::validation::ValidationErrors;
+use crate::domain::repositories::project_repository::ProjectRepository;
+use crate::domain::repositories::todo_repository::{RankDirection, TodoChange, TodoRepository};
 use async_trait::async_trait;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 
 #[derive(Clone)]
-pub struct TodoUsecase<T: TodoRepository + Clone> {
+pub struct TodoUsecase<T: TodoRepository + Clone, P: ProjectRepository + Clone> {
   repository: T,
+  project_repository: P,
 }
 
-impl<T: TodoRepository + Clone> TodoUsecase<T> {
-  pub fn new(repository: T) -> Self {
-    Self { repository }
+impl<T: TodoRepository + Clone, P: ProjectRepository + Clone> TodoUsecase<T, P> {
+  pub fn new(repository: T, project_repository: P) -> Self {
+    Self { repository, project_repository }
+  }
+}
+
+fn todo_not_found() -> AppError {
+  AppError::NotFound("Todo not found".to_string())
+}
+
+// ワークフローに存在しないステータス
+fn invalid_status(status: &str) -> AppError {
+  AppError::BadRequest(format!("unknown status '{}'", status))
+}
+
+// ワークフローで許可されていない遷移
+fn transition_not_allowed(from: &str, to: &str) -> AppError {
+  AppError::Conflict(format!("transition from '{}' to '{}' is not allowed", from, to))
+}
+
+// If-Match で指定された版数と一致しない、または読み込んだ後に他で更新された
+fn version_mismatch() -> AppError {
+  AppError::PreconditionFailed("Todo has been modified".to_string())
+}
+
+// rank の振り直しは組織のすべての Todo を書き換えるので、共有されたプロジェクトとしてのアクセスでは行わない
+fn ensure_can_rebalance(tenant: Tenant) -> Result<(), AppError> {
+  if tenant.project_id.is_some() {
+    return Err(AppError::Conflict("Todos must be reordered by a member of the organization before they can be placed here".to_string()));
+  }
+  Ok(())
+}
+
+// 並べ替えの基準。指定した Todo の直前または直後に移動する
+#[derive(Debug, Clone, Copy)]
+pub enum MoveAnchor {
+  Before(Uuid),
+  After(Uuid),
+}
+
+// 一括操作の 1 件分
+#[derive(Debug, Clone)]
+pub enum BulkOperation {
+  Complete(Uuid),
+  Reopen(Uuid),
+  Delete(Uuid),
+  // project_id が None の場合はプロジェクトから外す
+  MoveToProject { id: Uuid, project_id: Option<Uuid> },
+  AddTag { id: Uuid, tag: String },
+}
+
+impl BulkOperation {
+  pub fn id(&self) -> Uuid {
+    match self {
+      BulkOperation::Complete(id) | BulkOperation::Reopen(id) | BulkOperation::Delete(id) => *id,
+      BulkOperation::MoveToProject { id, .. } | BulkOperation::AddTag { id, .. } => *id,
+    }
+  }
+}
+
+impl<T: TodoRepository + Send + Sync + Clone, P: ProjectRepository + Send + Sync + Clone> TodoUsecase<T, P> {
+  // プロジェクトのワークフロー。プロジェクトに属さない Todo は既定のワークフローに従う
+  async fn workflow_for(&self, tenant: Tenant, project_id: Option<Uuid>) -> Result<Workflow, AppError> {
+    let Some(project_id) = project_id else {
+      // 共有されたプロジェクトの Todo は、そのプロジェクトの外に出せない
+      if tenant.project_id.is_some() {
+        return Err(AppError::Forbidden("Todos cannot be moved out of a shared project".to_string()));
+      }
+      return Ok(Workflow::default());
+    };
+    let project = self.project_repository.find_by_id(tenant, project_id).await?.ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+    Ok(project.workflow.0)
+  }
+
+  // 指定されたステータス、または completed の切り替えから遷移先を決め、ワークフローで許可されているか確かめる
+  fn resolve_status(workflow: &Workflow, current: &str, requested: Option<&str>, completed: bool) -> Result<String, AppError> {
+    let target = match requested {
+      Some(status) => workflow
+        .status(status)
+        .ok_or_else(|| invalid_status(status))?,
+      None if completed != workflow.is_done(current) => match workflow.first_reachable(current, completed) {
+        Some(status) => status,
+        None => {
+          let to = workflow.equivalent_status("", completed).key.clone();
+          return Err(transition_not_allowed(current, &to));
+        }
+      },
+      None => return Ok(current.to_string()),
+    };
+
+    if !workflow.can_transition(current, &target.key) {
+      return Err(transition_not_allowed(current, &target.key));
+    }
+    Ok(target.key.clone())
+  }
+
+  // 一括操作を todos に適用した結果の変更を求める。同じ Todo への操作は前の操作の結果に対して行う
+  async fn plan_bulk_change(
+    &self,
+    tenant: Tenant,
+    operation: &BulkOperation,
+    todos: &mut HashMap<Uuid, Option<Todo>>,
+    workflows: &mut HashMap<Option<Uuid>, Workflow>,
+    newly_completed: &mut HashSet<Uuid>,
+  ) -> Result<TodoChange, AppError> {
+    let id = operation.id();
+    if let Entry::Vacant(entry) = todos.entry(id) {
+      entry.insert(self.repository.find_by_id(tenant, id).await?);
+    }
+    let Some(mut todo) = todos.get(&id).cloned().flatten() else {
+      return Err(todo_not_found());
+    };
+
+    let project_id = match operation {
+      BulkOperation::MoveToProject { project_id, .. } => *project_id,
+      _ => todo.project_id,
+    };
+    if let Entry::Vacant(entry) = workflows.entry(project_id) {
+      entry.insert(self.workflow_for(tenant, project_id).await?);
+    }
+    let workflow = &workflows[&project_id];
+
+    match operation {
+      BulkOperation::Complete(_) | BulkOperation::Reopen(_) => {
+        let was_completed = todo.completed;
+        let completed = matches!(operation, BulkOperation::Complete(_));
+        todo.status = Self::resolve_status(workflow, &todo.status, None, completed)?;
+        todo.completed = workflow.is_done(&todo.status);
+        if todo.completed && !was_completed {
+          newly_completed.insert(id);
+        } else if !todo.completed {
+          newly_completed.remove(&id);
+        }
+      }
+      BulkOperation::Delete(_) => {
+        todos.insert(id, None);
+        newly_completed.remove(&id);
+        return Ok(TodoChange::Delete(id));
+      }
+      BulkOperation::MoveToProject { project_id, .. } => {
+        if *project_id != todo.project_id {
+          todo.status = workflow.equivalent_status(&todo.status, todo.completed).key.clone();
+          todo.completed = workflow.is_done(&todo.status);
+          todo.project_id = *project_id;
+        }
+      }
+      BulkOperation::AddTag { tag, .. } => {
+        if !todo.tags.contains(tag) {
+          todo.tags.push(tag.clone());
+          let mut errors = ValidationErrors::new();
+          validate_tags(&mut errors, &todo.tags);
+          errors.into_result()?;
+        }
+      }
+    }
+    // 書き込むと版数が 1 つ上がるため、同じ Todo への次の操作はその版数に対して行う
+    let change = TodoChange::Update { todo: Box::new(todo.clone()), follow_up: None };
+    todo.version += 1;
+    todos.insert(id, Some(todo));
+    Ok(change)
+  }
+
+  // 完了にした繰り返し Todo ごとに、その Todo への最後の変更へ次回分を付ける。
+  // 次回分を用意できなかった操作はエラーとし、完了にもしない
+  async fn plan_bulk_follow_ups(&self, tenant: Tenant, planned: &mut [Result<TodoChange, AppError>], mut newly_completed: HashSet<Uuid>) {
+    let mut indices: Vec<usize> = (0..planned.len())
+      .rev()
+      .filter(|&i| match &planned[i] {
+        Ok(TodoChange::Update { todo, .. }) => todo.completed && newly_completed.remove(&todo.id),
+        _ => false,
+      })
+      .collect();
+    indices.reverse();
+
+    // まだ保存していない次回分同士の rank が重ならないように、前の次回分の後ろに並べる
+    let mut last_rank: Option<String> = None;
+    for i in indices {
+      let Ok(TodoChange::Update { todo, follow_up }) = &mut planned[i] else {
+        continue;
+      };
+      match self.plan_next_occurrence(tenant, todo, last_rank.as_deref()).await {
+        Ok(next) => {
+          if let Some(next) = next {
+            last_rank = Some(next.rank.clone());
+            *follow_up = Some(Box::new(next));
+          }
+        }
+        Err(err) => planned[i] = Err(err),
+      }
+    }
+  }
+
+  // 版数つきの更新が対象なしで終わった場合に、削除されたのか他で更新されたのかを見分ける
+  async fn update_failure(&self, tenant: Tenant, id: Uuid, err: sqlx::Error) -> AppError {
+    match err {
+      sqlx::Error::RowNotFound => match self.repository.find_by_id(tenant, id).await {
+        Ok(Some(_)) => version_mismatch(),
+        Ok(None) => todo_not_found(),
+        Err(err) => err.into(),
+      },
+      err => err.into(),
+    }
+  }
+
+  // 初期ステータスと並び順の末尾を割り当てる。after を指定した場合は、まだ保存していないその rank の後ろに並べる
+  async fn prepare_insert(&self, tenant: Tenant, mut todo: Todo, after: Option<&str>) -> Result<Todo, AppError> {
+    let workflow = self.workflow_for(tenant, todo.project_id).await?;
+    if todo.status.is_empty() {
+      todo.status = workflow.initial_status().key.clone();
+    } else if workflow.status(&todo.status).is_none() {
+      return Err(invalid_status(&todo.status));
+    }
+    todo.completed = workflow.is_done(&todo.status);
+
+    let last = match after {
+      Some(rank) => Some(rank.to_string()),
+      None => self.repository.find_last_rank(tenant).await?,
+    };
+    todo.rank = match rank_between(last.as_deref(), None) {
+      Some(rank) => rank,
+      None => {
+        ensure_can_rebalance(tenant)?;
+        self.repository.rebalance_ranks(tenant).await?;
+        let last = self.repository.find_last_rank(tenant).await?;
+        rank_between(last.as_deref(), None).ok_or_else(|| AppError::Internal("failed to allocate a rank".to_string()))?
+      }
+    };
+    Ok(todo)
+  }
+
+  async fn insert(&self, tenant: Tenant, todo: Todo, audit: &AuditContext) -> Result<Todo, AppError> {
+    let todo = self.prepare_insert(tenant, todo, None).await?;
+    Ok(self.repository.create(tenant, todo, audit).await?)
+  }
+
+  // 基準の Todo と、その隣（移動する Todo 自身は除く）の間に入るキーを求める
+  async fn rank_for_move(&self, tenant: Tenant, id: Uuid, anchor: MoveAnchor) -> Result<Option<String>, AppError> {
+    let (anchor_id, direction) = match anchor {
+      MoveAnchor::Before(anchor_id) => (anchor_id, RankDirection::Before),
+      MoveAnchor::After(anchor_id) => (anchor_id, RankDirection::After),
+    };
+    let anchor_todo = self.repository.find_by_id(tenant, anchor_id).await?.ok_or_else(todo_not_found)?;
+    let neighbor = self.repository.find_adjacent_rank(tenant, &anchor_todo, direction, id).await?;
+
+    let rank = match direction {
+      RankDirection::Before => rank_between(neighbor.as_deref(), Some(&anchor_todo.rank)),
+      RankDirection::After => rank_between(Some(&anchor_todo.rank), neighbor.as_deref()),
+    };
+    Ok(rank)
+  }
+
+  // 繰り返し Todo を完了にするとき、期日をずらした次回分を用意する。
+  // 完了にする書き込みと同じトランザクションで保存するため、ここでは保存しない
+  async fn plan_next_occurrence(&self, tenant: Tenant, todo: &Todo, after: Option<&str>) -> Result<Option<Todo>, AppError> {
+    let (Some(recurrence), Some(due_date), Some(series_id)) = (&todo.recurrence, todo.due_date, todo.series_id) else {
+      return Ok(None);
+    };
+    let Ok(rule) = recurrence.parse::<RecurrenceRule>() else {
+      return Ok(None);
+    };
+
+    let series = self.repository.find_by_series(tenant, series_id).await?;
+    let Some(next_due) = rule.next_after(due_date, series.len() as u32) else {
+      return Ok(None);
+    };
+    // 完了→未完了→完了と切り替えられた場合に同じ回を二重に作らない
+    if series.iter().any(|t| t.due_date == Some(next_due)) {
+      return Ok(None);
+    }
+
+    let next = self.prepare_insert(tenant, todo.next_occurrence(next_due), after).await?;
+    Ok(Some(next))
   }
 }
 
 #[async_trait]
 pub trait TodoService {
-  async fn get_all_todos(&self) -> Result<Vec<Todo>, sqlx::Error>;
-  async fn get_todo_by_id(&self, id: Uuid) -> Result<Option<Todo>, sqlx::Error>;
-  async fn create_todo(&self, title: String, description: String) -> Result<Todo, sqlx::Error>;
-  async fn update_todo(&self, id: Uuid, title: String, description: String, completed: bool) -> Result<Todo, sqlx::Error>;
-  async fn delete_todo(&self, id: Uuid) -> Result<(), sqlx::Error>;
+  async fn get_all_todos(&self, tenant: Tenant, query: &TodoQuery, page: PageRequest) -> Result<Page<Todo>, AppError>;
+  async fn get_todo_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Todo>, AppError>;
+  async fn get_todo_series(&self, tenant: Tenant, series_id: Uuid) -> Result<Vec<Todo>, AppError>;
+  async fn search_todos(&self, tenant: Tenant, query: &str, limit: i64) -> Result<Vec<SearchHit<Todo>>, AppError>;
+  async fn get_board(&self, tenant: Tenant, project_id: Option<Uuid>) -> Result<Vec<BoardColumn>, AppError>;
+  async fn create_todo(&self, tenant: Tenant, draft: TodoDraft, status: Option<String>, audit: &AuditContext) -> Result<Todo, AppError>;
+  // status を省略した場合は completed の切り替えを、ワークフロー上で到達できる完了（未完了）ステータスへの遷移として扱う
+  // expected_version を指定した場合、現在の版数と異なれば PreconditionFailed
+  #[allow(clippy::too_many_arguments)]
+  async fn update_todo(&self, tenant: Tenant, id: Uuid, draft: TodoDraft, status: Option<String>, completed: bool, expected_version: Option<i64>, audit: &AuditContext) -> Result<Todo, AppError>;
+  // patch に含まれる項目だけを変更する。ステータスの扱いは update_todo と同じ
+  async fn patch_todo(&self, tenant: Tenant, id: Uuid, patch: TodoPatch, expected_version: Option<i64>, audit: &AuditContext) -> Result<Todo, AppError>;
+  async fn change_status(&self, tenant: Tenant, id: Uuid, status: String, audit: &AuditContext) -> Result<Todo, AppError>;
+  async fn move_todo(&self, tenant: Tenant, id: Uuid, anchor: MoveAnchor, audit: &AuditContext) -> Result<Todo, AppError>;
+  // ゴミ箱に移動する
+  async fn delete_todo(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), AppError>;
+  async fn restore_todo(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Todo, AppError>;
+  // 1 つのトランザクションでまとめて実行し、操作ごとの結果を返す（削除は None）
+  async fn bulk_update(&self, tenant: Tenant, operations: Vec<BulkOperation>, audit: &AuditContext) -> Result<Vec<Result<Option<Todo>, AppError>>, AppError>;
 }
 
 #[async_trait]
-impl<T: TodoRepository + Send + Sync + Clone> TodoService for TodoUsecase<T> {
-  async fn get_all_todos(&self) -> Result<Vec<Todo>, sqlx::Error> {
-    self.repository.find_all().await
+impl<T: TodoRepository + Send + Sync + Clone, P: ProjectRepository + Send + Sync + Clone> TodoService for TodoUsecase<T, P> {
+  async fn get_all_todos(&self, tenant: Tenant, query: &TodoQuery, page: PageRequest) -> Result<Page<Todo>, AppError> {
+    self.repository.find_all(tenant, query, page).await.map_err(|err| match err {
+      sqlx::Error::RowNotFound => AppError::BadRequest("Invalid cursor".to_string()),
+      err => err.into(),
+    })
+  }
+
+  async fn get_todo_by_id(&self, tenant: Tenant, id: Uuid) -> Result<Option<Todo>, AppError> {
+    Ok(self.repository.find_by_id(tenant, id).await?)
+  }
+
+  async fn get_todo_series(&self, tenant: Tenant, series_id: Uuid) -> Result<Vec<Todo>, AppError> {
+    Ok(self.repository.find_by_series(tenant, series_id).await?)
+  }
+
+  async fn search_todos(&self, tenant: Tenant, query: &str, limit: i64) -> Result<Vec<SearchHit<Todo>>, AppError> {
+    let terms = search_terms(query);
+    if terms.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let hits = self.repository.search(tenant, &terms, limit).await?;
+    Ok(hits
+      .into_iter()
+      .map(|mut hit| {
+        hit.snippet = hit.item.description
+          .as_deref()
+          .and_then(|description| highlight(description, &terms))
+          .or_else(|| highlight(&hit.item.title, &terms));
+        hit
+      })
+      .collect())
+  }
+
+  async fn get_board(&self, tenant: Tenant, project_id: Option<Uuid>) -> Result<Vec<BoardColumn>, AppError> {
+    let project_id = project_id.or(tenant.project_id);
+    let workflow = self.workflow_for(tenant, project_id).await?;
+    let mut todos = self.repository.find_by_project(tenant, project_id).await?;
+
+    let columns = workflow
+      .statuses
+      .into_iter()
+      .map(|status| {
+        let (in_column, rest): (Vec<Todo>, Vec<Todo>) = todos.drain(..).partition(|t| t.status == status.key);
+        todos = rest;
+        BoardColumn { status, todos: in_column }
+      })
+      .collect();
+    Ok(columns)
+  }
+
+  async fn create_todo(&self, tenant: Tenant, draft: TodoDraft, status: Option<String>, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    // 共有されたプロジェクトとしてのアクセスでは、プロジェクトを省略するとそのプロジェクトに作る
+    let draft = TodoDraft { project_id: draft.project_id.or(tenant.project_id), ..draft };
+    let mut new_todo = Todo::new(draft)?;
+    if let Some(status) = status {
+      new_todo.status = status;
+    }
+    self.insert(tenant, new_todo, audit).await
+  }
+
+  async fn update_todo(&self, tenant: Tenant, id: Uuid, draft: TodoDraft, status: Option<String>, completed: bool, expected_version: Option<i64>, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let mut todo = self.repository.find_by_id(tenant, id).await?.ok_or_else(todo_not_found)?;
+    if expected_version.is_some_and(|version| version != todo.version) {
+      return Err(version_mismatch());
+    }
+    let was_completed = todo.completed;
+
+    let workflow = if draft.project_id != todo.project_id {
+      // 別プロジェクトへの移動は遷移ではないため、移動先のワークフローで対応するステータスに置き換える
+      let workflow = self.workflow_for(tenant, draft.project_id).await?;
+      todo.status = match status.as_deref() {
+        Some(status) => workflow
+          .status(status)
+          .ok_or_else(|| invalid_status(status))?
+          .key
+          .clone(),
+        None => workflow.equivalent_status(&todo.status, completed).key.clone(),
+      };
+      todo.project_id = draft.project_id;
+      workflow
+    } else {
+      let workflow = self.workflow_for(tenant, todo.project_id).await?;
+      todo.status = Self::resolve_status(&workflow, &todo.status, status.as_deref(), completed)?;
+      workflow
+    };
+    todo.completed = workflow.is_done(&todo.status);
+    todo.apply(draft)?;
+
+    let follow_up = if todo.completed && !was_completed { self.plan_next_occurrence(tenant, &todo, None).await? } else { None };
+    match self.repository.update(tenant, todo, follow_up, audit).await {
+      Ok(todo) => Ok(todo),
+      Err(err) => Err(self.update_failure(tenant, id, err).await),
+    }
   }
 
-  async fn get_todo_by_id(&self, id: Uuid) -> Result<Option<Todo>, sqlx::Error> {
-    self.repository.find_by_id(id).await
+  async fn patch_todo(&self, tenant: Tenant, id: Uuid, mut patch: TodoPatch, expected_version: Option<i64>, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let todo = self.repository.find_by_id(tenant, id).await?.ok_or_else(todo_not_found)?;
+    if expected_version.is_some_and(|version| version != todo.version) {
+      return Err(version_mismatch());
+    }
+
+    let mut errors = patch.validate().err().unwrap_or_default();
+    // 繰り返し設定は変更後の期日とあわせて検証する
+    if patch.recurrence.is_some() || patch.due_date.is_some() {
+      let due_date = patch.due_date.unwrap_or(todo.due_date);
+      let recurrence = patch.recurrence.as_ref().unwrap_or(&todo.recurrence);
+      validate_recurrence(&mut errors, due_date, recurrence.as_deref());
+      if recurrence.is_some() && todo.series_id.is_none() {
+        patch.series_id = Some(Some(todo.id));
+      }
+    }
+    errors.into_result()?;
+
+    let project_id = patch.project_id.unwrap_or(todo.project_id);
+    let completed = patch.completed.unwrap_or(todo.completed);
+    if project_id != todo.project_id {
+      // 別プロジェクトへの移動は遷移ではないため、移動先のワークフローで対応するステータスに置き換える
+      let workflow = self.workflow_for(tenant, project_id).await?;
+      let status = match patch.status.as_deref() {
+        Some(status) => workflow.status(status).ok_or_else(|| invalid_status(status))?,
+        None => workflow.equivalent_status(&todo.status, completed),
+      };
+      patch.completed = Some(status.done);
+      patch.status = Some(status.key.clone());
+    } else if patch.status.is_some() || patch.completed.is_some() {
+      let workflow = self.workflow_for(tenant, project_id).await?;
+      let status = Self::resolve_status(&workflow, &todo.status, patch.status.as_deref(), completed)?;
+      patch.completed = Some(workflow.is_done(&status));
+      patch.status = Some(status);
+    }
+
+    // 値の変わらない項目は書き込まない
+    patch.project_id = patch.project_id.filter(|project_id| *project_id != todo.project_id);
+    patch.status = patch.status.filter(|status| *status != todo.status);
+    patch.completed = patch.completed.filter(|completed| *completed != todo.completed);
+    if patch.is_empty() {
+      return Ok(todo);
+    }
+
+    let patched = todo.patched(&patch);
+    let follow_up = if patched.completed && !todo.completed { self.plan_next_occurrence(tenant, &patched, None).await? } else { None };
+    match self.repository.patch(tenant, id, todo.version, &patch, follow_up, audit).await {
+      Ok(todo) => Ok(todo),
+      Err(err) => Err(self.update_failure(tenant, id, err).await),
+    }
   }
 
-  async fn create_todo(&self, title: String, description: String) -> Result<Todo, sqlx::Error> {
-    let new_todo = Todo::new(title, description);
-    self.repository.create(new_todo).await
+  async fn change_status(&self, tenant: Tenant, id: Uuid, status: String, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let mut todo = self.repository.find_by_id(tenant, id).await?.ok_or_else(todo_not_found)?;
+    let was_completed = todo.completed;
+
+    let workflow = self.workflow_for(tenant, todo.project_id).await?;
+    todo.status = Self::resolve_status(&workflow, &todo.status, Some(&status), was_completed)?;
+    todo.completed = workflow.is_done(&todo.status);
+
+    let follow_up = if todo.completed && !was_completed { self.plan_next_occurrence(tenant, &todo, None).await? } else { None };
+    match self.repository.update(tenant, todo, follow_up, audit).await {
+      Ok(todo) => Ok(todo),
+      Err(err) => Err(self.update_failure(tenant, id, err).await),
+    }
   }
 
-  async fn update_todo(&self, id: Uuid, title: String, description: String, completed: bool) -> Result<Todo, sqlx::Error> {
-    let existing_todo = self.repository.find_by_id(id).await?;
-    if let Some(mut todo) = existing_todo {
-      todo.title = title;
-      todo.description = Some(description);
-      todo.completed = completed;
-      return self.repository.update(todo).await;
+  async fn move_todo(&self, tenant: Tenant, id: Uuid, anchor: MoveAnchor, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    if self.repository.find_by_id(tenant, id).await?.is_none() {
+      return Err(todo_not_found());
+    }
+
+    // 同じキーが並んでいて間に入れられない場合は、振り直してからもう一度求める
+    let rank = match self.rank_for_move(tenant, id, anchor).await? {
+      Some(rank) => rank,
+      None => {
+        ensure_can_rebalance(tenant)?;
+        self.repository.rebalance_ranks(tenant).await?;
+        self.rank_for_move(tenant, id, anchor).await?.ok_or_else(|| AppError::Internal("failed to allocate a rank".to_string()))?
+      }
+    };
+    // 長くなりすぎたキーは移動の後で振り直すので、振り直せない場合は移動しない
+    if rank.len() > MAX_RANK_LENGTH {
+      ensure_can_rebalance(tenant)?;
     }
-    Err(sqlx::Error::RowNotFound)
+
+    let moved = self.repository.update_rank(tenant, id, &rank, audit).await.map_err(|err| AppError::from_sqlx(err, "Todo not found"))?;
+    if moved.rank.len() > MAX_RANK_LENGTH {
+      // 振り直した後に取り消すと移動前の（振り直し前の）キーに戻るため、元の位置とは限らない
+      self.repository.rebalance_ranks(tenant).await?;
+      return self.repository.find_by_id(tenant, id).await?.ok_or_else(todo_not_found);
+    }
+    Ok(moved)
+  }
+
+  async fn delete_todo(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<(), AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    self.repository.delete(tenant, id, audit).await.map_err(|err| AppError::from_sqlx(err, "Todo not found"))
+  }
+
+  async fn restore_todo(&self, tenant: Tenant, id: Uuid, audit: &AuditContext) -> Result<Todo, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    self.repository.restore(tenant, id, audit).await.map_err(|err| AppError::from_sqlx(err, "Todo not found in trash"))
   }
 
-  async fn delete_todo(&self, id: Uuid) -> Result<(), sqlx::Error> {
-      self.repository.delete(id).await
+  async fn bulk_update(&self, tenant: Tenant, operations: Vec<BulkOperation>, audit: &AuditContext) -> Result<Vec<Result<Option<Todo>, AppError>>, AppError> {
+    authorize(tenant.role, Permission::EditTodos)?;
+    let mut todos = HashMap::new();
+    let mut workflows = HashMap::new();
+    let mut newly_completed = HashSet::new();
+    let mut planned = Vec::with_capacity(operations.len());
+    for operation in &operations {
+      planned.push(self.plan_bulk_change(tenant, operation, &mut todos, &mut workflows, &mut newly_completed).await);
+    }
+
+    self.plan_bulk_follow_ups(tenant, &mut planned, newly_completed).await;
+
+    let changes: Vec<TodoChange> = planned.iter().filter_map(|plan| plan.as_ref().ok().cloned()).collect();
+    let mut written = self.repository.apply_changes(tenant, changes, audit).await?.into_iter();
+    let results: Vec<Result<Option<Todo>, AppError>> = planned
+      .into_iter()
+      .map(|plan| match plan {
+        Ok(change) => match (change, written.next().unwrap_or(Err(sqlx::Error::RowNotFound))) {
+          // 読み込んだ後に他で更新された
+          (TodoChange::Update { .. }, Err(sqlx::Error::RowNotFound)) => Err(version_mismatch()),
+          (_, result) => result.map_err(|err| AppError::from_sqlx(err, "Todo not found")),
+        },
+        Err(err) => Err(err),
+      })
+      .collect();
+    Ok(results)
   }
 }
@@ -0,0 +1,70 @@
+use crate::domain::error::AppError;
+use crate::domain::models::organization::Tenant;
+use crate::domain::models::trash::{PurgeSummary, Trash};
+use crate::domain::repositories::blob_store::BlobStore;
+use crate::domain::repositories::invoice_repository::InvoiceRepository;
+use crate::domain::repositories::todo_repository::TodoRepository;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::warn;
+
+
+#[derive(Clone)]
+pub struct TrashUsecase<T: TodoRepository + Clone, I: InvoiceRepository + Clone> {
+  todo_repository: T,
+  invoice_repository: I,
+  blob_store: Arc<dyn BlobStore>,
+  // ゴミ箱に移動してから物理削除するまでの期間
+  retention: Duration,
+}
+
+impl<T: TodoRepository + Clone, I: InvoiceRepository + Clone> TrashUsecase<T, I> {
+  pub fn new(todo_repository: T, invoice_repository: I, blob_store: Arc<dyn BlobStore>, retention: Duration) -> Self {
+    Self { todo_repository, invoice_repository, blob_store, retention }
+  }
+}
+
+impl<T: TodoRepository + Send + Sync + Clone, I: InvoiceRepository + Send + Sync + Clone> TrashUsecase<T, I> {
+  // 行はすでに削除済みなので、本体の削除に失敗しても参照されることはない
+  async fn delete_blobs(&self, storage_keys: &[String]) {
+    for key in storage_keys {
+      if let Err(err) = self.blob_store.delete(key).await {
+        warn!("failed to remove blob {}: {}", key, err);
+      }
+    }
+  }
+}
+
+#[async_trait]
+pub trait TrashService {
+  fn retention(&self) -> Duration;
+  async fn get_trash(&self, tenant: Tenant) -> Result<Trash, AppError>;
+  // 保持期間を過ぎたものを物理削除する。パージジョブから定期的に呼ばれる
+  async fn purge_expired(&self) -> Result<PurgeSummary, AppError>;
+}
+
+#[async_trait]
+impl<T: TodoRepository + Send + Sync + Clone, I: InvoiceRepository + Send + Sync + Clone> TrashService for TrashUsecase<T, I> {
+  fn retention(&self) -> Duration {
+    self.retention
+  }
+
+  async fn get_trash(&self, tenant: Tenant) -> Result<Trash, AppError> {
+    Ok(Trash {
+      todos: self.todo_repository.find_trashed(tenant).await?,
+      invoices: self.invoice_repository.find_trashed(tenant).await?,
+    })
+  }
+
+  async fn purge_expired(&self) -> Result<PurgeSummary, AppError> {
+    let cutoff = Utc::now() - self.retention;
+
+    let todos = self.todo_repository.purge_deleted_before(cutoff).await?;
+    self.delete_blobs(&todos.storage_keys).await;
+    let invoices = self.invoice_repository.purge_deleted_before(cutoff).await?;
+    self.delete_blobs(&invoices.storage_keys).await;
+
+    Ok(PurgeSummary { todos: todos.count, invoices: invoices.count })
+  }
+}
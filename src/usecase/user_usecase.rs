@@ -0,0 +1,113 @@
+use crate::domain::error::AppError;
+use crate::domain::models::user::{EmailVerification, User, EMAIL_VERIFICATION_TTL_HOURS};
+use crate::domain::repositories::email_sender::EmailSender;
+use crate::domain::repositories::user_repository::UserRepository;
+use crate::usecase::secret_token;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+
+#[derive(Clone)]
+pub struct UserUsecase<T: UserRepository + Clone> {
+  repository: T,
+  email_sender: Arc<dyn EmailSender>,
+}
+
+impl<T: UserRepository + Clone> UserUsecase<T> {
+  pub fn new(repository: T, email_sender: Arc<dyn EmailSender>) -> Self {
+    Self { repository, email_sender }
+  }
+}
+
+fn user_not_found() -> AppError {
+  AppError::NotFound("User not found".to_string())
+}
+
+fn email_in_use() -> AppError {
+  AppError::Conflict("Email is already in use".to_string())
+}
+
+// 他のユーザーのアカウントは参照・変更できない
+fn ensure_self(user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+  if user_id != id {
+    return Err(AppError::Forbidden("You can only access your own account".to_string()));
+  }
+  Ok(())
+}
+
+fn write_error(err: sqlx::Error) -> AppError {
+  match err {
+    sqlx::Error::RowNotFound => user_not_found(),
+    sqlx::Error::Database(db_err) if db_err.is_unique_violation() => email_in_use(),
+    // 所有している Todo・請求書が残っている（ゴミ箱のものを含む）
+    sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => AppError::Conflict("User still owns todos or invoices".to_string()),
+    err => err.into(),
+  }
+}
+
+// user_id はリクエストしたユーザー。自分のアカウントだけを扱える
+#[async_trait]
+pub trait UserService {
+  async fn get_user_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<User>, AppError>;
+  // メールアドレスを変える場合は新しいアドレスに確認用のトークンを送り、確認されるまでは変えない
+  async fn update_user(&self, user_id: Uuid, id: Uuid, name: String, email: String) -> Result<User, AppError>;
+  async fn confirm_email(&self, user_id: Uuid, id: Uuid, token: &str) -> Result<User, AppError>;
+  async fn delete_user(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl<T: UserRepository + Send + Sync + Clone> UserService for UserUsecase<T> {
+  async fn get_user_by_id(&self, user_id: Uuid, id: Uuid) -> Result<Option<User>, AppError> {
+    ensure_self(user_id, id)?;
+    Ok(self.repository.find_by_id(id).await?)
+  }
+
+  async fn update_user(&self, user_id: Uuid, id: Uuid, name: String, email: String) -> Result<User, AppError> {
+    ensure_self(user_id, id)?;
+    let mut user = self.repository.find_by_id(id).await?.ok_or_else(user_not_found)?;
+    user.name = name;
+    // 大文字小文字だけの変更は同じアドレスなので確認しない
+    if user.email.eq_ignore_ascii_case(&email) {
+      user.email = email;
+      return self.repository.update(user).await.map_err(write_error);
+    }
+
+    if self.repository.find_credentials_by_email(&email).await?.is_some() {
+      return Err(email_in_use());
+    }
+    let user = self.repository.update(user).await.map_err(write_error)?;
+    let token = secret_token::generate();
+    let verification = EmailVerification {
+      user_id: user.id,
+      email: email.clone(),
+      token_hash: secret_token::hash(&token),
+      expires_at: Utc::now() + Duration::hours(EMAIL_VERIFICATION_TTL_HOURS),
+    };
+    self.repository.save_email_verification(&verification).await?;
+    let body = format!(
+      "Confirm your new email address by sending this token to POST /api/users/{}/email/verify within {} hours:\n{}",
+      user.id, EMAIL_VERIFICATION_TTL_HOURS, token
+    );
+    self
+      .email_sender
+      .send(&email, "Confirm your email address", &body)
+      .await
+      .map_err(|err| AppError::Internal(err.to_string()))?;
+    Ok(user)
+  }
+
+  async fn confirm_email(&self, user_id: Uuid, id: Uuid, token: &str) -> Result<User, AppError> {
+    ensure_self(user_id, id)?;
+    self.repository.confirm_email(id, &secret_token::hash(token), Utc::now()).await.map_err(|err| match err {
+      sqlx::Error::RowNotFound => AppError::NotFound("Verification token is invalid or expired".to_string()),
+      err => write_error(err),
+    })
+  }
+
+  async fn delete_user(&self, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+    ensure_self(user_id, id)?;
+    self.repository.delete(id).await.map_err(write_error)
+  }
+}